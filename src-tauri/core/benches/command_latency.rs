@@ -0,0 +1,143 @@
+//! 并发调用下的命令延迟基准测试
+//!
+//! 后台任务持续采样的同时，多个调用方并发执行与 Tauri 命令相同的查询
+//! （GPU 信息、采集器状态、进程列表、建议刷新间隔），统计每次调用的延迟分布。
+//!
+//! 对比两种采样方式：
+//! - 写锁采样：采样期间持有监控器写锁并等待 `MINIMUM_CPU_UPDATE_INTERVAL`（旧实现）
+//! - 读锁采样：采样只持有读锁且不休眠，采集器在阻塞线程中运行，查询只读取快照（当前实现）
+//!
+//! 运行：`cargo bench -p system_monitor_core --bench command_latency`
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use system_monitor_core::models::{MonitorConfig, ProcessQuery};
+use system_monitor_core::SystemMonitor;
+use tokio::sync::RwLock;
+
+/// 并发调用方数量
+const CALLERS: usize = 16;
+/// 每个调用方的调用次数
+const CALLS_PER_CALLER: usize = 200;
+/// 两次调用之间的间隔
+const CALL_PAUSE: Duration = Duration::from_millis(2);
+/// 两次采样之间的间隔
+const SAMPLE_PAUSE: Duration = Duration::from_millis(20);
+
+/// 采样方式
+#[derive(Clone, Copy)]
+enum SamplingMode {
+    /// 持有写锁采样并等待 CPU 最小刷新间隔
+    WriteLockWithSleep,
+    /// 只持有读锁采样
+    ReadLock,
+}
+
+impl SamplingMode {
+    fn label(self) -> &'static str {
+        match self {
+            SamplingMode::WriteLockWithSleep => "写锁采样 + 休眠",
+            SamplingMode::ReadLock => "读锁采样",
+        }
+    }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("创建 tokio 运行时失败");
+
+    println!(
+        "{} 个调用方 × {} 次调用，采样间隔 {:?}",
+        CALLERS, CALLS_PER_CALLER, SAMPLE_PAUSE
+    );
+    println!(
+        "{:<18} {:>10} {:>10} {:>10} {:>10}",
+        "采样方式", "p50", "p95", "p99", "max"
+    );
+
+    for mode in [SamplingMode::WriteLockWithSleep, SamplingMode::ReadLock] {
+        let mut latencies = runtime.block_on(run_scenario(mode));
+        latencies.sort();
+        println!(
+            "{:<18} {:>10.3?} {:>10.3?} {:>10.3?} {:>10.3?}",
+            mode.label(),
+            percentile(&latencies, 0.50),
+            percentile(&latencies, 0.95),
+            percentile(&latencies, 0.99),
+            latencies.last().copied().unwrap_or_default(),
+        );
+    }
+}
+
+/// 运行一种采样方式并返回所有调用的延迟
+async fn run_scenario(mode: SamplingMode) -> Vec<Duration> {
+    let monitor = Arc::new(RwLock::new(SystemMonitor::new(MonitorConfig::default())));
+    let running = Arc::new(AtomicBool::new(true));
+
+    let sampler = {
+        let monitor = monitor.clone();
+        let running = running.clone();
+        tokio::spawn(async move {
+            while running.load(Ordering::Relaxed) {
+                match mode {
+                    SamplingMode::WriteLockWithSleep => {
+                        let monitor = monitor.write().await;
+                        let _ = monitor.refresh().await;
+                        tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+                    }
+                    SamplingMode::ReadLock => {
+                        let _ = monitor.read().await.refresh().await;
+                    }
+                }
+                tokio::time::sleep(SAMPLE_PAUSE).await;
+            }
+        })
+    };
+
+    let callers: Vec<_> = (0..CALLERS)
+        .map(|_| {
+            let monitor = monitor.clone();
+            tokio::spawn(async move {
+                let query = ProcessQuery {
+                    limit: Some(20),
+                    ..Default::default()
+                };
+                let mut latencies = Vec::with_capacity(CALLS_PER_CALLER);
+                for _ in 0..CALLS_PER_CALLER {
+                    let started = Instant::now();
+                    {
+                        let monitor = monitor.read().await;
+                        std::hint::black_box(monitor.get_gpu_info());
+                        std::hint::black_box(monitor.get_collector_statuses());
+                        std::hint::black_box(monitor.get_processes(&query).await);
+                        std::hint::black_box(monitor.suggested_refresh_interval());
+                    }
+                    latencies.push(started.elapsed());
+                    tokio::time::sleep(CALL_PAUSE).await;
+                }
+                latencies
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::with_capacity(CALLERS * CALLS_PER_CALLER);
+    for caller in callers {
+        latencies.extend(caller.await.expect("调用方任务失败"));
+    }
+
+    running.store(false, Ordering::Relaxed);
+    let _ = sampler.await;
+    latencies
+}
+
+/// 已排序延迟列表的百分位数
+fn percentile(sorted: &[Duration], quantile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let index = ((sorted.len() - 1) as f64 * quantile).round() as usize;
+    sorted[index]
+}
@@ -1,8 +1,6 @@
 //! 自适应刷新频率管理模块
 //! 根据系统状态和用户使用模式智能调整刷新频率
 
-#![allow(dead_code)]
-
 use crate::models::*;
 use std::time::{Duration, Instant};
 
@@ -123,6 +121,13 @@ pub struct AdaptiveRefreshManager {
     last_high_load: Option<Instant>,
 }
 
+impl Default for AdaptiveRefreshManager {
+    /// 使用默认策略创建管理器
+    fn default() -> Self {
+        Self::new(RefreshStrategy::default())
+    }
+}
+
 impl AdaptiveRefreshManager {
     /// 创建新的自适应刷新管理器
     pub fn new(strategy: RefreshStrategy) -> Self {
@@ -134,11 +139,6 @@ impl AdaptiveRefreshManager {
         }
     }
 
-    /// 使用默认策略创建管理器
-    pub fn default() -> Self {
-        Self::new(RefreshStrategy::default())
-    }
-
     /// 更新策略
     pub fn update_strategy(&mut self, strategy: RefreshStrategy) {
         self.strategy = strategy;
@@ -436,14 +436,3 @@ pub mod strategies {
         RefreshStrategy::Fixed(interval)
     }
 }
-
-// Duration 乘法扩展
-trait DurationExt {
-    fn mul_f32(self, factor: f32) -> Duration;
-}
-
-impl DurationExt for Duration {
-    fn mul_f32(self, factor: f32) -> Duration {
-        Duration::from_millis((self.as_millis() as f32 * factor) as u64)
-    }
-}
@@ -0,0 +1,394 @@
+//! 阈值告警模块
+//!
+//! 在每次采样后按用户定义的规则评估指标，使用独立的触发阈值和解除阈值实现回差，
+//! 避免指标在阈值附近波动时告警反复触发。规则状态依次经历
+//! 未触发 → 等待（满足条件但未达到持续时长）→ 告警中 → 已解除。
+
+use crate::errors::{MonitorError, MonitorResult};
+use crate::models::{AlertComparison, AlertEvent, AlertRule, AlertState, AlertStatus, SystemInfo};
+use log::{error, warn};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 告警状态变化事件名称
+pub const ALERT_EVENT: &str = "alert-state-changed";
+
+/// 路径指向对象时依次尝试的数值字段
+const PRIMARY_VALUE_FIELDS: [&str; 3] = ["temperature", "usage_percent", "value"];
+
+/// 数组元素筛选方式
+#[derive(Debug, Clone, PartialEq)]
+enum PathFilter {
+    /// 选取全部元素
+    All,
+    /// 按下标选取
+    Index(usize),
+    /// 按字段值筛选
+    Equals(String, String),
+}
+
+/// 路径中的一段
+#[derive(Debug, Clone, PartialEq)]
+struct PathSegment {
+    field: String,
+    filter: Option<PathFilter>,
+}
+
+/// 解析指标路径
+///
+/// # Arguments
+/// * `path` - 指标路径，例如 `temperatures[category=cpu-package].temperature`
+///
+/// # Returns
+/// * `MonitorResult<Vec<PathSegment>>` - 路径段列表或配置错误
+fn parse_path(path: &str) -> MonitorResult<Vec<PathSegment>> {
+    let invalid =
+        |reason: &str| MonitorError::ConfigError(format!("无效的指标路径 '{}': {}", path, reason));
+
+    // 按点号切分，方括号内的点号属于筛选值的一部分
+    let mut raw_segments = Vec::new();
+    let mut current = String::new();
+    let mut in_brackets = false;
+    for ch in path.trim().chars() {
+        match ch {
+            '[' if !in_brackets => {
+                in_brackets = true;
+                current.push(ch);
+            }
+            ']' if in_brackets => {
+                in_brackets = false;
+                current.push(ch);
+            }
+            '.' if !in_brackets => raw_segments.push(std::mem::take(&mut current)),
+            _ => current.push(ch),
+        }
+    }
+    if in_brackets {
+        return Err(invalid("方括号未闭合"));
+    }
+    raw_segments.push(current);
+
+    raw_segments
+        .into_iter()
+        .map(|segment| {
+            let (field, filter) = match segment.split_once('[') {
+                Some((field, rest)) => {
+                    let inner = rest
+                        .strip_suffix(']')
+                        .ok_or_else(|| invalid("方括号后不能有其他字符"))?
+                        .trim();
+                    let filter = if inner == "*" {
+                        PathFilter::All
+                    } else if let Some((key, value)) = inner.split_once('=') {
+                        PathFilter::Equals(key.trim().to_string(), value.trim().to_string())
+                    } else {
+                        inner
+                            .parse::<usize>()
+                            .map(PathFilter::Index)
+                            .map_err(|_| invalid("筛选条件应为 *、下标或 key=value"))?
+                    };
+                    (field.trim().to_string(), Some(filter))
+                }
+                None => (segment.trim().to_string(), None),
+            };
+
+            if field.is_empty() {
+                return Err(invalid("字段名不能为空"));
+            }
+            Ok(PathSegment { field, filter })
+        })
+        .collect()
+}
+
+/// 按路径从 JSON 中提取所有匹配的数值
+fn resolve_path(root: &Value, segments: &[PathSegment]) -> Vec<f64> {
+    let mut nodes = vec![root];
+
+    for segment in segments {
+        let mut next = Vec::new();
+        for node in nodes {
+            let Some(child) = node.get(&segment.field) else {
+                continue;
+            };
+            match (&segment.filter, child) {
+                (None, _) => next.push(child),
+                (Some(PathFilter::All), Value::Array(items)) => next.extend(items.iter()),
+                (Some(PathFilter::Index(index)), Value::Array(items)) => {
+                    next.extend(items.get(*index));
+                }
+                (Some(PathFilter::Equals(key, expected)), Value::Array(items)) => {
+                    next.extend(
+                        items.iter().filter(|item| {
+                            item.get(key).is_some_and(|v| value_matches(v, expected))
+                        }),
+                    );
+                }
+                _ => {}
+            }
+        }
+        nodes = next;
+    }
+
+    nodes.into_iter().filter_map(numeric_value).collect()
+}
+
+/// 比较筛选字段的值（字符串按原文比较，其他类型先将筛选值解析为 JSON 再比较）
+fn value_matches(value: &Value, expected: &str) -> bool {
+    match value {
+        Value::String(text) => text == expected,
+        Value::Null => false,
+        other => serde_json::from_str::<Value>(expected).is_ok_and(|parsed| parsed == *other),
+    }
+}
+
+/// 取节点的数值，对象取其主数值字段
+fn numeric_value(node: &Value) -> Option<f64> {
+    match node {
+        Value::Number(number) => number.as_f64(),
+        Value::Object(map) => PRIMARY_VALUE_FIELDS
+            .iter()
+            .find_map(|field| map.get(*field).and_then(Value::as_f64)),
+        _ => None,
+    }
+}
+
+/// 校验规则
+///
+/// # Arguments
+/// * `rule` - 要校验的告警规则
+///
+/// # Returns
+/// * `MonitorResult<()>` - 规则有效或配置错误
+pub fn validate_rule(rule: &AlertRule) -> MonitorResult<()> {
+    if rule.id.trim().is_empty() {
+        return Err(MonitorError::ConfigError("告警规则ID不能为空".to_string()));
+    }
+    if !rule.trigger_threshold.is_finite() || !rule.clear_threshold.is_finite() {
+        return Err(MonitorError::ConfigError(
+            "告警阈值必须是有限数值".to_string(),
+        ));
+    }
+
+    let hysteresis_valid = match rule.comparison {
+        AlertComparison::Above => rule.clear_threshold <= rule.trigger_threshold,
+        AlertComparison::Below => rule.clear_threshold >= rule.trigger_threshold,
+    };
+    if !hysteresis_valid {
+        return Err(MonitorError::ConfigError(format!(
+            "告警规则 '{}' 的解除阈值 {} 与触发阈值 {} 方向不一致",
+            rule.id, rule.clear_threshold, rule.trigger_threshold
+        )));
+    }
+
+    parse_path(&rule.metric).map(|_| ())
+}
+
+/// 已编译的告警规则
+struct CompiledRule {
+    rule: AlertRule,
+    path: Vec<PathSegment>,
+}
+
+impl CompiledRule {
+    /// 计算规则关注的指标值，多个匹配值时取最不利的一个
+    fn evaluate_value(&self, root: &Value) -> Option<f64> {
+        let values = resolve_path(root, &self.path).into_iter();
+        match self.rule.comparison {
+            AlertComparison::Above => values.reduce(f64::max),
+            AlertComparison::Below => values.reduce(f64::min),
+        }
+    }
+
+    fn trigger_met(&self, value: f64) -> bool {
+        match self.rule.comparison {
+            AlertComparison::Above => value > self.rule.trigger_threshold,
+            AlertComparison::Below => value < self.rule.trigger_threshold,
+        }
+    }
+
+    fn clear_met(&self, value: f64) -> bool {
+        match self.rule.comparison {
+            AlertComparison::Above => value < self.rule.clear_threshold,
+            AlertComparison::Below => value > self.rule.clear_threshold,
+        }
+    }
+}
+
+/// 告警引擎
+#[derive(Default)]
+pub struct AlertEngine {
+    /// 按添加顺序排列的规则
+    rules: Vec<CompiledRule>,
+    /// 各规则的运行状态
+    statuses: HashMap<String, AlertStatus>,
+}
+
+impl AlertEngine {
+    /// 使用已持久化的规则创建告警引擎，无效的规则会被跳过
+    ///
+    /// # Arguments
+    /// * `rules` - 告警规则列表
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let mut engine = Self::default();
+        for rule in rules {
+            if let Err(e) = engine.upsert_rule(rule) {
+                warn!("跳过无效的告警规则: {}", e);
+            }
+        }
+        engine
+    }
+
+    /// 获取所有规则
+    pub fn rules(&self) -> Vec<AlertRule> {
+        self.rules
+            .iter()
+            .map(|compiled| compiled.rule.clone())
+            .collect()
+    }
+
+    /// 添加或替换规则（同ID的规则被替换后状态会重置）
+    ///
+    /// # Arguments
+    /// * `rule` - 告警规则
+    ///
+    /// # Returns
+    /// * `MonitorResult<()>` - 成功或配置错误
+    pub fn upsert_rule(&mut self, rule: AlertRule) -> MonitorResult<()> {
+        validate_rule(&rule)?;
+        let compiled = CompiledRule {
+            path: parse_path(&rule.metric)?,
+            rule,
+        };
+
+        self.statuses.remove(&compiled.rule.id);
+        match self
+            .rules
+            .iter_mut()
+            .find(|existing| existing.rule.id == compiled.rule.id)
+        {
+            Some(existing) => *existing = compiled,
+            None => self.rules.push(compiled),
+        }
+        Ok(())
+    }
+
+    /// 删除规则
+    ///
+    /// # Returns
+    /// * `bool` - 规则是否存在
+    pub fn remove_rule(&mut self, id: &str) -> bool {
+        self.statuses.remove(id);
+        let before = self.rules.len();
+        self.rules.retain(|compiled| compiled.rule.id != id);
+        self.rules.len() != before
+    }
+
+    /// 获取所有规则的当前状态（尚未评估过的规则视为未触发）
+    pub fn statuses(&self) -> Vec<AlertStatus> {
+        self.rules
+            .iter()
+            .map(|compiled| {
+                self.statuses
+                    .get(&compiled.rule.id)
+                    .cloned()
+                    .unwrap_or_else(|| AlertStatus {
+                        rule_id: compiled.rule.id.clone(),
+                        state: AlertState::Inactive,
+                        value: None,
+                        since: 0,
+                        last_evaluated: 0,
+                    })
+            })
+            .collect()
+    }
+
+    /// 按最新采样评估所有启用的规则
+    ///
+    /// # Arguments
+    /// * `timestamp` - 采样时间戳（毫秒）
+    /// * `system_info` - 采样数据
+    ///
+    /// # Returns
+    /// * `Vec<AlertEvent>` - 本次评估中发生的状态变化
+    pub fn evaluate(&mut self, timestamp: u64, system_info: &SystemInfo) -> Vec<AlertEvent> {
+        if !self.rules.iter().any(|compiled| compiled.rule.enabled) {
+            return Vec::new();
+        }
+
+        let root = match serde_json::to_value(system_info) {
+            Ok(root) => root,
+            Err(e) => {
+                error!("序列化系统信息失败，跳过告警评估: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut events = Vec::new();
+        for compiled in self.rules.iter().filter(|compiled| compiled.rule.enabled) {
+            let value = compiled.evaluate_value(&root);
+            let status = self
+                .statuses
+                .entry(compiled.rule.id.clone())
+                .or_insert_with(|| AlertStatus {
+                    rule_id: compiled.rule.id.clone(),
+                    state: AlertState::Inactive,
+                    value: None,
+                    since: timestamp,
+                    last_evaluated: timestamp,
+                });
+
+            let previous_state = status.state;
+            let next_state = Self::next_state(compiled, status, value, timestamp);
+
+            status.value = value;
+            status.last_evaluated = timestamp;
+            if next_state != previous_state {
+                status.state = next_state;
+                status.since = timestamp;
+                events.push(AlertEvent {
+                    rule_id: compiled.rule.id.clone(),
+                    rule_name: compiled.rule.name.clone(),
+                    previous_state,
+                    state: next_state,
+                    value,
+                    timestamp,
+                });
+            }
+        }
+
+        events
+    }
+
+    /// 计算规则的下一个状态
+    ///
+    /// 指标暂时缺失时，告警中的规则保持告警，等待中的规则退回未触发
+    fn next_state(
+        compiled: &CompiledRule,
+        status: &AlertStatus,
+        value: Option<f64>,
+        timestamp: u64,
+    ) -> AlertState {
+        let held_long_enough = |since: u64| timestamp.saturating_sub(since) >= compiled.rule.for_ms;
+
+        match (status.state, value) {
+            (AlertState::Firing, Some(value)) if compiled.clear_met(value) => AlertState::Resolved,
+            (AlertState::Firing, _) => AlertState::Firing,
+            (AlertState::Pending, Some(value)) if compiled.trigger_met(value) => {
+                if held_long_enough(status.since) {
+                    AlertState::Firing
+                } else {
+                    AlertState::Pending
+                }
+            }
+            (AlertState::Pending, _) => AlertState::Inactive,
+            (_, Some(value)) if compiled.trigger_met(value) => {
+                if compiled.rule.for_ms == 0 {
+                    AlertState::Firing
+                } else {
+                    AlertState::Pending
+                }
+            }
+            (state, _) => state,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! 无界面模式入口
+//!
+//! 与桌面应用共用采集核心，但不依赖 Tauri 和任何图形库，适合在服务器和 CI 环境中运行
+
+fn main() {
+    std::process::exit(system_monitor_core::headless::run(std::env::args().skip(1)));
+}
@@ -0,0 +1,786 @@
+//! 指标采集器模块
+//!
+//! 定义可插拔的 [`Collector`] 接口和 [`CollectorRegistry`] 注册表。
+//! CPU、内存、网络、磁盘、温度、系统详情和 GPU 都以采集器的形式注册，
+//! 每个采集器独立运行：某个采集器失败时只记录错误并沿用它上一次的结果，不会导致整次采样失败。
+
+use crate::cpu_times::{self, CpuTimes};
+use crate::disk_io::{self, DiskCounters};
+use crate::errors::{MonitorError, MonitorResult};
+use crate::gpu_monitor::GpuMonitor;
+use crate::models::*;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sysinfo::{Components, DiskRefreshKind, Disks, Networks, System};
+
+/// 判断采集器是否到期时允许的误差，避免刷新计时抖动导致采集被推迟一整个刷新周期
+const DUE_TOLERANCE: Duration = Duration::from_millis(50);
+
+/// 采集器的一次采集结果
+#[derive(Debug, Clone)]
+pub enum MetricSet {
+    /// CPU 总使用率、逻辑核心和时间分解
+    Cpu {
+        usage: f32,
+        cores: Vec<CpuCoreInfo>,
+        times: Option<CpuTimeBreakdown>,
+    },
+    /// 内存信息
+    Memory(MemoryInfo),
+    /// 网络信息
+    Network(NetworkInfo),
+    /// 磁盘空间
+    Disk(DiskInfo),
+    /// 块设备IO统计
+    BlockDevices(Vec<BlockDeviceStats>),
+    /// 组件温度
+    Temperature(Vec<TemperatureInfo>),
+    /// 系统详情
+    System(SystemDetails),
+    /// GPU信息
+    Gpu(Vec<GpuInfo>),
+    /// 自定义指标
+    Custom(Vec<CustomMetric>),
+}
+
+impl MetricSet {
+    /// 将采集结果写入系统信息
+    ///
+    /// # Arguments
+    /// * `info` - 正在组装的系统信息
+    pub fn apply_to(self, info: &mut SystemInfo) {
+        match self {
+            MetricSet::Cpu {
+                usage,
+                cores,
+                times,
+            } => {
+                info.cpu_usage = usage;
+                info.cpus = cores;
+                info.cpu_times = times;
+            }
+            MetricSet::Memory(memory) => info.memory = memory,
+            MetricSet::Network(network) => info.network = network,
+            MetricSet::Disk(disk) => info.disk = disk,
+            MetricSet::BlockDevices(devices) => info.block_devices = devices,
+            MetricSet::Temperature(temperatures) => info.temperatures = temperatures,
+            MetricSet::System(details) => info.system = details,
+            MetricSet::Gpu(gpus) => info.gpus = gpus,
+            MetricSet::Custom(metrics) => info.custom_metrics.extend(metrics),
+        }
+    }
+}
+
+/// 采集时可用的共享数据
+pub struct CollectContext<'a> {
+    /// 已刷新 CPU 和内存数据的 sysinfo 系统实例
+    pub system: &'a System,
+    /// 当前监控配置
+    pub config: &'a MonitorConfig,
+}
+
+/// 指标采集器
+///
+/// 实现该接口并通过 [`CollectorRegistry::register`] 注册，即可在不修改监控器的情况下增加新的数据源。
+/// 自定义采集器返回 [`MetricSet::Custom`]，其指标会出现在 `SystemInfo::custom_metrics` 中。
+///
+/// # Examples
+///
+/// ```no_run
+/// use system_monitor_core::collectors::{CollectContext, Collector, MetricSet};
+/// use system_monitor_core::models::CustomMetric;
+/// use system_monitor_core::MonitorError;
+/// use std::time::Duration;
+///
+/// /// 从文件读取机房温度传感器的数值
+/// struct RoomSensorCollector;
+///
+/// impl Collector for RoomSensorCollector {
+///     fn name(&self) -> &str {
+///         "room_sensor"
+///     }
+///
+///     fn interval(&self, _config: &system_monitor_core::models::MonitorConfig) -> Duration {
+///         Duration::from_secs(10)
+///     }
+///
+///     fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+///         let raw = std::fs::read_to_string("/var/run/room-sensor")
+///             .map_err(|e| MonitorError::IoError(e.to_string()))?;
+///         let value = raw
+///             .trim()
+///             .parse()
+///             .map_err(|e| MonitorError::SerializationError(format!("{}", e)))?;
+///         Ok(MetricSet::Custom(vec![CustomMetric {
+///             name: "room_temperature".to_string(),
+///             value,
+///             unit: Some("celsius".to_string()),
+///         }]))
+///     }
+/// }
+/// ```
+pub trait Collector: Send + Sync {
+    /// 采集器名称（在注册表中唯一）
+    fn name(&self) -> &str;
+
+    /// 当前配置下是否启用，默认始终启用
+    fn is_enabled(&self, _config: &MonitorConfig) -> bool {
+        true
+    }
+
+    /// 采集间隔，间隔内的刷新沿用上一次的结果；`Duration::ZERO` 表示每次刷新都采集
+    fn interval(&self, _config: &MonitorConfig) -> Duration {
+        Duration::ZERO
+    }
+
+    /// 执行一次采集
+    ///
+    /// # Arguments
+    /// * `ctx` - 采集上下文
+    ///
+    /// # Returns
+    /// * `Result<MetricSet, MonitorError>` - 采集结果或错误
+    fn collect(&mut self, ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError>;
+}
+
+/// 注册表中的采集器及其缓存结果
+struct RegisteredCollector {
+    collector: Box<dyn Collector>,
+    cached: Option<MetricSet>,
+    last_run: Option<Instant>,
+    last_success: Option<u64>,
+    last_error: Option<String>,
+    consecutive_failures: u32,
+}
+
+/// 采集器注册表
+///
+/// 按注册顺序运行采集器，并缓存每个采集器最近一次成功的结果
+#[derive(Default)]
+pub struct CollectorRegistry {
+    entries: Vec<RegisteredCollector>,
+}
+
+impl CollectorRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含全部内置采集器的注册表
+    ///
+    /// # Arguments
+    /// * `gpu_monitor` - GPU监控器，与监控器共享
+    pub fn with_builtin(gpu_monitor: Arc<GpuMonitor>) -> Self {
+        let builtin: Vec<Box<dyn Collector>> = vec![
+            Box::new(CpuCollector::default()),
+            Box::new(MemoryCollector),
+            Box::new(NetworkCollector::default()),
+            Box::new(DiskCollector::default()),
+            Box::new(DiskIoCollector::default()),
+            Box::new(TemperatureCollector::default()),
+            Box::new(SystemDetailsCollector),
+            Box::new(GpuCollector::new(gpu_monitor)),
+        ];
+
+        let mut registry = Self::new();
+        for collector in builtin {
+            // 内置采集器名称互不相同，注册不会失败
+            let _ = registry.register(collector);
+        }
+        registry
+    }
+
+    /// 注册采集器
+    ///
+    /// # Arguments
+    /// * `collector` - 采集器
+    ///
+    /// # Returns
+    /// * `MonitorResult<()>` - 名称已被占用时返回配置错误
+    pub fn register(&mut self, collector: Box<dyn Collector>) -> MonitorResult<()> {
+        if self.contains(collector.name()) {
+            return Err(MonitorError::ConfigError(format!(
+                "采集器 '{}' 已注册",
+                collector.name()
+            )));
+        }
+
+        log::info!("注册采集器: {}", collector.name());
+        self.entries.push(RegisteredCollector {
+            collector,
+            cached: None,
+            last_run: None,
+            last_success: None,
+            last_error: None,
+            consecutive_failures: 0,
+        });
+        Ok(())
+    }
+
+    /// 移除采集器
+    ///
+    /// # Arguments
+    /// * `name` - 采集器名称
+    ///
+    /// # Returns
+    /// * `bool` - 是否存在并已移除
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.collector.name() != name);
+        self.entries.len() != before
+    }
+
+    /// 是否已注册指定名称的采集器
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.collector.name() == name)
+    }
+
+    /// 获取所有采集器的运行状态
+    ///
+    /// # Arguments
+    /// * `config` - 当前监控配置
+    pub fn statuses(&self, config: &MonitorConfig) -> Vec<CollectorStatus> {
+        self.entries
+            .iter()
+            .map(|entry| CollectorStatus {
+                name: entry.collector.name().to_string(),
+                enabled: entry.collector.is_enabled(config),
+                interval_ms: Some(entry.collector.interval(config))
+                    .filter(|interval| *interval != Duration::MAX)
+                    .map(|interval| interval.as_millis() as u64),
+                last_success: entry.last_success,
+                last_error: entry.last_error.clone(),
+                consecutive_failures: entry.consecutive_failures,
+            })
+            .collect()
+    }
+
+    /// 运行到期的采集器并返回所有已启用采集器的最新结果
+    ///
+    /// 失败（包括 panic）的采集器记录错误后沿用上一次成功的结果；被禁用的采集器清空缓存
+    ///
+    /// # Arguments
+    /// * `ctx` - 采集上下文
+    ///
+    /// # Returns
+    /// * `Vec<MetricSet>` - 按注册顺序排列的采集结果
+    pub fn collect(&mut self, ctx: &CollectContext<'_>) -> Vec<MetricSet> {
+        let now = Instant::now();
+        let mut sets = Vec::with_capacity(self.entries.len());
+
+        for entry in &mut self.entries {
+            if !entry.collector.is_enabled(ctx.config) {
+                entry.cached = None;
+                entry.last_run = None;
+                continue;
+            }
+
+            let interval = entry.collector.interval(ctx.config);
+            let due = entry
+                .last_run
+                .is_none_or(|last| now.duration_since(last) + DUE_TOLERANCE >= interval);
+
+            if due {
+                entry.last_run = Some(now);
+                let collector = &mut entry.collector;
+                let result = panic::catch_unwind(AssertUnwindSafe(|| collector.collect(ctx)))
+                    .unwrap_or_else(|_| {
+                        Err(MonitorError::GenericError("采集器发生 panic".to_string()))
+                    });
+
+                match result {
+                    Ok(set) => {
+                        entry.cached = Some(set);
+                        entry.last_success = Some(timestamp_ms());
+                        entry.last_error = None;
+                        entry.consecutive_failures = 0;
+                    }
+                    Err(e) => {
+                        entry.consecutive_failures += 1;
+                        // 只在首次失败时警告，避免持续失败刷屏
+                        if entry.consecutive_failures == 1 {
+                            log::warn!("采集器 '{}' 采集失败: {}", entry.collector.name(), e);
+                        } else {
+                            log::debug!(
+                                "采集器 '{}' 连续第 {} 次采集失败: {}",
+                                entry.collector.name(),
+                                entry.consecutive_failures,
+                                e
+                            );
+                        }
+                        entry.last_error = Some(e.to_string());
+                    }
+                }
+            }
+
+            if let Some(set) = &entry.cached {
+                sets.push(set.clone());
+            }
+        }
+
+        sets
+    }
+}
+
+/// CPU 采集器（总使用率、逻辑核心和 CPU 时间分解）
+#[derive(Default)]
+pub struct CpuCollector {
+    last_cpu_times: HashMap<String, CpuTimes>,
+}
+
+impl Collector for CpuCollector {
+    fn name(&self) -> &str {
+        "cpu"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_cpu
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.cpu_ms)
+    }
+
+    fn collect(&mut self, ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        let system = ctx.system;
+        if system.cpus().is_empty() {
+            return Err(MonitorError::cpu_info("未检测到逻辑核心"));
+        }
+
+        // 计算与上一次采样之间的时间分解（仅 Linux 可用）
+        let mut breakdowns = HashMap::new();
+        if let Some(current_times) = cpu_times::read_cpu_times() {
+            for (name, times) in &current_times {
+                if let Some(breakdown) = self
+                    .last_cpu_times
+                    .get(name)
+                    .and_then(|previous| times.breakdown_since(previous))
+                {
+                    breakdowns.insert(name.clone(), breakdown);
+                }
+            }
+            self.last_cpu_times = current_times;
+        }
+
+        let cores = system
+            .cpus()
+            .iter()
+            .map(|cpu| CpuCoreInfo {
+                name: cpu.name().to_string(),
+                usage_percent: cpu.cpu_usage(),
+                frequency: cpu.frequency(),
+                // Linux 下 sysinfo 的核心名称与 /proc/stat 一致（cpu0、cpu1...）
+                times: breakdowns.remove(cpu.name()),
+            })
+            .collect();
+
+        Ok(MetricSet::Cpu {
+            usage: system.global_cpu_usage(),
+            cores,
+            times: breakdowns.remove("cpu"),
+        })
+    }
+}
+
+/// 内存采集器
+pub struct MemoryCollector;
+
+impl Collector for MemoryCollector {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_memory
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.memory_ms)
+    }
+
+    fn collect(&mut self, ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        let system = ctx.system;
+        let total = system.total_memory();
+        if total == 0 {
+            return Err(MonitorError::memory_info("无法读取内存总量"));
+        }
+
+        let used = system.used_memory();
+        Ok(MetricSet::Memory(MemoryInfo {
+            total,
+            used,
+            available: system.available_memory(),
+            usage_percent: (used as f32 / total as f32) * 100.0,
+            swap_total: system.total_swap(),
+            swap_used: system.used_swap(),
+        }))
+    }
+}
+
+/// 网络采集器（根据与上一次采集的差值计算速率）
+pub struct NetworkCollector {
+    /// 常驻的网络接口列表，每次采集原地刷新
+    networks: Networks,
+    last_data: HashMap<String, (u64, u64, Instant)>,
+}
+
+impl Default for NetworkCollector {
+    fn default() -> Self {
+        Self {
+            networks: Networks::new_with_refreshed_list(),
+            last_data: HashMap::new(),
+        }
+    }
+}
+
+impl Collector for NetworkCollector {
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_network
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.network_ms)
+    }
+
+    fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        // 刷新已有接口的数据，同时加入新接口、移除已消失的接口
+        self.networks.refresh(true);
+        let current_time = Instant::now();
+
+        let mut interfaces = Vec::new();
+        let mut total_received = 0u64;
+        let mut total_transmitted = 0u64;
+
+        for (interface_name, data) in self.networks.list() {
+            let received = data.total_received();
+            let transmitted = data.total_transmitted();
+
+            total_received += received;
+            total_transmitted += transmitted;
+
+            // 计算速率
+            let (receive_rate, transmit_rate) = match self.last_data.get(interface_name) {
+                Some((last_received, last_transmitted, last_time)) => {
+                    let seconds = current_time.duration_since(*last_time).as_secs_f64();
+                    if seconds > 0.0 {
+                        (
+                            received.saturating_sub(*last_received) as f64 / seconds,
+                            transmitted.saturating_sub(*last_transmitted) as f64 / seconds,
+                        )
+                    } else {
+                        (0.0, 0.0)
+                    }
+                }
+                None => (0.0, 0.0),
+            };
+
+            interfaces.push(NetworkInterface {
+                name: interface_name.clone(),
+                received,
+                transmitted,
+                receive_rate,
+                transmit_rate,
+            });
+        }
+
+        // 更新最后记录的数据（同时移除已消失的接口）
+        self.last_data = interfaces
+            .iter()
+            .map(|interface| {
+                (
+                    interface.name.clone(),
+                    (interface.received, interface.transmitted, current_time),
+                )
+            })
+            .collect();
+
+        Ok(MetricSet::Network(NetworkInfo {
+            interfaces,
+            total_received,
+            total_transmitted,
+        }))
+    }
+}
+
+/// 磁盘空间采集器
+pub struct DiskCollector {
+    /// 常驻的磁盘列表，每次采集原地刷新
+    disks: Disks,
+}
+
+impl Default for DiskCollector {
+    fn default() -> Self {
+        Self {
+            disks: Disks::new_with_refreshed_list_specifics(Self::refresh_kind()),
+        }
+    }
+}
+
+impl DiskCollector {
+    /// 只刷新空间信息，IO统计由 [`DiskIoCollector`] 负责
+    fn refresh_kind() -> DiskRefreshKind {
+        DiskRefreshKind::nothing().with_storage()
+    }
+}
+
+impl Collector for DiskCollector {
+    fn name(&self) -> &str {
+        "disk"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_disk
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.disk_ms)
+    }
+
+    fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        // 重新读取挂载表：新挂载的磁盘加入列表，已卸载的磁盘移除
+        self.disks.refresh_specifics(true, Self::refresh_kind());
+
+        let disks = self
+            .disks
+            .list()
+            .iter()
+            .map(|disk| {
+                let total_space = disk.total_space();
+                let available_space = disk.available_space();
+                let used_space = total_space.saturating_sub(available_space);
+                let usage_percent = if total_space > 0 {
+                    (used_space as f32 / total_space as f32) * 100.0
+                } else {
+                    0.0
+                };
+
+                Disk {
+                    name: disk.name().to_string_lossy().to_string(),
+                    mount_point: disk.mount_point().to_string_lossy().to_string(),
+                    file_system: disk.file_system().to_string_lossy().to_string(),
+                    total_space,
+                    available_space,
+                    used_space,
+                    usage_percent,
+                }
+            })
+            .collect();
+
+        Ok(MetricSet::Disk(DiskInfo { disks }))
+    }
+}
+
+/// 块设备IO采集器（根据与上一次读取的差值计算速率，仅 Linux 可用）
+#[derive(Default)]
+pub struct DiskIoCollector {
+    last_counters: HashMap<String, (DiskCounters, Instant)>,
+}
+
+impl Collector for DiskIoCollector {
+    fn name(&self) -> &str {
+        "disk_io"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_disk
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.disk_io_ms)
+    }
+
+    fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        let Some(counters) = disk_io::read_disk_counters() else {
+            return Ok(MetricSet::BlockDevices(Vec::new()));
+        };
+
+        let current_time = Instant::now();
+        let mut devices: Vec<BlockDeviceStats> = counters
+            .iter()
+            .filter_map(|(name, current)| {
+                let (previous, last_time) = self.last_counters.get(name)?;
+                current.stats_since(name, previous, current_time.duration_since(*last_time))
+            })
+            .collect();
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // 更新最后记录的数据（同时移除已拔出的设备）
+        self.last_counters = counters
+            .into_iter()
+            .map(|(name, current)| (name, (current, current_time)))
+            .collect();
+
+        Ok(MetricSet::BlockDevices(devices))
+    }
+}
+
+/// 温度采集器
+pub struct TemperatureCollector {
+    /// 常驻的传感器列表，每次采集原地刷新
+    components: Components,
+}
+
+impl Default for TemperatureCollector {
+    fn default() -> Self {
+        Self {
+            components: Components::new_with_refreshed_list(),
+        }
+    }
+}
+
+impl TemperatureCollector {
+    /// 根据传感器标签归类温度（前端用于分组）
+    fn classify_category(label: &str) -> String {
+        let normalized = label.to_lowercase();
+        let contains = |keywords: &[&str]| keywords.iter().any(|k| normalized.contains(k));
+
+        if normalized.contains("cpu") && contains(&["package", "tdie", "tctl", "socket", "die"]) {
+            return "cpu-package".to_string();
+        }
+        if normalized.contains("cpu") && contains(&["core", "#", "ccd", "ccx", "thread", "l3"]) {
+            return "cpu-core".to_string();
+        }
+        if contains(&["dimm", "memory", "ram"]) {
+            return "memory".to_string();
+        }
+        if contains(&["gpu", "graphics", "video"]) {
+            return "gpu".to_string();
+        }
+        if contains(&["vrm", "vcore", "soc"]) {
+            return "vrm".to_string();
+        }
+        if contains(&["pch", "chipset", "motherboard", "board"]) {
+            return "motherboard".to_string();
+        }
+        if contains(&["nvme", "ssd", "hdd", "m.2", "m2", "drive", "storage"]) {
+            return "storage".to_string();
+        }
+
+        "other".to_string()
+    }
+}
+
+impl Collector for TemperatureCollector {
+    fn name(&self) -> &str {
+        "temperature"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_temperature
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.temperature_ms)
+    }
+
+    fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        self.components.refresh(true);
+        let temperatures = self
+            .components
+            .list()
+            .iter()
+            .filter_map(|component| {
+                let temperature = component.temperature()?;
+                Some(TemperatureInfo {
+                    label: component.label().to_string(),
+                    temperature,
+                    max: component.max(),
+                    critical: component.critical(),
+                    category: Some(Self::classify_category(component.label())),
+                })
+            })
+            .collect();
+
+        Ok(MetricSet::Temperature(temperatures))
+    }
+}
+
+/// 系统详情采集器（操作系统、主机名和 CPU 型号）
+pub struct SystemDetailsCollector;
+
+impl Collector for SystemDetailsCollector {
+    fn name(&self) -> &str {
+        "system"
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        config
+            .intervals
+            .system_details_ms
+            .map_or(Duration::MAX, Duration::from_millis)
+    }
+
+    fn collect(&mut self, ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        let cpus = ctx.system.cpus();
+
+        Ok(MetricSet::System(SystemDetails {
+            name: System::name(),
+            kernel_version: System::kernel_version(),
+            os_version: System::os_version(),
+            host_name: System::host_name(),
+            cpu_count: cpus.len(),
+            cpu_brand: cpus.first().map(|cpu| cpu.brand().to_string()),
+            // 频率随时变化，由 SystemMonitor 根据 CPU 采集器的核心频率填写
+            cpu_frequency: None,
+        }))
+    }
+}
+
+/// GPU 采集器
+pub struct GpuCollector {
+    gpu_monitor: Arc<GpuMonitor>,
+}
+
+impl GpuCollector {
+    /// 创建GPU采集器
+    ///
+    /// # Arguments
+    /// * `gpu_monitor` - 共享的GPU监控器
+    pub fn new(gpu_monitor: Arc<GpuMonitor>) -> Self {
+        Self { gpu_monitor }
+    }
+}
+
+impl Collector for GpuCollector {
+    fn name(&self) -> &str {
+        "gpu"
+    }
+
+    fn is_enabled(&self, config: &MonitorConfig) -> bool {
+        config.enable_gpu
+    }
+
+    fn interval(&self, config: &MonitorConfig) -> Duration {
+        Duration::from_millis(config.intervals.gpu_ms)
+    }
+
+    fn collect(&mut self, ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        // 没有可用的GPU不算采集失败
+        if !self.gpu_monitor.is_available() {
+            return Ok(MetricSet::Gpu(Vec::new()));
+        }
+
+        let gpus = self.gpu_monitor.get_gpu_info(&ctx.config.gpu_devices);
+        if gpus.is_empty() && ctx.config.gpu_devices.is_empty() {
+            return Err(MonitorError::GpuInfoError("读取GPU数据失败".to_string()));
+        }
+        Ok(MetricSet::Gpu(gpus))
+    }
+}
+
+/// 当前时间戳（毫秒）
+fn timestamp_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
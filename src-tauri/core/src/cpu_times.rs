@@ -0,0 +1,122 @@
+//! CPU 时间分解模块
+//!
+//! 解析 Linux `/proc/stat` 中的 CPU 时间计数器，按两次采样的差值计算
+//! user/system/iowait/steal/irq/softirq 等时间占比。
+
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use crate::models::CpuTimeBreakdown;
+use std::collections::HashMap;
+
+/// `/proc/stat` 中单个 CPU 行的原始计数器（单位：USER_HZ 时钟滴答）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    /// 所有计数器之和
+    fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// 根据上一次的计数器计算时间占比
+    ///
+    /// # Arguments
+    /// * `previous` - 上一次采样的计数器
+    ///
+    /// # Returns
+    /// * `Option<CpuTimeBreakdown>` - 两次采样间隔为零或计数器回绕时返回 None
+    pub fn breakdown_since(&self, previous: &CpuTimes) -> Option<CpuTimeBreakdown> {
+        let total = self.total().checked_sub(previous.total())?;
+        if total == 0 {
+            return None;
+        }
+
+        let percent = |current: u64, last: u64| -> f32 {
+            (current.saturating_sub(last) as f64 / total as f64 * 100.0) as f32
+        };
+
+        Some(CpuTimeBreakdown {
+            user: percent(self.user, previous.user),
+            nice: percent(self.nice, previous.nice),
+            system: percent(self.system, previous.system),
+            idle: percent(self.idle, previous.idle),
+            iowait: percent(self.iowait, previous.iowait),
+            irq: percent(self.irq, previous.irq),
+            softirq: percent(self.softirq, previous.softirq),
+            steal: percent(self.steal, previous.steal),
+        })
+    }
+}
+
+/// 解析 `/proc/stat` 内容
+///
+/// # Arguments
+/// * `content` - `/proc/stat` 文件内容
+///
+/// # Returns
+/// * `HashMap<String, CpuTimes>` - 键为 `cpu`（汇总）或 `cpu0`、`cpu1` 等
+pub fn parse_proc_stat(content: &str) -> HashMap<String, CpuTimes> {
+    let mut result = HashMap::new();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(name) = fields.next() else {
+            continue;
+        };
+        if !name.starts_with("cpu") {
+            continue;
+        }
+
+        let values: Vec<u64> = fields.map(|v| v.parse().unwrap_or(0)).collect();
+        let field = |index: usize| values.get(index).copied().unwrap_or(0);
+
+        result.insert(
+            name.to_string(),
+            CpuTimes {
+                user: field(0),
+                nice: field(1),
+                system: field(2),
+                idle: field(3),
+                iowait: field(4),
+                irq: field(5),
+                softirq: field(6),
+                steal: field(7),
+            },
+        );
+    }
+
+    result
+}
+
+/// 读取当前的 CPU 时间计数器
+///
+/// # Returns
+/// * `Option<HashMap<String, CpuTimes>>` - 非 Linux 平台或读取失败时返回 None
+pub fn read_cpu_times() -> Option<HashMap<String, CpuTimes>> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/stat")
+            .ok()
+            .map(|content| parse_proc_stat(&content))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
@@ -0,0 +1,133 @@
+//! 块设备 IO 统计模块
+//!
+//! 解析 Linux `/proc/diskstats` 中的累计计数器，按两次采样的差值计算
+//! 读写吞吐量、IOPS、平均队列深度和设备利用率。
+
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use crate::models::BlockDeviceStats;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// `/proc/diskstats` 中的扇区大小固定为 512 字节
+const SECTOR_SIZE: u64 = 512;
+
+/// 单个块设备的累计计数器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskCounters {
+    /// 完成的读请求数
+    pub reads_completed: u64,
+    /// 读取的扇区数
+    pub sectors_read: u64,
+    /// 完成的写请求数
+    pub writes_completed: u64,
+    /// 写入的扇区数
+    pub sectors_written: u64,
+    /// 设备处于忙碌状态的时间（毫秒）
+    pub io_ticks_ms: u64,
+    /// 加权的 IO 时间（毫秒），用于计算平均队列深度
+    pub weighted_io_ticks_ms: u64,
+}
+
+impl DiskCounters {
+    /// 根据上一次的计数器计算速率
+    ///
+    /// # Arguments
+    /// * `name` - 设备名称
+    /// * `previous` - 上一次采样的计数器
+    /// * `elapsed` - 两次采样的间隔
+    ///
+    /// # Returns
+    /// * `Option<BlockDeviceStats>` - 间隔为零时返回 None
+    pub fn stats_since(
+        &self,
+        name: &str,
+        previous: &DiskCounters,
+        elapsed: Duration,
+    ) -> Option<BlockDeviceStats> {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return None;
+        }
+        let elapsed_ms = seconds * 1000.0;
+
+        let delta = |current: u64, last: u64| current.saturating_sub(last) as f64;
+
+        Some(BlockDeviceStats {
+            name: name.to_string(),
+            read_bytes_per_sec: delta(self.sectors_read, previous.sectors_read)
+                * SECTOR_SIZE as f64
+                / seconds,
+            write_bytes_per_sec: delta(self.sectors_written, previous.sectors_written)
+                * SECTOR_SIZE as f64
+                / seconds,
+            read_iops: delta(self.reads_completed, previous.reads_completed) / seconds,
+            write_iops: delta(self.writes_completed, previous.writes_completed) / seconds,
+            avg_queue_depth: delta(self.weighted_io_ticks_ms, previous.weighted_io_ticks_ms)
+                / elapsed_ms,
+            utilization_percent: (delta(self.io_ticks_ms, previous.io_ticks_ms) / elapsed_ms
+                * 100.0)
+                .min(100.0) as f32,
+        })
+    }
+}
+
+/// 解析 `/proc/diskstats` 内容
+///
+/// # Arguments
+/// * `content` - `/proc/diskstats` 文件内容
+///
+/// # Returns
+/// * `HashMap<String, DiskCounters>` - 键为设备名称（如 `sda`、`nvme0n1`）
+pub fn parse_diskstats(content: &str) -> HashMap<String, DiskCounters> {
+    let mut result = HashMap::new();
+
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        // 前三列为主设备号、次设备号和设备名称，至少需要 11 个统计字段
+        if fields.len() < 14 {
+            continue;
+        }
+
+        let field = |index: usize| fields[index].parse::<u64>().unwrap_or(0);
+
+        result.insert(
+            fields[2].to_string(),
+            DiskCounters {
+                reads_completed: field(3),
+                sectors_read: field(5),
+                writes_completed: field(7),
+                sectors_written: field(9),
+                io_ticks_ms: field(12),
+                weighted_io_ticks_ms: field(13),
+            },
+        );
+    }
+
+    result
+}
+
+/// 读取物理块设备（不含分区、loop 和 ram 设备）的当前计数器
+///
+/// # Returns
+/// * `Option<HashMap<String, DiskCounters>>` - 非 Linux 平台或读取失败时返回 None
+pub fn read_disk_counters() -> Option<HashMap<String, DiskCounters>> {
+    #[cfg(target_os = "linux")]
+    {
+        let content = std::fs::read_to_string("/proc/diskstats").ok()?;
+        let mut counters = parse_diskstats(&content);
+
+        // /sys/block 下只包含整块设备，分区位于各设备的子目录中
+        counters.retain(|name, _| {
+            !name.starts_with("loop")
+                && !name.starts_with("ram")
+                && std::path::Path::new("/sys/block").join(name).exists()
+        });
+
+        Some(counters)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
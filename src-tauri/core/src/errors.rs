@@ -1,12 +1,14 @@
 //! 错误类型定义模块
 //! 提供具体的错误类型，提升错误处理的质量和可维护性
 
-#![allow(dead_code)]
-
+use serde::Serialize;
 use thiserror::Error;
 
 /// 系统监控器的主要错误类型
-#[derive(Error, Debug, Clone)]
+///
+/// 序列化为 `{ "kind": "...", "message": ... }`，前端可以据此区分错误类型
+#[derive(Error, Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "message")]
 pub enum MonitorError {
     /// 系统信息获取失败
     #[error("系统信息获取失败: {0}")]
@@ -56,6 +58,22 @@ pub enum MonitorError {
     #[error("IO操作错误: {0}")]
     IoError(String),
 
+    /// 进程不存在
+    #[error("进程不存在: PID {0}")]
+    ProcessNotFound(u32),
+
+    /// 权限不足
+    #[error("权限不足: {0}")]
+    PermissionDenied(String),
+
+    /// 当前平台不支持该操作
+    #[error("当前平台不支持该操作: {0}")]
+    UnsupportedOperation(String),
+
+    /// 确认令牌无效或已过期
+    #[error("确认令牌无效: {0}")]
+    InvalidConfirmation(String),
+
     /// 通用错误
     #[error("未知错误: {0}")]
     GenericError(String),
@@ -80,13 +98,17 @@ pub enum GpuMonitorError {
     #[error("GPU访问权限不足")]
     PermissionDenied,
 
-    /// GPU不支持（如AMD/Intel GPU）
+    /// GPU不支持（驱动没有可用的监控后端，如 nouveau、radeon）
     #[error("GPU不支持或驱动不兼容")]
     UnsupportedGpu,
 
     /// NVML库未安装
     #[error("NVML库未安装或不可用")]
     NvmlNotAvailable,
+
+    /// 没有找到任何GPU设备
+    #[error("没有找到GPU设备")]
+    NoGpuFound,
 }
 
 /// 结果类型别名，使用我们的错误类型
@@ -134,6 +156,10 @@ impl MonitorError {
             | MonitorError::SerializationError(_)
             | MonitorError::ConfigError(_)
             | MonitorError::IoError(_) => false,
+            MonitorError::ProcessNotFound(_)
+            | MonitorError::PermissionDenied(_)
+            | MonitorError::UnsupportedOperation(_)
+            | MonitorError::InvalidConfirmation(_) => false,
             MonitorError::GenericError(_) => false,
         }
     }
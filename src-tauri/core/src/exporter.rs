@@ -0,0 +1,582 @@
+//! Prometheus/OpenMetrics 导出模块
+//!
+//! 在独立线程中运行一个轻量 HTTP 服务，`/metrics` 路径以 OpenMetrics 文本格式返回
+//! 后台采样得到的最新系统信息，便于 Prometheus 统一抓取各台机器的数据。
+
+use crate::errors::{MonitorError, MonitorResult};
+use crate::models::{ExporterConfig, GpuThrottleReason, SystemInfo};
+use log::{debug, error, info, warn};
+use std::fmt::Write;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tokio::sync::RwLock;
+
+/// OpenMetrics 文本格式的 Content-Type
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// 指标名称前缀
+const METRIC_PREFIX: &str = "system_monitor";
+
+/// 导出服务读取数据的来源
+#[derive(Clone)]
+pub struct ExporterSource {
+    /// 最新采样结果
+    pub current_data: Arc<RwLock<Option<Arc<SystemInfo>>>>,
+}
+
+impl ExporterSource {
+    /// 生成当前的指标文本，尚无采样数据时返回 None
+    fn render(&self) -> Option<String> {
+        let system_info = self.current_data.blocking_read().clone()?;
+        Some(render_metrics(&system_info))
+    }
+}
+
+/// 运行中的导出服务，析构时停止监听
+pub struct MetricsExporter {
+    server: Arc<Server>,
+    config: ExporterConfig,
+    address: String,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsExporter {
+    /// 启动导出服务
+    ///
+    /// # Arguments
+    /// * `config` - 导出配置
+    /// * `source` - 数据来源
+    ///
+    /// # Returns
+    /// * `MonitorResult<MetricsExporter>` - 运行中的导出服务或错误信息
+    pub fn start(config: ExporterConfig, source: ExporterSource) -> MonitorResult<Self> {
+        let server = Server::http(&config.listen_address).map_err(|e| {
+            MonitorError::IoError(format!("无法监听 {}: {}", config.listen_address, e))
+        })?;
+        let server = Arc::new(server);
+        let address = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|| config.listen_address.clone());
+
+        let worker = server.clone();
+        let handle = std::thread::Builder::new()
+            .name("metrics-exporter".to_string())
+            .spawn(move || {
+                for request in worker.incoming_requests() {
+                    handle_request(request, &source);
+                }
+                debug!("导出服务线程已退出");
+            })
+            .map_err(|e| MonitorError::IoError(format!("无法启动导出服务线程: {}", e)))?;
+
+        info!("指标导出服务已启动: http://{}/metrics", address);
+        Ok(Self {
+            server,
+            config,
+            address,
+            handle: Some(handle),
+        })
+    }
+
+    /// 实际监听的地址
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// 按新配置启动、停止或重启导出服务
+    ///
+    /// # Arguments
+    /// * `slot` - 当前运行的导出服务
+    /// * `config` - 新的导出配置
+    /// * `source` - 数据来源
+    ///
+    /// # Returns
+    /// * `MonitorResult<()>` - 成功或启动失败的错误信息
+    pub fn reconcile(
+        slot: &mut Option<MetricsExporter>,
+        config: &ExporterConfig,
+        source: ExporterSource,
+    ) -> MonitorResult<()> {
+        if slot.as_ref().map(|exporter| &exporter.config) == Some(config) {
+            return Ok(());
+        }
+
+        // 先停止旧服务，释放端口后再按新地址监听
+        slot.take();
+        if config.enabled {
+            *slot = Some(Self::start(config.clone(), source)?);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                error!("导出服务线程异常退出");
+            }
+        }
+        info!("指标导出服务已停止: {}", self.address);
+    }
+}
+
+/// 处理单个 HTTP 请求
+fn handle_request(request: Request, source: &ExporterSource) {
+    let path = request.url().split('?').next().unwrap_or_default();
+
+    let response = match (request.method(), path) {
+        (Method::Get, "/metrics") => match source.render() {
+            Some(body) => Response::from_string(body).with_header(content_type_header()),
+            None => Response::from_string("no sample collected yet\n").with_status_code(503),
+        },
+        (_, "/metrics") => Response::from_string("method not allowed\n").with_status_code(405),
+        _ => Response::from_string("not found\n").with_status_code(404),
+    };
+
+    if let Err(e) = request.respond(response) {
+        warn!("响应抓取请求失败: {}", e);
+    }
+}
+
+fn content_type_header() -> Header {
+    Header::from_bytes(&b"Content-Type"[..], OPENMETRICS_CONTENT_TYPE.as_bytes())
+        .expect("Content-Type 头部是合法的 ASCII")
+}
+
+/// 单个指标族的写入器
+struct MetricFamily<'a> {
+    out: &'a mut String,
+    name: String,
+}
+
+impl<'a> MetricFamily<'a> {
+    /// 写入指标族的 TYPE、UNIT 和 HELP 元数据
+    fn new(out: &'a mut String, name: &str, metric_type: &str, unit: &str, help: &str) -> Self {
+        let name = format!("{}_{}", METRIC_PREFIX, name);
+        let _ = writeln!(out, "# TYPE {} {}", name, metric_type);
+        if !unit.is_empty() {
+            let _ = writeln!(out, "# UNIT {} {}", name, unit);
+        }
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        Self { out, name }
+    }
+
+    /// 写入一个样本，`suffix` 用于计数器的 `_total` 后缀
+    fn sample(&mut self, suffix: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.out, "{}{}", self.name, suffix);
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(self.out, "{{{}}}", labels);
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+    }
+}
+
+/// 按 OpenMetrics 规范格式化样本值
+///
+/// Rust 的 `Display` 将非有限值输出为 `inf`/`NaN`，而 OpenMetrics 要求 `+Inf`、`-Inf` 和 `NaN`
+///
+/// # Arguments
+/// * `value` - 样本值
+///
+/// # Returns
+/// * `String` - 格式化后的样本值
+pub fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// 降频原因及其标签值（与序列化名称一致）
+const THROTTLE_REASON_LABELS: [(GpuThrottleReason, &str); 9] = [
+    (GpuThrottleReason::GpuIdle, "gpu_idle"),
+    (
+        GpuThrottleReason::ApplicationsClocksSetting,
+        "applications_clocks_setting",
+    ),
+    (GpuThrottleReason::SwPowerCap, "sw_power_cap"),
+    (GpuThrottleReason::HwSlowdown, "hw_slowdown"),
+    (GpuThrottleReason::SyncBoost, "sync_boost"),
+    (GpuThrottleReason::SwThermalSlowdown, "sw_thermal_slowdown"),
+    (GpuThrottleReason::HwThermalSlowdown, "hw_thermal_slowdown"),
+    (
+        GpuThrottleReason::HwPowerBrakeSlowdown,
+        "hw_power_brake_slowdown",
+    ),
+    (
+        GpuThrottleReason::DisplayClockSetting,
+        "display_clock_setting",
+    ),
+];
+
+/// 转义标签值中的反斜杠、双引号和换行符
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 将系统信息渲染为 OpenMetrics 文本
+///
+/// # Arguments
+/// * `info` - 系统信息（包含GPU信息）
+///
+/// # Returns
+/// * `String` - 以 `# EOF` 结尾的 OpenMetrics 文本
+pub fn render_metrics(info: &SystemInfo) -> String {
+    let mut out = String::new();
+
+    MetricFamily::new(
+        &mut out,
+        "cpu_usage_percent",
+        "gauge",
+        "percent",
+        "总体CPU使用率",
+    )
+    .sample("", &[], info.cpu_usage as f64);
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "cpu_core_usage_percent",
+        "gauge",
+        "percent",
+        "逻辑核心CPU使用率",
+    );
+    for core in &info.cpus {
+        family.sample("", &[("core", &core.name)], core.usage_percent as f64);
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "cpu_core_frequency_megahertz",
+        "gauge",
+        "megahertz",
+        "逻辑核心当前频率",
+    );
+    for core in &info.cpus {
+        family.sample("", &[("core", &core.name)], core.frequency as f64);
+    }
+
+    let mut family = MetricFamily::new(&mut out, "memory_bytes", "gauge", "bytes", "内存使用情况");
+    family.sample("", &[("type", "total")], info.memory.total as f64);
+    family.sample("", &[("type", "used")], info.memory.used as f64);
+    family.sample("", &[("type", "available")], info.memory.available as f64);
+    family.sample("", &[("type", "swap_total")], info.memory.swap_total as f64);
+    family.sample("", &[("type", "swap_used")], info.memory.swap_used as f64);
+
+    MetricFamily::new(
+        &mut out,
+        "memory_usage_percent",
+        "gauge",
+        "percent",
+        "内存使用率",
+    )
+    .sample("", &[], info.memory.usage_percent as f64);
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "network_received_bytes",
+        "counter",
+        "bytes",
+        "网络接口累计接收字节数",
+    );
+    for iface in &info.network.interfaces {
+        family.sample(
+            "_total",
+            &[("interface", &iface.name)],
+            iface.received as f64,
+        );
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "network_transmitted_bytes",
+        "counter",
+        "bytes",
+        "网络接口累计发送字节数",
+    );
+    for iface in &info.network.interfaces {
+        family.sample(
+            "_total",
+            &[("interface", &iface.name)],
+            iface.transmitted as f64,
+        );
+    }
+
+    let mut family =
+        MetricFamily::new(&mut out, "disk_bytes", "gauge", "bytes", "磁盘空间使用情况");
+    for disk in &info.disk.disks {
+        let labels = |kind| {
+            [
+                ("device", disk.name.as_str()),
+                ("mountpoint", disk.mount_point.as_str()),
+                ("fstype", disk.file_system.as_str()),
+                ("type", kind),
+            ]
+        };
+        family.sample("", &labels("total"), disk.total_space as f64);
+        family.sample("", &labels("used"), disk.used_space as f64);
+        family.sample("", &labels("available"), disk.available_space as f64);
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "disk_usage_percent",
+        "gauge",
+        "percent",
+        "磁盘使用率",
+    );
+    for disk in &info.disk.disks {
+        family.sample(
+            "",
+            &[
+                ("device", disk.name.as_str()),
+                ("mountpoint", disk.mount_point.as_str()),
+            ],
+            disk.usage_percent as f64,
+        );
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "temperature_celsius",
+        "gauge",
+        "celsius",
+        "传感器温度",
+    );
+    for sensor in &info.temperatures {
+        let category = sensor.category.as_deref().unwrap_or("other");
+        family.sample(
+            "",
+            &[("sensor", &sensor.label), ("category", category)],
+            sensor.temperature as f64,
+        );
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "temperature_critical_celsius",
+        "gauge",
+        "celsius",
+        "传感器临界温度",
+    );
+    for sensor in &info.temperatures {
+        if let Some(critical) = sensor.critical {
+            let category = sensor.category.as_deref().unwrap_or("other");
+            family.sample(
+                "",
+                &[("sensor", &sensor.label), ("category", category)],
+                critical as f64,
+            );
+        }
+    }
+
+    // GPU 以设备索引区分，同型号的多块显卡不会混在一起
+    // 不可用的指标直接省略，不输出占位值
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_usage_percent",
+        "gauge",
+        "percent",
+        "GPU使用率",
+    );
+    for gpu in &info.gpus {
+        if let Some(usage) = gpu.usage_percent {
+            let index = gpu.index.to_string();
+            family.sample("", &[("gpu", &index), ("name", &gpu.name)], usage as f64);
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_memory_bytes",
+        "gauge",
+        "bytes",
+        "GPU显存使用情况",
+    );
+    for gpu in &info.gpus {
+        if let Some(memory) = &gpu.memory {
+            let index = gpu.index.to_string();
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name), ("type", "total")],
+                memory.total as f64,
+            );
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name), ("type", "used")],
+                memory.used as f64,
+            );
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_temperature_celsius",
+        "gauge",
+        "celsius",
+        "GPU温度",
+    );
+    for gpu in &info.gpus {
+        if let Some(temperature) = gpu.temperature {
+            let index = gpu.index.to_string();
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name)],
+                temperature as f64,
+            );
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_frequency_megahertz",
+        "gauge",
+        "megahertz",
+        "GPU核心频率",
+    );
+    for gpu in &info.gpus {
+        if let Some(frequency) = gpu.frequency {
+            let index = gpu.index.to_string();
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name)],
+                frequency as f64,
+            );
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_memory_frequency_megahertz",
+        "gauge",
+        "megahertz",
+        "GPU显存频率",
+    );
+    for gpu in &info.gpus {
+        if let Some(frequency) = gpu.memory_frequency {
+            let index = gpu.index.to_string();
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name)],
+                frequency as f64,
+            );
+        }
+    }
+
+    let mut family = MetricFamily::new(&mut out, "gpu_power_watts", "gauge", "watts", "GPU功耗");
+    for gpu in &info.gpus {
+        let index = gpu.index.to_string();
+        for (kind, watts) in [
+            ("draw", gpu.power_draw_watts),
+            ("limit", gpu.power_limit_watts),
+        ] {
+            if let Some(watts) = watts {
+                family.sample(
+                    "",
+                    &[("gpu", &index), ("name", &gpu.name), ("type", kind)],
+                    watts,
+                );
+            }
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_fan_speed_percent",
+        "gauge",
+        "percent",
+        "GPU风扇转速",
+    );
+    for gpu in &info.gpus {
+        if let Some(fan_speed) = gpu.fan_speed_percent {
+            let index = gpu.index.to_string();
+            family.sample(
+                "",
+                &[("gpu", &index), ("name", &gpu.name)],
+                fan_speed as f64,
+            );
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_performance_state",
+        "gauge",
+        "",
+        "GPU性能状态（0 为 P0）",
+    );
+    for gpu in &info.gpus {
+        if let Some(state) = gpu.performance_state {
+            let index = gpu.index.to_string();
+            family.sample("", &[("gpu", &index), ("name", &gpu.name)], state as f64);
+        }
+    }
+
+    // 每个降频原因都输出 0 或 1，便于对某一原因持续生效设置告警
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_throttle_active",
+        "gauge",
+        "",
+        "GPU降频原因是否生效",
+    );
+    for gpu in &info.gpus {
+        if let Some(reasons) = &gpu.throttle_reasons {
+            let index = gpu.index.to_string();
+            for (reason, label) in THROTTLE_REASON_LABELS {
+                family.sample(
+                    "",
+                    &[("gpu", &index), ("name", &gpu.name), ("reason", label)],
+                    if reasons.contains(&reason) { 1.0 } else { 0.0 },
+                );
+            }
+        }
+    }
+
+    let mut family = MetricFamily::new(
+        &mut out,
+        "gpu_pcie_throughput_bytes_per_second",
+        "gauge",
+        "bytes_per_second",
+        "GPU PCIe吞吐量",
+    );
+    for gpu in &info.gpus {
+        let index = gpu.index.to_string();
+        for (direction, bytes) in [
+            ("rx", gpu.pcie_rx_bytes_per_sec),
+            ("tx", gpu.pcie_tx_bytes_per_sec),
+        ] {
+            if let Some(bytes) = bytes {
+                family.sample(
+                    "",
+                    &[
+                        ("gpu", &index),
+                        ("name", &gpu.name),
+                        ("direction", direction),
+                    ],
+                    bytes as f64,
+                );
+            }
+        }
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
@@ -1,36 +1,78 @@
 use crate::errors::MonitorError;
-#[cfg(target_os = "windows")]
+#[cfg(target_os = "linux")]
+use crate::mangohud;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 use crate::models::FrameDataSource;
-use crate::models::FrameStats;
+use crate::models::{FrameCaptureConfig, FrameStats};
 #[cfg(target_os = "windows")]
-use std::path::{Path, PathBuf};
+use std::path::Path;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use std::path::PathBuf;
 #[cfg(target_os = "windows")]
 use std::process::{Command, Stdio};
 use std::time::Duration;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 use std::time::SystemTime;
 
+/// MangoHud 日志超过该时长没有更新时，认为没有正在记录的游戏
+#[cfg(target_os = "linux")]
+const MANGOHUD_STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// 单次读取 MangoHud 日志的最长采集时长
+#[cfg(target_os = "linux")]
+const MANGOHUD_MAX_CAPTURE: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct FrameMonitor {
     #[cfg(target_os = "windows")]
     presentmon_path: Option<PathBuf>,
+    #[cfg(target_os = "linux")]
+    mangohud_log_dir: Option<PathBuf>,
+}
+
+impl Default for FrameMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FrameMonitor {
     pub fn new() -> Self {
+        Self::with_config(&FrameCaptureConfig::default())
+    }
+
+    /// 按配置创建帧率监控器
+    ///
+    /// # Arguments
+    /// * `config` - 帧率采集配置
+    pub fn with_config(config: &FrameCaptureConfig) -> Self {
+        #[cfg(not(target_os = "linux"))]
+        let _ = config;
         Self {
             #[cfg(target_os = "windows")]
             presentmon_path: Self::locate_presentmon(),
+            #[cfg(target_os = "linux")]
+            mangohud_log_dir: config
+                .mangohud_log_dir
+                .as_ref()
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(PathBuf::from)),
         }
     }
 
-    #[allow(dead_code)]
+    /// 当前平台的帧率数据源是否可用
     pub fn is_available(&self) -> bool {
         #[cfg(target_os = "windows")]
         {
             self.presentmon_path.is_some()
         }
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            self.mangohud_log_dir
+                .as_ref()
+                .is_some_and(|dir| dir.is_dir())
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             false
         }
@@ -44,7 +86,11 @@ impl FrameMonitor {
         {
             self.capture_with_presentmon(duration).await
         }
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            self.capture_with_mangohud(duration).await
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             let _ = duration;
             Err(MonitorError::GenericError(
@@ -156,4 +202,62 @@ impl FrameMonitor {
             source: FrameDataSource::PresentMon,
         })
     }
+
+    /// 读取 MangoHud 在采集期间追加的日志行
+    ///
+    /// 需要在 MangoHud 配置中开启日志记录（`output_folder`，以及 `autostart_log` 或日志快捷键）
+    #[cfg(target_os = "linux")]
+    async fn capture_with_mangohud(&self, duration: Duration) -> Result<FrameStats, MonitorError> {
+        let dir = self
+            .mangohud_log_dir
+            .as_ref()
+            .ok_or_else(|| MonitorError::ConfigError("未配置 MangoHud 日志目录".into()))?;
+        let io_error =
+            |err: std::io::Error| MonitorError::IoError(format!("读取 MangoHud 日志失败: {err}"));
+
+        let log = mangohud::latest_log(dir)
+            .map_err(io_error)?
+            .ok_or_else(|| {
+                MonitorError::ConfigError(format!(
+                "未在 {} 找到 MangoHud 日志，请在 MangoHud 配置中开启日志记录并设置 output_folder",
+                dir.display()
+            ))
+            })?;
+        if log
+            .modified
+            .elapsed()
+            .is_ok_and(|elapsed| elapsed > MANGOHUD_STALE_AFTER)
+        {
+            return Err(MonitorError::GenericError(format!(
+                "MangoHud 日志 {} 没有在更新，请确认游戏正在运行并已开始记录",
+                log.path.display()
+            )));
+        }
+
+        let offset = std::fs::metadata(&log.path).map_err(io_error)?.len();
+        let duration = duration.min(MANGOHUD_MAX_CAPTURE);
+        tokio::time::sleep(duration).await;
+
+        let rows = mangohud::read_appended(&log.path, offset).map_err(io_error)?;
+        let samples = log.columns.parse_fps(&rows);
+        if samples.is_empty() {
+            return Err(MonitorError::GenericError(
+                "MangoHud 日志在采集期间没有新的帧数据".into(),
+            ));
+        }
+
+        let average_fps = samples.iter().sum::<f32>() / samples.len() as f32;
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Ok(FrameStats {
+            average_fps,
+            sample_count: samples.len() as u32,
+            duration_ms: duration.as_millis() as u64,
+            timestamp,
+            source: FrameDataSource::MangoHud,
+        })
+    }
 }
@@ -0,0 +1,753 @@
+//! AMD / Intel GPU 后端（Linux sysfs/DRM）
+//!
+//! 读取 `/sys/class/drm/card*/` 下由内核驱动导出的文件：
+//! - amdgpu：`device/gpu_busy_percent`、`device/mem_info_vram_*`，以及 hwmon 中的温度、频率和功耗
+//! - i915：`gt_act_freq_mhz` 实际频率，使用率由 `power/rc6_residency_ms` 的增量计算，
+//!   降频原因来自 `gt/gt0/throttle_reason_*`
+//!
+//! 进程级的显存和引擎占用来自 `/proc/<pid>/fdinfo` 中的 DRM 客户端统计。
+
+use crate::errors::{GpuMonitorError, GpuResult};
+use crate::gpu_monitor::{available, proc_process_name, GpuBackend, PROC_ROOT};
+use crate::models::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+/// DRM 设备在 sysfs 中的默认位置
+pub const DRM_ROOT: &str = "/sys/class/drm";
+
+/// 支持的内核驱动
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DrmDriver {
+    Amdgpu,
+    I915,
+}
+
+/// 一块显卡（`cardN`）
+#[derive(Debug)]
+struct DrmCard {
+    /// 卡目录，如 `/sys/class/drm/card0`
+    card_dir: PathBuf,
+    /// 卡对应的 PCI 设备目录（`cardN/device`）
+    device_dir: PathBuf,
+    /// 设备的第一个 hwmon 目录
+    hwmon_dir: Option<PathBuf>,
+    driver: DrmDriver,
+    name: String,
+    pci_bus_id: Option<String>,
+    uuid: Option<String>,
+}
+
+/// 指标读取结果
+type Reading<T> = Result<T, MetricUnavailableReason>;
+
+/// RC6 驻留时间采样（毫秒，采样时刻）
+type Rc6Sample = (u64, Instant);
+
+/// 按设备索引保存的 RC6 基准
+type Rc6Baselines = Mutex<HashMap<u32, Rc6Sample>>;
+
+/// 按引擎类别累计的忙碌时间（纳秒）：SM（图形/计算）、编码、解码
+type EngineBusy = [Option<u64>; 3];
+
+/// 进程引擎忙碌时间采样（忙碌时间，采样时刻）
+type EngineSample = (EngineBusy, Instant);
+
+/// 基于 sysfs/DRM 的GPU后端
+pub struct DrmBackend {
+    cards: Vec<DrmCard>,
+    /// 周期采样（`device_info`）上一次读取的 i915 RC6 驻留时间
+    last_rc6: Rc6Baselines,
+    /// 详情查询（`detailed_info`）上一次读取的 RC6 驻留时间，与周期采样分开计算，
+    /// 按需查询详情不会缩短周期采样的计算区间
+    last_rc6_details: Rc6Baselines,
+    /// 读取进程 fdinfo 使用的 procfs 目录
+    proc_root: PathBuf,
+    /// 上一次查询时各进程的引擎忙碌时间，按（设备索引，进程ID）保存
+    last_engine_busy: Mutex<HashMap<(u32, u32), EngineSample>>,
+}
+
+impl DrmBackend {
+    /// 在 `/sys/class/drm` 下探测 AMD/Intel 显卡
+    pub fn probe() -> GpuResult<Self> {
+        Self::probe_at(Path::new(DRM_ROOT))
+    }
+
+    /// 在指定目录下探测显卡
+    ///
+    /// # Arguments
+    /// * `root` - 与 `/sys/class/drm` 结构相同的目录
+    ///
+    /// # Returns
+    /// * `GpuResult<DrmBackend>` - 只有不受支持的显卡时返回 `UnsupportedGpu`，
+    ///   没有任何显卡时返回 `NoGpuFound`
+    pub fn probe_at(root: &Path) -> GpuResult<Self> {
+        let entries = fs::read_dir(root).map_err(|e| {
+            GpuMonitorError::DataFetchError(format!("无法读取 {}: {}", root.display(), e))
+        })?;
+
+        // 只保留 cardN，跳过 card0-DP-1 这类显示接口
+        let mut card_numbers: Vec<u32> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.strip_prefix("card"))
+                    .and_then(|number| number.parse().ok())
+            })
+            .collect();
+        card_numbers.sort_unstable();
+
+        let mut cards = Vec::new();
+        let mut unsupported = false;
+        for number in card_numbers {
+            let card_dir = root.join(format!("card{}", number));
+            match DrmCard::open(&card_dir) {
+                Ok(Some(card)) => {
+                    log::info!(
+                        "发现 {:?} 显卡: {} ({})",
+                        card.driver,
+                        card.name,
+                        card_dir.display()
+                    );
+                    cards.push(card);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::info!("跳过 {}: {}", card_dir.display(), e);
+                    unsupported |= matches!(e, GpuMonitorError::UnsupportedGpu);
+                }
+            }
+        }
+
+        if cards.is_empty() {
+            return Err(if unsupported {
+                GpuMonitorError::UnsupportedGpu
+            } else {
+                GpuMonitorError::NoGpuFound
+            });
+        }
+
+        // 记录 RC6 基准，使第一次采样就能计算 i915 使用率
+        let now = Instant::now();
+        let last_rc6: HashMap<u32, Rc6Sample> = cards
+            .iter()
+            .enumerate()
+            .filter_map(|(index, card)| {
+                card.rc6_residency_ms()
+                    .map(|rc6| (index as u32, (rc6, now)))
+            })
+            .collect();
+
+        Ok(Self {
+            cards,
+            last_rc6_details: Mutex::new(last_rc6.clone()),
+            last_rc6: Mutex::new(last_rc6),
+            proc_root: PathBuf::from(PROC_ROOT),
+            last_engine_busy: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 使用指定的 procfs 目录读取进程的 DRM 客户端统计
+    pub fn with_proc_root(mut self, proc_root: impl Into<PathBuf>) -> Self {
+        self.proc_root = proc_root.into();
+        self
+    }
+
+    /// 扫描所有进程的 fdinfo，收集属于指定 PCI 设备的 DRM 客户端
+    ///
+    /// 同一客户端可能被多个文件描述符引用，按 `drm-client-id` 去重
+    fn drm_clients(&self, pci_bus_id: &str) -> HashMap<u64, (u32, DrmClientStats)> {
+        let mut clients = HashMap::new();
+        let Ok(entries) = fs::read_dir(&self.proc_root) else {
+            return clients;
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            // 没有权限读取其他用户的进程时跳过
+            let Ok(fdinfos) = fs::read_dir(entry.path().join("fdinfo")) else {
+                continue;
+            };
+
+            for fdinfo in fdinfos.filter_map(|fdinfo| fdinfo.ok()) {
+                let Some(stats) = fs::read_to_string(fdinfo.path())
+                    .ok()
+                    .and_then(|content| DrmClientStats::parse(&content))
+                else {
+                    continue;
+                };
+                if stats.pdev.eq_ignore_ascii_case(pci_bus_id) {
+                    clients.entry(stats.client_id).or_insert((pid, stats));
+                }
+            }
+        }
+
+        clients
+    }
+
+    /// 获取显卡
+    fn card(&self, index: u32) -> GpuResult<&DrmCard> {
+        self.cards
+            .get(index as usize)
+            .ok_or(GpuMonitorError::DeviceNotFound {
+                device_index: index,
+            })
+    }
+
+    /// 获取GPU使用率
+    ///
+    /// i915 没有直接的忙碌百分比，按两次读取之间 GPU 处于 RC6 省电状态的时间比例推算
+    ///
+    /// # Arguments
+    /// * `baselines` - 计算增量使用的 RC6 基准，读取后更新为本次的读数
+    fn get_gpu_usage(&self, index: u32, card: &DrmCard, baselines: &Rc6Baselines) -> Reading<f32> {
+        match card.driver {
+            DrmDriver::Amdgpu => read_metric(&card.device_dir.join("gpu_busy_percent"))
+                .map(|busy| busy.min(100) as f32),
+            DrmDriver::I915 => {
+                let rc6 = read_metric(&card.card_dir.join("power/rc6_residency_ms"))?;
+                let now = Instant::now();
+                let previous = baselines
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(index, (rc6, now));
+
+                // 还没有上一次的读数时无法计算
+                let (last_rc6, last_time) = previous.ok_or(MetricUnavailableReason::ReadFailed)?;
+                let elapsed_ms = now.duration_since(last_time).as_secs_f64() * 1000.0;
+                if elapsed_ms <= 0.0 {
+                    return Err(MetricUnavailableReason::ReadFailed);
+                }
+                let idle = rc6.saturating_sub(last_rc6) as f64 / elapsed_ms;
+                Ok(((1.0 - idle) * 100.0).clamp(0.0, 100.0) as f32)
+            }
+        }
+    }
+}
+
+impl DrmCard {
+    /// 读取显卡的驱动和标识信息
+    ///
+    /// # Returns
+    /// * `GpuResult<Option<DrmCard>>` - 由其他后端负责的显卡（NVIDIA）返回 `None`
+    fn open(card_dir: &Path) -> GpuResult<Option<Self>> {
+        let device_dir = card_dir.join("device");
+        let uevent = fs::read_to_string(device_dir.join("uevent"))
+            .map_err(|e| GpuMonitorError::DataFetchError(format!("无法读取 uevent: {}", e)))?;
+        let field = |key: &str| {
+            uevent.lines().find_map(|line| {
+                line.strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix('='))
+                    .map(|value| value.trim().to_string())
+            })
+        };
+
+        let driver = match field("DRIVER").as_deref() {
+            Some("amdgpu") => DrmDriver::Amdgpu,
+            Some("i915") => DrmDriver::I915,
+            // NVIDIA 显卡由 NVML 后端监控
+            Some("nvidia") => return Ok(None),
+            _ => return Err(GpuMonitorError::UnsupportedGpu),
+        };
+
+        let pci_id = field("PCI_ID").unwrap_or_default();
+        let vendor = match driver {
+            DrmDriver::Amdgpu => "AMD",
+            DrmDriver::I915 => "Intel",
+        };
+        let name = read_string(&device_dir.join("product_name"))
+            .unwrap_or_else(|| format!("{} GPU [{}]", vendor, pci_id));
+
+        let hwmon_dir = fs::read_dir(device_dir.join("hwmon"))
+            .ok()
+            .and_then(|entries| {
+                let mut dirs: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .collect();
+                dirs.sort();
+                dirs.into_iter().next()
+            });
+
+        Ok(Some(Self {
+            card_dir: card_dir.to_path_buf(),
+            uuid: read_string(&device_dir.join("unique_id")),
+            device_dir,
+            hwmon_dir,
+            driver,
+            name,
+            pci_bus_id: field("PCI_SLOT_NAME"),
+        }))
+    }
+
+    /// 读取 hwmon 中的数值
+    fn hwmon_value(&self, file: &str) -> Option<u64> {
+        self.hwmon_metric(file).ok()
+    }
+
+    /// 读取 hwmon 中的指标，没有 hwmon 目录时视为不支持
+    fn hwmon_metric(&self, file: &str) -> Reading<u64> {
+        match &self.hwmon_dir {
+            Some(dir) => read_metric(&dir.join(file)),
+            None => Err(MetricUnavailableReason::NotSupported),
+        }
+    }
+
+    /// i915 RC6 累计驻留时间（毫秒）
+    fn rc6_residency_ms(&self) -> Option<u64> {
+        match self.driver {
+            DrmDriver::I915 => read_u64(&self.card_dir.join("power/rc6_residency_ms")),
+            DrmDriver::Amdgpu => None,
+        }
+    }
+
+    /// 获取显存信息（集成显卡没有独立显存，不导出这些文件）
+    fn get_memory_info(&self) -> Reading<GpuMemoryInfo> {
+        let total = read_metric(&self.device_dir.join("mem_info_vram_total"))?;
+        let used = read_metric(&self.device_dir.join("mem_info_vram_used"))?;
+        Ok(GpuMemoryInfo::new(total, used))
+    }
+
+    /// 获取GPU温度（hwmon 单位为毫摄氏度）
+    fn get_temperature(&self) -> Reading<f32> {
+        self.hwmon_metric("temp1_input")
+            .map(|millidegrees| millidegrees as f32 / 1000.0)
+    }
+
+    /// 获取GPU图形频率（MHz）
+    fn get_frequency(&self) -> Reading<u64> {
+        match self.driver {
+            // hwmon 单位为 Hz
+            DrmDriver::Amdgpu => self.hwmon_metric("freq1_input").map(|hz| hz / 1_000_000),
+            DrmDriver::I915 => read_metric(&self.card_dir.join("gt_act_freq_mhz"))
+                .or_else(|_| read_metric(&self.card_dir.join("gt_cur_freq_mhz"))),
+        }
+    }
+
+    /// 获取各时钟域的频率
+    fn get_clocks(&self) -> Vec<GpuClockInfo> {
+        let mhz = |value: Option<u64>| value.map(|mhz| mhz as u32);
+        match self.driver {
+            DrmDriver::Amdgpu => [
+                (GpuClockDomain::Graphics, "freq1_input"),
+                (GpuClockDomain::Memory, "freq2_input"),
+            ]
+            .into_iter()
+            .filter_map(|(domain, file)| {
+                self.hwmon_value(file).map(|hz| GpuClockInfo {
+                    domain,
+                    current_mhz: Some((hz / 1_000_000) as u32),
+                    max_mhz: None,
+                })
+            })
+            .collect(),
+            DrmDriver::I915 => vec![GpuClockInfo {
+                domain: GpuClockDomain::Graphics,
+                current_mhz: mhz(self.get_frequency().ok()),
+                max_mhz: mhz(read_u64(&self.card_dir.join("gt_RP0_freq_mhz"))),
+            }],
+        }
+    }
+
+    /// 获取 amdgpu RAS 统计的显存 ECC 错误计数
+    fn get_ecc_errors(&self) -> Option<GpuEccErrors> {
+        // 格式为 "ue: 0\nce: 0"
+        let content = read_string(&self.device_dir.join("ras/umc_err_count"))?;
+        let count = |key: &str| {
+            content.lines().find_map(|line| {
+                line.strip_prefix(key)
+                    .and_then(|rest| rest.trim().parse::<u64>().ok())
+            })
+        };
+
+        Some(GpuEccErrors {
+            corrected: count("ce:")?,
+            uncorrected: count("ue:")?,
+        })
+    }
+
+    /// 获取显存频率（MHz，hwmon 单位为 Hz）
+    fn get_memory_frequency(&self) -> Reading<u64> {
+        match self.driver {
+            DrmDriver::Amdgpu => self.hwmon_metric("freq2_input").map(|hz| hz / 1_000_000),
+            // 集成显卡使用系统内存
+            DrmDriver::I915 => Err(MetricUnavailableReason::NotSupported),
+        }
+    }
+
+    /// 获取功耗（W，hwmon 单位为微瓦）
+    fn get_power_watts(&self) -> Reading<f64> {
+        self.hwmon_metric("power1_average")
+            .or_else(|_| self.hwmon_metric("power1_input"))
+            .map(|microwatts| microwatts as f64 / 1_000_000.0)
+    }
+
+    /// 获取功耗上限（W）
+    fn get_power_limit_watts(&self) -> Reading<f64> {
+        self.hwmon_metric("power1_cap")
+            .map(|microwatts| microwatts as f64 / 1_000_000.0)
+    }
+
+    /// 获取风扇转速百分比（pwm1 取值范围为 0-255）
+    fn get_fan_speed_percent(&self) -> Reading<u32> {
+        self.hwmon_metric("pwm1")
+            .map(|pwm| (pwm.min(255) * 100 / 255) as u32)
+    }
+
+    /// 获取当前生效的降频原因
+    ///
+    /// 只有 i915 通过 `gt/gt0/throttle_reason_*` 导出，每个文件为 0 或 1
+    fn get_throttle_reasons(&self) -> Reading<Vec<GpuThrottleReason>> {
+        if self.driver != DrmDriver::I915 {
+            return Err(MetricUnavailableReason::NotSupported);
+        }
+
+        let gt_dir = self.card_dir.join("gt/gt0");
+        // 汇总状态为 0 时没有任何降频
+        if read_metric(&gt_dir.join("throttle_reason_status"))? == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut reasons = Vec::new();
+        for (file, reason) in [
+            ("throttle_reason_pl1", GpuThrottleReason::SwPowerCap),
+            ("throttle_reason_pl2", GpuThrottleReason::SwPowerCap),
+            ("throttle_reason_pl4", GpuThrottleReason::HwSlowdown),
+            ("throttle_reason_prochot", GpuThrottleReason::HwSlowdown),
+            ("throttle_reason_vr_tdc", GpuThrottleReason::HwSlowdown),
+            ("throttle_reason_ratl", GpuThrottleReason::SwThermalSlowdown),
+            (
+                "throttle_reason_thermal",
+                GpuThrottleReason::HwThermalSlowdown,
+            ),
+            (
+                "throttle_reason_vr_thermalert",
+                GpuThrottleReason::HwThermalSlowdown,
+            ),
+        ] {
+            if read_u64(&gt_dir.join(file)).is_some_and(|active| active != 0)
+                && !reasons.contains(&reason)
+            {
+                reasons.push(reason);
+            }
+        }
+        Ok(reasons)
+    }
+}
+
+impl GpuBackend for DrmBackend {
+    fn name(&self) -> &str {
+        "drm"
+    }
+
+    fn device_count(&self) -> u32 {
+        self.cards.len() as u32
+    }
+
+    fn device_name(&self, index: u32) -> GpuResult<String> {
+        Ok(self.card(index)?.name.clone())
+    }
+
+    fn device_id(&self, index: u32) -> GpuResult<GpuDeviceId> {
+        let card = self.card(index)?;
+        Ok(GpuDeviceId {
+            name: card.name.clone(),
+            uuid: card.uuid.clone(),
+            pci_bus_id: card.pci_bus_id.clone(),
+        })
+    }
+
+    fn device_info(&self, index: u32) -> GpuResult<GpuInfo> {
+        let card = self.card(index)?;
+        let mut unavailable = BTreeMap::new();
+        // sysfs 没有与 NVIDIA P 状态对应的概念；
+        // amdgpu 的 pcie_bw 每次读取都会阻塞一秒，不适合周期采样
+        for metric in [GpuMetric::PerformanceState, GpuMetric::PcieThroughput] {
+            unavailable.insert(metric, MetricUnavailableReason::NotSupported);
+        }
+
+        Ok(GpuInfo {
+            index,
+            name: card.name.clone(),
+            uuid: card.uuid.clone(),
+            pci_bus_id: card.pci_bus_id.clone(),
+            usage_percent: available(
+                &mut unavailable,
+                GpuMetric::Usage,
+                self.get_gpu_usage(index, card, &self.last_rc6),
+            ),
+            memory: available(&mut unavailable, GpuMetric::Memory, card.get_memory_info()),
+            temperature: available(
+                &mut unavailable,
+                GpuMetric::Temperature,
+                card.get_temperature(),
+            ),
+            frequency: available(&mut unavailable, GpuMetric::Frequency, card.get_frequency()),
+            memory_frequency: available(
+                &mut unavailable,
+                GpuMetric::MemoryFrequency,
+                card.get_memory_frequency(),
+            ),
+            power_draw_watts: available(
+                &mut unavailable,
+                GpuMetric::PowerDraw,
+                card.get_power_watts(),
+            ),
+            power_limit_watts: available(
+                &mut unavailable,
+                GpuMetric::PowerLimit,
+                card.get_power_limit_watts(),
+            ),
+            fan_speed_percent: available(
+                &mut unavailable,
+                GpuMetric::FanSpeed,
+                card.get_fan_speed_percent(),
+            ),
+            performance_state: None,
+            throttle_reasons: available(
+                &mut unavailable,
+                GpuMetric::ThrottleReasons,
+                card.get_throttle_reasons(),
+            ),
+            pcie_rx_bytes_per_sec: None,
+            pcie_tx_bytes_per_sec: None,
+            unavailable,
+        })
+    }
+
+    fn detailed_info(&self, index: u32) -> GpuResult<GpuDetails> {
+        let card = self.card(index)?;
+        let memory = card.get_memory_info().ok();
+
+        Ok(GpuDetails {
+            index,
+            name: card.name.clone(),
+            uuid: card.uuid.clone(),
+            pci_bus_id: card.pci_bus_id.clone(),
+            // 只有树外模块会导出版本号，内核自带驱动的版本即内核版本
+            driver_version: read_string(&card.device_dir.join("driver/module/version")),
+            vbios_version: read_string(&card.device_dir.join("vbios_version")),
+            pcie_generation: read_string(&card.device_dir.join("current_link_speed"))
+                .and_then(|speed| pcie_generation(&speed)),
+            pcie_width: read_u64(&card.device_dir.join("current_link_width"))
+                .map(|width| width as u32),
+            gpu_utilization: self.get_gpu_usage(index, card, &self.last_rc6_details).ok(),
+            memory_utilization: read_u64(&card.device_dir.join("mem_busy_percent"))
+                .map(|busy| busy.min(100) as f32),
+            memory_total: memory.as_ref().map(|memory| memory.total),
+            memory_used: memory.as_ref().map(|memory| memory.used),
+            temperature: card.get_temperature().ok(),
+            power_draw_watts: card.get_power_watts().ok(),
+            power_limit_watts: card.get_power_limit_watts().ok(),
+            fan_speed_percent: card.get_fan_speed_percent().ok(),
+            clocks: card.get_clocks(),
+            throttle_reasons: card.get_throttle_reasons().unwrap_or_default(),
+            ecc_errors: card.get_ecc_errors(),
+            encoder_utilization: None,
+            decoder_utilization: None,
+        })
+    }
+
+    fn processes(&self, index: u32) -> GpuResult<Vec<GpuProcessInfo>> {
+        let card = self.card(index)?;
+        // 没有 PCI 地址时无法把客户端对应到显卡
+        let pci_bus_id = card
+            .pci_bus_id
+            .as_deref()
+            .ok_or(GpuMonitorError::UnsupportedGpu)?;
+        let now = Instant::now();
+
+        // 按进程汇总显存和引擎忙碌时间
+        let mut per_process: BTreeMap<u32, (Option<u64>, EngineBusy)> = BTreeMap::new();
+        for (pid, stats) in self.drm_clients(pci_bus_id).into_values() {
+            let (memory, busy) = per_process.entry(pid).or_default();
+            if let Some(bytes) = stats.memory {
+                *memory = Some(memory.unwrap_or(0) + bytes);
+            }
+            for (total, client) in busy.iter_mut().zip(stats.engine_busy) {
+                if let Some(ns) = client {
+                    *total = Some(total.unwrap_or(0) + ns);
+                }
+            }
+        }
+
+        // 利用率由与上一次查询之间的忙碌时间增量计算，第一次查询时为 None
+        let mut last_engine_busy = self
+            .last_engine_busy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let processes = per_process
+            .into_iter()
+            .map(|(pid, (used_memory, busy))| {
+                let previous = last_engine_busy.get(&(index, pid));
+                let utilization = |class: usize| {
+                    let (last_busy, last_time) = previous?;
+                    let elapsed_ns = now.duration_since(*last_time).as_nanos() as f64;
+                    let delta = busy[class]?.checked_sub(last_busy[class]?)?;
+                    (elapsed_ns > 0.0)
+                        .then(|| (delta as f64 / elapsed_ns * 100.0).min(100.0) as f32)
+                };
+
+                let process = GpuProcessInfo {
+                    pid,
+                    name: proc_process_name(&self.proc_root, pid),
+                    used_memory,
+                    sm_utilization: utilization(0),
+                    encoder_utilization: utilization(1),
+                    decoder_utilization: utilization(2),
+                };
+                (process, busy)
+            })
+            .collect::<Vec<_>>();
+
+        // 只保留仍在使用该设备的进程
+        last_engine_busy.retain(|(card_index, _), _| *card_index != index);
+        for (process, busy) in &processes {
+            last_engine_busy.insert((index, process.pid), (*busy, now));
+        }
+
+        Ok(processes.into_iter().map(|(process, _)| process).collect())
+    }
+}
+
+/// 一个 DRM 客户端在 fdinfo 中导出的统计
+#[derive(Debug)]
+struct DrmClientStats {
+    /// 设备 PCI 地址
+    pdev: String,
+    /// 客户端ID（同一设备内唯一）
+    client_id: u64,
+    /// 占用的显存（字节）
+    memory: Option<u64>,
+    /// 各引擎类别累计的忙碌时间（纳秒）
+    engine_busy: EngineBusy,
+}
+
+impl DrmClientStats {
+    /// 解析 `/proc/<pid>/fdinfo/<fd>`，不是 DRM 文件时返回 `None`
+    ///
+    /// 格式见内核文档 drm-usage-stats，例如：
+    /// `drm-pdev: 0000:03:00.0`、`drm-engine-gfx: 123456 ns`、`drm-memory-vram: 1024 KiB`
+    fn parse(content: &str) -> Option<Self> {
+        let mut pdev = None;
+        let mut client_id = None;
+        let mut memory_keys: HashMap<&str, u64> = HashMap::new();
+        let mut engine_busy: EngineBusy = [None; 3];
+
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key {
+                "drm-pdev" => pdev = Some(value.to_string()),
+                "drm-client-id" => client_id = value.parse().ok(),
+                _ => {
+                    if let Some(engine) = key.strip_prefix("drm-engine-") {
+                        let (Some(class), Some(ns)) = (
+                            engine_class(engine),
+                            value
+                                .strip_suffix("ns")
+                                .and_then(|ns| ns.trim().parse::<u64>().ok()),
+                        ) else {
+                            continue;
+                        };
+                        engine_busy[class] = Some(engine_busy[class].unwrap_or(0) + ns);
+                    } else if key.starts_with("drm-") {
+                        if let Some(bytes) = parse_memory(value) {
+                            memory_keys.insert(key, bytes);
+                        }
+                    }
+                }
+            }
+        }
+
+        // 新内核使用 drm-resident-*，旧版 amdgpu 使用 drm-memory-vram，i915 独显为 local0
+        let memory = [
+            "drm-resident-vram",
+            "drm-memory-vram",
+            "drm-resident-local0",
+        ]
+        .iter()
+        .find_map(|key| memory_keys.get(key).copied());
+
+        Some(Self {
+            pdev: pdev?,
+            client_id: client_id?,
+            memory,
+            engine_busy,
+        })
+    }
+}
+
+/// 引擎所属类别：0 为 SM（图形/计算），1 为编码，2 为解码
+fn engine_class(engine: &str) -> Option<usize> {
+    match engine {
+        "gfx" | "compute" | "render" => Some(0),
+        // i915 的 video 引擎同时负责编解码，计入解码
+        "dec" | "video" => Some(2),
+        engine if engine.starts_with("enc") => Some(1),
+        _ => None,
+    }
+}
+
+/// 解析 fdinfo 中的内存数值（如 "1024 KiB"），单位缺省为字节
+fn parse_memory(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let number: u64 = parts.next()?.parse().ok()?;
+    let multiplier = match parts.next() {
+        None => 1,
+        Some("KiB") => 1024,
+        Some("MiB") => 1024 * 1024,
+        Some(_) => return None,
+    };
+    Some(number * multiplier)
+}
+
+/// 读取 sysfs 文本文件并去掉首尾空白
+fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+/// 将 `current_link_speed`（如 "16.0 GT/s PCIe"）转换为 PCIe 代数
+fn pcie_generation(speed: &str) -> Option<u32> {
+    let rate: f32 = speed.split_whitespace().next()?.parse().ok()?;
+    [2.5, 5.0, 8.0, 16.0, 32.0, 64.0]
+        .iter()
+        .position(|&generation_rate| (rate - generation_rate).abs() < 0.1)
+        .map(|position| position as u32 + 1)
+}
+
+/// 读取 sysfs 中的整数指标，区分文件不存在（不支持）和读取失败
+fn read_metric(path: &Path) -> Reading<u64> {
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => MetricUnavailableReason::NotSupported,
+        io::ErrorKind::PermissionDenied => MetricUnavailableReason::PermissionDenied,
+        _ => MetricUnavailableReason::ReadFailed,
+    })?;
+    content
+        .trim()
+        .parse()
+        .map_err(|_| MetricUnavailableReason::ReadFailed)
+}
+
+/// 读取 sysfs 中的整数
+fn read_u64(path: &Path) -> Option<u64> {
+    read_string(path).and_then(|content| content.parse().ok())
+}
@@ -0,0 +1,317 @@
+//! 可编排的内存 GPU 后端
+//!
+//! 用于在没有显卡的环境中测试 GPU 相关逻辑：模拟任意数量的设备、
+//! 按预设曲线变化的使用率、读取错误以及设备热拔出。
+//!
+//! [`MockGpuBackend`] 是共享状态的句柄，交给 [`GpuMonitor`](crate::GpuMonitor) 后
+//! 仍可以通过克隆的句柄继续编排设备状态。
+//!
+//! # Examples
+//!
+//! ```
+//! use system_monitor_core::gpu_mock::{MockGpuBackend, MockGpuDevice};
+//! use system_monitor_core::GpuMonitor;
+//!
+//! let backend = MockGpuBackend::new(vec![MockGpuDevice {
+//!     name: "Mock GPU".to_string(),
+//!     usage_curve: vec![10.0, 90.0],
+//!     ..Default::default()
+//! }]);
+//! let monitor = GpuMonitor::with_backends(vec![Box::new(backend.clone())]);
+//!
+//! assert_eq!(monitor.get_gpu_info(&[])[0].usage_percent, Some(10.0));
+//! backend.unplug(0);
+//! assert!(monitor.get_gpu_info(&[]).is_empty());
+//! ```
+
+use crate::errors::{GpuMonitorError, GpuResult};
+use crate::gpu_monitor::{available, GpuBackend};
+use crate::models::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// 模拟设备
+#[derive(Debug, Clone, Default)]
+pub struct MockGpuDevice {
+    pub name: String,
+    pub uuid: Option<String>,
+    pub pci_bus_id: Option<String>,
+    /// 每次读取依次返回的使用率，读完后从头循环；为空时模拟不支持读取使用率
+    pub usage_curve: Vec<f32>,
+    /// 以下指标为 `None` 时模拟设备不支持该指标
+    pub memory: Option<GpuMemoryInfo>,
+    pub temperature: Option<f32>,
+    pub frequency: Option<u64>,
+    pub memory_frequency: Option<u64>,
+    pub power_draw_watts: Option<f64>,
+    pub power_limit_watts: Option<f64>,
+    pub fan_speed_percent: Option<u32>,
+    pub performance_state: Option<u32>,
+    pub throttle_reasons: Option<Vec<GpuThrottleReason>>,
+    /// PCIe 接收和发送吞吐量（字节/秒）
+    pub pcie_throughput: Option<(u64, u64)>,
+    /// 使用该设备的进程
+    pub processes: Vec<GpuProcessInfo>,
+}
+
+/// 模拟设备的运行状态
+struct DeviceState {
+    device: MockGpuDevice,
+    /// 下一次读取使用率曲线的位置
+    cursor: usize,
+    /// 是否已被拔出
+    unplugged: bool,
+    /// 按顺序返回的读取错误
+    errors: VecDeque<GpuMonitorError>,
+}
+
+/// 可编排的GPU后端
+#[derive(Clone)]
+pub struct MockGpuBackend {
+    devices: Arc<Mutex<Vec<DeviceState>>>,
+}
+
+impl MockGpuBackend {
+    /// 创建模拟后端
+    ///
+    /// # Arguments
+    /// * `devices` - 模拟设备，按顺序编号
+    pub fn new(devices: Vec<MockGpuDevice>) -> Self {
+        let devices = devices
+            .into_iter()
+            .map(|device| DeviceState {
+                device,
+                cursor: 0,
+                unplugged: false,
+                errors: VecDeque::new(),
+            })
+            .collect();
+
+        Self {
+            devices: Arc::new(Mutex::new(devices)),
+        }
+    }
+
+    /// 让设备接下来的一次读取失败
+    ///
+    /// 多次调用时错误按顺序返回，每次读取消耗一个；失败的读取不推进使用率曲线。
+    ///
+    /// # Arguments
+    /// * `index` - 设备索引
+    /// * `error` - 返回的错误
+    pub fn fail_next(&self, index: u32, error: GpuMonitorError) {
+        if let Some(state) = self.lock().get_mut(index as usize) {
+            state.errors.push_back(error);
+        }
+    }
+
+    /// 模拟设备被拔出：设备数量不变，之后的读取都返回错误（与 NVML 的行为一致）
+    pub fn unplug(&self, index: u32) {
+        if let Some(state) = self.lock().get_mut(index as usize) {
+            state.unplugged = true;
+        }
+    }
+
+    /// 重新插入被拔出的设备
+    pub fn replug(&self, index: u32) {
+        if let Some(state) = self.lock().get_mut(index as usize) {
+            state.unplugged = false;
+        }
+    }
+
+    /// 追加一个新设备（模拟热插入）
+    pub fn add_device(&self, device: MockGpuDevice) {
+        self.lock().push(DeviceState {
+            device,
+            cursor: 0,
+            unplugged: false,
+            errors: VecDeque::new(),
+        });
+    }
+
+    /// 替换设备的使用率曲线并从头开始读取
+    pub fn set_usage_curve(&self, index: u32, usage_curve: Vec<f32>) {
+        if let Some(state) = self.lock().get_mut(index as usize) {
+            state.device.usage_curve = usage_curve;
+            state.cursor = 0;
+        }
+    }
+
+    /// 替换使用设备的进程
+    pub fn set_processes(&self, index: u32, processes: Vec<GpuProcessInfo>) {
+        if let Some(state) = self.lock().get_mut(index as usize) {
+            state.device.processes = processes;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<DeviceState>> {
+        self.devices.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 检查设备是否可读，并消耗一个预设错误
+    fn check(state: &mut DeviceState) -> GpuResult<()> {
+        if state.unplugged {
+            return Err(GpuMonitorError::DataFetchError(format!(
+                "{}: GPU已断开",
+                state.device.name
+            )));
+        }
+        match state.errors.pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// 对指定设备执行读取
+    fn with_device<T>(
+        &self,
+        index: u32,
+        read: impl FnOnce(&mut DeviceState) -> GpuResult<T>,
+    ) -> GpuResult<T> {
+        let mut devices = self.lock();
+        let state = devices
+            .get_mut(index as usize)
+            .ok_or(GpuMonitorError::DeviceNotFound {
+                device_index: index,
+            })?;
+        Self::check(state)?;
+        read(state)
+    }
+}
+
+impl GpuBackend for MockGpuBackend {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn device_count(&self) -> u32 {
+        self.lock().len() as u32
+    }
+
+    fn device_name(&self, index: u32) -> GpuResult<String> {
+        self.with_device(index, |state| Ok(state.device.name.clone()))
+    }
+
+    fn device_id(&self, index: u32) -> GpuResult<GpuDeviceId> {
+        self.with_device(index, |state| {
+            Ok(GpuDeviceId {
+                name: state.device.name.clone(),
+                uuid: state.device.uuid.clone(),
+                pci_bus_id: state.device.pci_bus_id.clone(),
+            })
+        })
+    }
+
+    fn device_info(&self, index: u32) -> GpuResult<GpuInfo> {
+        self.with_device(index, |state| {
+            let curve = &state.device.usage_curve;
+            let usage = if curve.is_empty() {
+                None
+            } else {
+                Some(curve[state.cursor % curve.len()])
+            };
+            state.cursor += 1;
+
+            let device = &state.device;
+            let mut unavailable = BTreeMap::new();
+            let pcie_throughput = supported(
+                &mut unavailable,
+                GpuMetric::PcieThroughput,
+                device.pcie_throughput,
+            );
+            Ok(GpuInfo {
+                index,
+                name: device.name.clone(),
+                uuid: device.uuid.clone(),
+                pci_bus_id: device.pci_bus_id.clone(),
+                usage_percent: supported(&mut unavailable, GpuMetric::Usage, usage),
+                memory: supported(&mut unavailable, GpuMetric::Memory, device.memory.clone()),
+                temperature: supported(
+                    &mut unavailable,
+                    GpuMetric::Temperature,
+                    device.temperature,
+                ),
+                frequency: supported(&mut unavailable, GpuMetric::Frequency, device.frequency),
+                memory_frequency: supported(
+                    &mut unavailable,
+                    GpuMetric::MemoryFrequency,
+                    device.memory_frequency,
+                ),
+                power_draw_watts: supported(
+                    &mut unavailable,
+                    GpuMetric::PowerDraw,
+                    device.power_draw_watts,
+                ),
+                power_limit_watts: supported(
+                    &mut unavailable,
+                    GpuMetric::PowerLimit,
+                    device.power_limit_watts,
+                ),
+                fan_speed_percent: supported(
+                    &mut unavailable,
+                    GpuMetric::FanSpeed,
+                    device.fan_speed_percent,
+                ),
+                performance_state: supported(
+                    &mut unavailable,
+                    GpuMetric::PerformanceState,
+                    device.performance_state,
+                ),
+                throttle_reasons: supported(
+                    &mut unavailable,
+                    GpuMetric::ThrottleReasons,
+                    device.throttle_reasons.clone(),
+                ),
+                pcie_rx_bytes_per_sec: pcie_throughput.map(|(rx, _)| rx),
+                pcie_tx_bytes_per_sec: pcie_throughput.map(|(_, tx)| tx),
+                unavailable,
+            })
+        })
+    }
+
+    fn detailed_info(&self, index: u32) -> GpuResult<GpuDetails> {
+        self.with_device(index, |state| {
+            let device = &state.device;
+            Ok(GpuDetails {
+                index,
+                name: device.name.clone(),
+                uuid: device.uuid.clone(),
+                pci_bus_id: device.pci_bus_id.clone(),
+                memory_total: device.memory.as_ref().map(|memory| memory.total),
+                memory_used: device.memory.as_ref().map(|memory| memory.used),
+                temperature: device.temperature,
+                clocks: device
+                    .frequency
+                    .map(|mhz| GpuClockInfo {
+                        domain: GpuClockDomain::Graphics,
+                        current_mhz: Some(mhz as u32),
+                        max_mhz: None,
+                    })
+                    .into_iter()
+                    .collect(),
+                power_draw_watts: device.power_draw_watts,
+                power_limit_watts: device.power_limit_watts,
+                fan_speed_percent: device.fan_speed_percent,
+                throttle_reasons: device.throttle_reasons.clone().unwrap_or_default(),
+                ..Default::default()
+            })
+        })
+    }
+
+    fn processes(&self, index: u32) -> GpuResult<Vec<GpuProcessInfo>> {
+        self.with_device(index, |state| Ok(state.device.processes.clone()))
+    }
+}
+
+/// 模拟值为 `None` 时视为设备不支持该指标
+fn supported<T>(
+    unavailable: &mut BTreeMap<GpuMetric, MetricUnavailableReason>,
+    metric: GpuMetric,
+    value: Option<T>,
+) -> Option<T> {
+    available(
+        unavailable,
+        metric,
+        value.ok_or(MetricUnavailableReason::NotSupported),
+    )
+}
@@ -0,0 +1,527 @@
+//! GPU 监控模块
+//!
+//! [`GpuMonitor`] 汇总多个 [`GpuBackend`] 的设备，对外提供统一的设备索引：
+//! NVIDIA 显卡通过 NVML 读取，Linux 上的 AMD/Intel 显卡通过 sysfs/DRM 读取。
+
+use crate::errors::{GpuMonitorError, GpuResult};
+use crate::gpu_drm::DrmBackend;
+use crate::gpu_nvml::NvmlBackend;
+use crate::models::*;
+use crate::retry::RetryConfig;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Instant;
+
+/// GPU可用性或设备变化事件名称
+pub const GPU_STATUS_EVENT: &str = "gpu-status-changed";
+
+/// procfs 的默认位置
+pub const PROC_ROOT: &str = "/proc";
+
+/// GPU 数据后端
+///
+/// 每个后端管理自己的设备，`index` 为后端内部的设备索引（从 0 开始）
+pub trait GpuBackend: Send + Sync {
+    /// 后端名称（如 "nvml"、"drm"）
+    fn name(&self) -> &str;
+
+    /// 设备数量
+    fn device_count(&self) -> u32;
+
+    /// 获取设备名称
+    fn device_name(&self, index: u32) -> GpuResult<String>;
+
+    /// 获取用于识别设备的信息
+    fn device_id(&self, index: u32) -> GpuResult<GpuDeviceId>;
+
+    /// 读取设备的实时信息
+    fn device_info(&self, index: u32) -> GpuResult<GpuInfo>;
+
+    /// 获取设备的详细状态信息
+    fn detailed_info(&self, index: u32) -> GpuResult<GpuDetails>;
+
+    /// 获取正在使用设备的进程
+    fn processes(&self, index: u32) -> GpuResult<Vec<GpuProcessInfo>>;
+}
+
+/// 探测GPU后端的函数，参数为当前生效的后端（可直接复用），返回所有可用的后端
+pub type GpuProber =
+    Box<dyn Fn(&[Arc<dyn GpuBackend>]) -> GpuResult<Vec<Arc<dyn GpuBackend>>> + Send + Sync>;
+
+/// 当前生效的后端
+struct ActiveBackends {
+    /// 已初始化且至少有一个设备的后端
+    backends: Vec<Arc<dyn GpuBackend>>,
+    /// 最后的错误信息
+    last_error: Option<String>,
+}
+
+/// 重新探测的状态
+struct ProbeState {
+    /// 自上一次设备变化以来的探测次数，用于计算退避延迟
+    attempt: usize,
+    /// 下一次探测的时间
+    next_probe: Instant,
+    /// 当前后端中的设备
+    devices: Vec<GpuDeviceId>,
+    /// 最近一次可用性或设备变化
+    last_change: Option<GpuStatusChange>,
+}
+
+/// GPU监控器结构体
+///
+/// 使用探测函数创建的监控器会按退避间隔重新探测：驱动延迟加载、外接显卡插拔后
+/// 无需重启即可恢复或更新监控，变化通过 [`GpuMonitor::poll_devices`] 返回。
+pub struct GpuMonitor {
+    active: RwLock<ActiveBackends>,
+    /// 重新探测使用的函数，为 `None` 时后端固定不变
+    prober: Option<GpuProber>,
+    /// 重新探测的退避配置
+    retry: RetryConfig,
+    probe: Mutex<ProbeState>,
+}
+
+impl GpuMonitor {
+    /// 创建新的GPU监控器，探测所有可用的后端
+    ///
+    /// # Returns
+    /// * `GpuResult<GpuMonitor>` - 所有后端都不可用时返回错误
+    pub fn new() -> GpuResult<Self> {
+        Self::probe_system(&[])
+            .map(|backends| Self::build(Ok(backends), None, RetryConfig::default()))
+    }
+
+    /// 探测本机所有可用的GPU后端
+    ///
+    /// NVML 只在初始化时枚举设备，所有设备仍可读取时直接复用当前的 NVML 后端，
+    /// 不再重新初始化；DRM 每次重新扫描 sysfs 以发现插拔的显卡，设备未变化时复用
+    /// 当前的 DRM 后端，保留其中用于计算速率的状态。
+    ///
+    /// # Arguments
+    /// * `current` - 当前生效的后端，首次探测时为空
+    ///
+    /// # Returns
+    /// * `GpuResult<Vec<Arc<dyn GpuBackend>>>` - 所有后端都不可用时返回错误
+    pub fn probe_system(current: &[Arc<dyn GpuBackend>]) -> GpuResult<Vec<Arc<dyn GpuBackend>>> {
+        let mut backends: Vec<Arc<dyn GpuBackend>> = Vec::new();
+        let mut errors = Vec::new();
+        let existing = |name: &str| current.iter().find(|backend| backend.name() == name);
+
+        match existing("nvml").filter(|backend| all_devices_readable(backend.as_ref())) {
+            Some(backend) => backends.push(backend.clone()),
+            None => match NvmlBackend::new() {
+                Ok(backend) => backends.push(Arc::new(backend)),
+                Err(e) => {
+                    log::debug!("NVML 后端不可用: {}", e);
+                    errors.push(e);
+                }
+            },
+        }
+
+        if cfg!(target_os = "linux") {
+            match DrmBackend::probe() {
+                Ok(backend) => {
+                    let backend: Arc<dyn GpuBackend> = Arc::new(backend);
+                    let unchanged = existing("drm").filter(|existing| {
+                        device_ids(std::slice::from_ref(*existing))
+                            == device_ids(std::slice::from_ref(&backend))
+                    });
+                    backends.push(unchanged.cloned().unwrap_or(backend));
+                }
+                Err(e) => {
+                    log::debug!("DRM 后端不可用: {}", e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        if backends.is_empty() {
+            // 有显卡但驱动不受支持时给出更明确的提示，否则返回 NVML 的错误
+            let error = match errors
+                .iter()
+                .position(|e| matches!(e, GpuMonitorError::UnsupportedGpu))
+            {
+                Some(position) => errors.swap_remove(position),
+                None => errors
+                    .into_iter()
+                    .next()
+                    .unwrap_or(GpuMonitorError::NoGpuFound),
+            };
+            return Err(error);
+        }
+
+        Ok(backends)
+    }
+
+    /// 使用指定的后端创建GPU监控器，之后不会重新探测
+    ///
+    /// # Arguments
+    /// * `backends` - GPU 后端，设备按后端顺序统一编号
+    pub fn with_backends(backends: Vec<Box<dyn GpuBackend>>) -> Self {
+        let backends = backends.into_iter().map(Arc::from).collect();
+        Self::build(Ok(backends), None, RetryConfig::default())
+    }
+
+    /// 使用探测函数创建GPU监控器
+    ///
+    /// 立即探测一次，失败时监控器不可用，之后由 [`GpuMonitor::poll_devices`] 按退避间隔重试
+    ///
+    /// # Arguments
+    /// * `prober` - 探测函数，如 [`GpuMonitor::probe_system`]，参数为当前生效的后端
+    /// * `retry` - 重新探测的退避配置
+    pub fn with_prober(
+        prober: impl Fn(&[Arc<dyn GpuBackend>]) -> GpuResult<Vec<Arc<dyn GpuBackend>>>
+            + Send
+            + Sync
+            + 'static,
+        retry: RetryConfig,
+    ) -> Self {
+        let probed = prober(&[]).map_err(|e| {
+            log::warn!("GPU监控器初始化失败: {}", e);
+            e.to_string()
+        });
+        Self::build(probed, Some(Box::new(prober)), retry)
+    }
+
+    fn build(
+        probed: Result<Vec<Arc<dyn GpuBackend>>, String>,
+        prober: Option<GpuProber>,
+        retry: RetryConfig,
+    ) -> Self {
+        let (backends, last_error) = match probed {
+            Ok(backends) => (backends, None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
+        for backend in &backends {
+            log::info!(
+                "GPU 后端 {}: {} 个设备",
+                backend.name(),
+                backend.device_count()
+            );
+        }
+
+        let probe = ProbeState {
+            attempt: 0,
+            next_probe: Instant::now() + retry.calculate_delay(0),
+            devices: device_ids(&backends),
+            last_change: None,
+        };
+
+        Self {
+            active: RwLock::new(ActiveBackends {
+                backends,
+                last_error,
+            }),
+            prober,
+            retry,
+            probe: Mutex::new(probe),
+        }
+    }
+
+    /// 到达探测时间时重新探测GPU，检测驱动加载和设备的增减
+    ///
+    /// 设备没有变化时沿用原有后端，探测间隔按 `retry` 配置退避；
+    /// 发生变化后切换到新探测的后端，并从最短间隔重新开始。
+    /// 探测可能会加载驱动库，应在阻塞线程中调用。
+    ///
+    /// # Returns
+    /// * `Option<GpuStatusChange>` - 本次探测发现的可用性或设备变化
+    pub fn poll_devices(&self) -> Option<GpuStatusChange> {
+        let prober = self.prober.as_ref()?;
+        {
+            let mut probe = self.lock_probe();
+            if Instant::now() < probe.next_probe {
+                return None;
+            }
+            // 探测期间不持有状态锁，先按当前退避间隔推迟下一次探测，避免并发调用重复探测
+            probe.next_probe = Instant::now() + self.retry.calculate_delay(probe.attempt);
+        }
+
+        // 探测时不持有任何锁，`status` 等调用不必等待；探测函数只拿到当前后端的快照
+        let current = self.read_active().backends.clone();
+        let (backends, error) = match prober(&current) {
+            Ok(backends) => (backends, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        let devices = device_ids(&backends);
+
+        let mut probe = self.lock_probe();
+        // 设备未变化时保留当前的后端，丢弃本次探测创建的后端
+        let change = if devices == probe.devices {
+            probe.attempt += 1;
+            self.write_active().last_error = error;
+            None
+        } else {
+            let change = GpuStatusChange {
+                previously_available: !probe.devices.is_empty(),
+                available: !devices.is_empty(),
+                added: missing_from(&devices, &probe.devices),
+                removed: missing_from(&probe.devices, &devices),
+                timestamp: timestamp_ms(),
+            };
+            log::info!(
+                "GPU设备变化: 新增 {} 个，移除 {} 个",
+                change.added.len(),
+                change.removed.len()
+            );
+
+            *self.write_active() = ActiveBackends {
+                backends,
+                last_error: error,
+            };
+            probe.attempt = 0;
+            probe.devices = devices;
+            probe.last_change = Some(change.clone());
+            Some(change)
+        };
+
+        probe.next_probe = Instant::now() + self.retry.calculate_delay(probe.attempt);
+        change
+    }
+
+    /// 获取GPU监控状态
+    pub fn status(&self) -> GpuMonitorStatus {
+        let probe = self.lock_probe();
+        GpuMonitorStatus {
+            available: self.is_available(),
+            error: self.get_last_error(),
+            devices: probe.devices.clone(),
+            next_probe_in_ms: self.prober.as_ref().map(|_| {
+                probe
+                    .next_probe
+                    .saturating_duration_since(Instant::now())
+                    .as_millis() as u64
+            }),
+            last_change: probe.last_change.clone(),
+        }
+    }
+
+    fn read_active(&self) -> RwLockReadGuard<'_, ActiveBackends> {
+        self.active.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_active(&self) -> RwLockWriteGuard<'_, ActiveBackends> {
+        self.active.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_probe(&self) -> MutexGuard<'_, ProbeState> {
+        self.probe.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 设备总数
+    fn device_count(&self) -> u32 {
+        total_device_count(&self.read_active().backends)
+    }
+
+    /// 将统一设备索引转换为后端及其内部索引，并对该设备执行读取
+    fn with_device<T>(
+        &self,
+        device_index: u32,
+        read: impl FnOnce(&dyn GpuBackend, u32) -> GpuResult<T>,
+    ) -> GpuResult<T> {
+        let active = self.read_active();
+        if total_device_count(&active.backends) == 0 {
+            return Err(GpuMonitorError::NoGpuFound);
+        }
+
+        let mut offset = 0;
+        for backend in &active.backends {
+            let count = backend.device_count();
+            if device_index < offset + count {
+                return read(backend.as_ref(), device_index - offset);
+            }
+            offset += count;
+        }
+
+        Err(GpuMonitorError::DeviceNotFound { device_index })
+    }
+
+    /// 获取所有被选中GPU的信息
+    ///
+    /// # Arguments
+    /// * `selection` - 要监控的设备（按索引、UUID 或 PCI 总线ID 匹配），为空表示全部设备
+    ///
+    /// # Returns
+    /// * `Vec<GpuInfo>` - 按设备索引排列的GPU信息，读取失败的设备会被跳过
+    pub fn get_gpu_info(&self, selection: &[String]) -> Vec<GpuInfo> {
+        let mut gpus = Vec::new();
+        let mut index = 0;
+
+        for backend in &self.read_active().backends {
+            for local_index in 0..backend.device_count() {
+                let device_index = index;
+                index += 1;
+
+                let mut info = match backend.device_info(local_index) {
+                    Ok(info) => info,
+                    Err(e) => {
+                        log::warn!("获取GPU设备 {} 信息失败: {}", device_index, e);
+                        continue;
+                    }
+                };
+                info.index = device_index;
+
+                if Self::is_selected(
+                    selection,
+                    device_index,
+                    info.uuid.as_deref(),
+                    info.pci_bus_id.as_deref(),
+                ) {
+                    gpus.push(info);
+                }
+            }
+        }
+
+        gpus
+    }
+
+    /// 判断设备是否在监控范围内（PCI 总线ID 不区分大小写）
+    fn is_selected(
+        selection: &[String],
+        index: u32,
+        uuid: Option<&str>,
+        pci_bus_id: Option<&str>,
+    ) -> bool {
+        selection.is_empty()
+            || selection.iter().map(|s| s.trim()).any(|selector| {
+                selector == index.to_string()
+                    || uuid == Some(selector)
+                    || pci_bus_id.is_some_and(|bus_id| bus_id.eq_ignore_ascii_case(selector))
+            })
+    }
+
+    /// 检查GPU监控是否可用
+    pub fn is_available(&self) -> bool {
+        self.device_count() > 0
+    }
+
+    /// 获取最后的错误信息
+    pub fn get_last_error(&self) -> Option<String> {
+        self.read_active().last_error.clone()
+    }
+
+    /// 获取所有GPU的基本信息列表
+    pub fn get_all_gpu_names(&self) -> Vec<String> {
+        (0..self.device_count())
+            .map(|device_index| {
+                self.with_device(device_index, |backend, index| backend.device_name(index))
+                    .unwrap_or_else(|_| format!("GPU {}", device_index))
+            })
+            .collect()
+    }
+
+    /// 获取详细的GPU状态信息
+    pub fn get_detailed_gpu_info(&self, device_index: u32) -> GpuResult<GpuDetails> {
+        let mut details =
+            self.with_device(device_index, |backend, index| backend.detailed_info(index))?;
+        details.index = device_index;
+        Ok(details)
+    }
+
+    /// 获取正在使用GPU的进程
+    ///
+    /// # Arguments
+    /// * `device_index` - GPU设备索引
+    ///
+    /// # Returns
+    /// * `GpuResult<Vec<GpuProcessInfo>>` - 按占用显存从大到小排列的进程
+    pub fn get_gpu_processes(&self, device_index: u32) -> GpuResult<Vec<GpuProcessInfo>> {
+        let mut processes =
+            self.with_device(device_index, |backend, index| backend.processes(index))?;
+        processes.sort_by(|a, b| {
+            b.used_memory
+                .cmp(&a.used_memory)
+                .then_with(|| a.pid.cmp(&b.pid))
+        });
+        Ok(processes)
+    }
+}
+
+impl Default for GpuMonitor {
+    fn default() -> Self {
+        // 默认创建不可用的GPU监控器
+        Self::build(
+            Err("GPU监控不可用".to_string()),
+            None,
+            RetryConfig::default(),
+        )
+    }
+}
+
+/// 后端中的设备总数
+fn total_device_count(backends: &[Arc<dyn GpuBackend>]) -> u32 {
+    backends.iter().map(|backend| backend.device_count()).sum()
+}
+
+/// 按统一编号列出后端中的设备，读取失败的设备视为已移除
+fn device_ids(backends: &[Arc<dyn GpuBackend>]) -> Vec<GpuDeviceId> {
+    backends
+        .iter()
+        .flat_map(|backend| {
+            (0..backend.device_count()).filter_map(|index| backend.device_id(index).ok())
+        })
+        .collect()
+}
+
+/// 后端的所有设备是否仍可读取（驱动卸载或设备掉线后读取失败）
+fn all_devices_readable(backend: &dyn GpuBackend) -> bool {
+    (0..backend.device_count()).all(|index| backend.device_id(index).is_ok())
+}
+
+/// `devices` 中不在 `other` 里的设备（同型号的多块显卡按数量计算）
+fn missing_from(devices: &[GpuDeviceId], other: &[GpuDeviceId]) -> Vec<GpuDeviceId> {
+    let mut remaining = other.to_vec();
+    devices
+        .iter()
+        .filter(|device| match remaining.iter().position(|r| r == *device) {
+            Some(position) => {
+                remaining.swap_remove(position);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// 当前时间戳（毫秒）
+fn timestamp_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// 取出一次指标读取的结果，读取失败时记录不可用原因
+///
+/// # Arguments
+/// * `unavailable` - 本次采样的不可用指标
+/// * `metric` - 指标
+/// * `reading` - 读取结果
+pub(crate) fn available<T>(
+    unavailable: &mut BTreeMap<GpuMetric, MetricUnavailableReason>,
+    metric: GpuMetric,
+    reading: Result<T, MetricUnavailableReason>,
+) -> Option<T> {
+    match reading {
+        Ok(value) => Some(value),
+        Err(reason) => {
+            unavailable.insert(metric, reason);
+            None
+        }
+    }
+}
+
+/// 从 procfs 读取进程名称
+///
+/// # Arguments
+/// * `proc_root` - procfs 目录
+/// * `pid` - 进程ID
+pub(crate) fn proc_process_name(proc_root: &Path, pid: u32) -> Option<String> {
+    fs::read_to_string(proc_root.join(pid.to_string()).join("comm"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
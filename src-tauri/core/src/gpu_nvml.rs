@@ -0,0 +1,474 @@
+//! NVIDIA GPU 后端
+//!
+//! 通过 NVML 读取 NVIDIA 显卡的使用率、显存、温度、频率、功耗、降频原因和 PCIe 吞吐量。
+
+use crate::errors::{GpuMonitorError, GpuResult};
+use crate::gpu_monitor::{available, proc_process_name, GpuBackend, PROC_ROOT};
+use crate::models::*;
+use nvml_wrapper::{
+    bitmasks::device::ThrottleReasons,
+    enum_wrappers::device::{
+        Clock, EccCounter, MemoryError, PcieUtilCounter, PerformanceState, TemperatureSensor,
+    },
+    enums::device::UsedGpuMemory,
+    error::NvmlError,
+    struct_wrappers::device::ProcessUtilizationSample,
+    Device, Nvml,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// 基于 NVML 的GPU后端
+pub struct NvmlBackend {
+    /// NVML实例
+    nvml: Nvml,
+    /// 设备数量
+    device_count: u32,
+    /// 每个设备不支持的查询
+    unsupported: Mutex<HashMap<u32, HashSet<GpuMetric>>>,
+    /// 每个设备最近一次进程利用率样本的时间戳（微秒）
+    last_process_sample: Mutex<HashMap<u32, u64>>,
+}
+
+impl NvmlBackend {
+    /// 初始化 NVML 并枚举设备
+    ///
+    /// # Returns
+    /// * `GpuResult<NvmlBackend>` - NVML 不可用或没有设备时返回错误
+    pub fn new() -> GpuResult<Self> {
+        let nvml = Nvml::init().map_err(map_nvml_error)?;
+        let device_count = nvml.device_count().map_err(map_nvml_error)?;
+
+        if device_count == 0 {
+            return Err(GpuMonitorError::NoGpuFound);
+        }
+
+        Ok(Self {
+            nvml,
+            device_count,
+            unsupported: Mutex::new(HashMap::new()),
+            last_process_sample: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 获取设备句柄
+    fn device(&self, index: u32) -> GpuResult<Device<'_>> {
+        if index >= self.device_count {
+            return Err(GpuMonitorError::DeviceNotFound {
+                device_index: index,
+            });
+        }
+        self.nvml.device_by_index(index).map_err(map_nvml_error)
+    }
+
+    /// 执行一次 NVML 查询
+    ///
+    /// 设备不支持的查询会被记住，之后的采样直接返回 `NotSupported` 而不再调用驱动
+    fn query<T>(
+        &self,
+        index: u32,
+        metric: GpuMetric,
+        read: impl FnOnce() -> Result<T, NvmlError>,
+    ) -> Result<T, MetricUnavailableReason> {
+        if self.is_unsupported(index, metric) {
+            return Err(MetricUnavailableReason::NotSupported);
+        }
+
+        read().map_err(|error| {
+            let reason = unavailable_reason(&error);
+            if reason == MetricUnavailableReason::NotSupported {
+                log::info!("GPU {} 不支持读取 {:?}，之后不再查询", index, metric);
+                self.lock_unsupported()
+                    .entry(index)
+                    .or_default()
+                    .insert(metric);
+            } else {
+                log::debug!("GPU {} 读取 {:?} 失败: {}", index, metric, error);
+            }
+            reason
+        })
+    }
+
+    /// 查询是否已知设备不支持该指标
+    fn is_unsupported(&self, index: u32, metric: GpuMetric) -> bool {
+        self.lock_unsupported()
+            .get(&index)
+            .is_some_and(|metrics| metrics.contains(&metric))
+    }
+
+    fn lock_unsupported(&self) -> MutexGuard<'_, HashMap<u32, HashSet<GpuMetric>>> {
+        self.unsupported
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl GpuBackend for NvmlBackend {
+    fn name(&self) -> &str {
+        "nvml"
+    }
+
+    fn device_count(&self) -> u32 {
+        self.device_count
+    }
+
+    fn device_name(&self, index: u32) -> GpuResult<String> {
+        self.device(index)?.name().map_err(map_nvml_error)
+    }
+
+    fn device_id(&self, index: u32) -> GpuResult<GpuDeviceId> {
+        let device = self.device(index)?;
+        Ok(GpuDeviceId {
+            name: device.name().map_err(map_nvml_error)?,
+            uuid: device.uuid().ok(),
+            pci_bus_id: device.pci_info().ok().map(|pci| pci.bus_id),
+        })
+    }
+
+    fn device_info(&self, index: u32) -> GpuResult<GpuInfo> {
+        let device = self.device(index)?;
+        let mut unavailable = BTreeMap::new();
+
+        let usage_percent = available(
+            &mut unavailable,
+            GpuMetric::Usage,
+            self.query(index, GpuMetric::Usage, || device.utilization_rates())
+                .map(|utilization| utilization.gpu as f32),
+        );
+        let memory = available(
+            &mut unavailable,
+            GpuMetric::Memory,
+            self.query(index, GpuMetric::Memory, || device.memory_info())
+                .map(|memory| GpuMemoryInfo::new(memory.total, memory.used)),
+        );
+        let temperature = available(
+            &mut unavailable,
+            GpuMetric::Temperature,
+            self.query(index, GpuMetric::Temperature, || {
+                device.temperature(TemperatureSensor::Gpu)
+            })
+            .map(|temp| temp as f32),
+        );
+        let frequency = available(
+            &mut unavailable,
+            GpuMetric::Frequency,
+            self.query(index, GpuMetric::Frequency, || {
+                device.clock_info(Clock::Graphics)
+            })
+            .map(|freq| freq as u64),
+        );
+        let memory_frequency = available(
+            &mut unavailable,
+            GpuMetric::MemoryFrequency,
+            self.query(index, GpuMetric::MemoryFrequency, || {
+                device.clock_info(Clock::Memory)
+            })
+            .map(|freq| freq as u64),
+        );
+        // NVML 功耗单位为毫瓦
+        let power_draw_watts = available(
+            &mut unavailable,
+            GpuMetric::PowerDraw,
+            self.query(index, GpuMetric::PowerDraw, || device.power_usage())
+                .map(|mw| mw as f64 / 1000.0),
+        );
+        let power_limit_watts = available(
+            &mut unavailable,
+            GpuMetric::PowerLimit,
+            self.query(index, GpuMetric::PowerLimit, || {
+                device.enforced_power_limit()
+            })
+            .map(|mw| mw as f64 / 1000.0),
+        );
+        let fan_speed_percent = available(
+            &mut unavailable,
+            GpuMetric::FanSpeed,
+            self.query(index, GpuMetric::FanSpeed, || device.fan_speed(0)),
+        );
+        let performance_state = available(
+            &mut unavailable,
+            GpuMetric::PerformanceState,
+            self.query(index, GpuMetric::PerformanceState, || {
+                device.performance_state()
+            })
+            .and_then(performance_state),
+        );
+        let throttle_reasons = available(
+            &mut unavailable,
+            GpuMetric::ThrottleReasons,
+            self.query(index, GpuMetric::ThrottleReasons, || {
+                device.current_throttle_reasons()
+            })
+            .map(throttle_reasons),
+        );
+        // NVML 吞吐量单位为 KB/s
+        let pcie_throughput = available(
+            &mut unavailable,
+            GpuMetric::PcieThroughput,
+            self.query(index, GpuMetric::PcieThroughput, || {
+                Ok((
+                    device.pcie_throughput(PcieUtilCounter::Receive)?,
+                    device.pcie_throughput(PcieUtilCounter::Send)?,
+                ))
+            })
+            .map(|(rx, tx)| (rx as u64 * 1024, tx as u64 * 1024)),
+        );
+
+        Ok(GpuInfo {
+            index,
+            // GPU基本信息
+            name: device.name().unwrap_or_else(|_| "Unknown GPU".to_string()),
+            uuid: device.uuid().ok(),
+            pci_bus_id: device.pci_info().ok().map(|pci| pci.bus_id),
+            usage_percent,
+            memory,
+            temperature,
+            frequency,
+            memory_frequency,
+            power_draw_watts,
+            power_limit_watts,
+            fan_speed_percent,
+            performance_state,
+            throttle_reasons,
+            pcie_rx_bytes_per_sec: pcie_throughput.map(|(rx, _)| rx),
+            pcie_tx_bytes_per_sec: pcie_throughput.map(|(_, tx)| tx),
+            unavailable,
+        })
+    }
+
+    fn detailed_info(&self, index: u32) -> GpuResult<GpuDetails> {
+        let device = self.device(index)?;
+        let utilization = device.utilization_rates().ok();
+        let memory = device.memory_info().ok();
+
+        let clocks = [
+            (GpuClockDomain::Graphics, Clock::Graphics),
+            (GpuClockDomain::Sm, Clock::SM),
+            (GpuClockDomain::Memory, Clock::Memory),
+            (GpuClockDomain::Video, Clock::Video),
+        ]
+        .into_iter()
+        .map(|(domain, clock)| GpuClockInfo {
+            domain,
+            current_mhz: device.clock_info(clock.clone()).ok(),
+            max_mhz: device.max_clock_info(clock).ok(),
+        })
+        .filter(|clock| clock.current_mhz.is_some() || clock.max_mhz.is_some())
+        .collect();
+
+        // 只有启用 ECC 的设备才读取错误计数
+        let ecc_errors = match device.is_ecc_enabled() {
+            Ok(state) if state.currently_enabled => {
+                let count = |error_type| {
+                    device
+                        .total_ecc_errors(error_type, EccCounter::Volatile)
+                        .ok()
+                };
+                count(MemoryError::Corrected)
+                    .zip(count(MemoryError::Uncorrected))
+                    .map(|(corrected, uncorrected)| GpuEccErrors {
+                        corrected,
+                        uncorrected,
+                    })
+            }
+            _ => None,
+        };
+
+        Ok(GpuDetails {
+            index,
+            name: device.name().map_err(map_nvml_error)?,
+            uuid: device.uuid().ok(),
+            pci_bus_id: device.pci_info().ok().map(|pci| pci.bus_id),
+            driver_version: self.nvml.sys_driver_version().ok(),
+            vbios_version: device.vbios_version().ok(),
+            pcie_generation: device.current_pcie_link_gen().ok(),
+            pcie_width: device.current_pcie_link_width().ok(),
+            gpu_utilization: utilization.as_ref().map(|rates| rates.gpu as f32),
+            memory_utilization: utilization.as_ref().map(|rates| rates.memory as f32),
+            memory_total: memory.as_ref().map(|memory| memory.total),
+            memory_used: memory.as_ref().map(|memory| memory.used),
+            temperature: device
+                .temperature(TemperatureSensor::Gpu)
+                .ok()
+                .map(|temp| temp as f32),
+            // NVML 功耗单位为毫瓦
+            power_draw_watts: device.power_usage().ok().map(|mw| mw as f64 / 1000.0),
+            power_limit_watts: device
+                .enforced_power_limit()
+                .ok()
+                .map(|mw| mw as f64 / 1000.0),
+            fan_speed_percent: device.fan_speed(0).ok(),
+            clocks,
+            throttle_reasons: device
+                .current_throttle_reasons()
+                .map(throttle_reasons)
+                .unwrap_or_default(),
+            ecc_errors,
+            encoder_utilization: device
+                .encoder_utilization()
+                .ok()
+                .map(|info| info.utilization as f32),
+            decoder_utilization: device
+                .decoder_utilization()
+                .ok()
+                .map(|info| info.utilization as f32),
+        })
+    }
+
+    fn processes(&self, index: u32) -> GpuResult<Vec<GpuProcessInfo>> {
+        let device = self.device(index)?;
+        let mut processes: BTreeMap<u32, GpuProcessInfo> = BTreeMap::new();
+
+        // 同一进程可能同时出现在计算和图形列表中，显存取较大值
+        let lists = match (
+            device.running_compute_processes(),
+            device.running_graphics_processes(),
+        ) {
+            (Err(error), Err(_)) => return Err(map_nvml_error(error)),
+            (compute, graphics) => [compute, graphics],
+        };
+        for process in lists.into_iter().flatten().flatten() {
+            let entry = processes
+                .entry(process.pid)
+                .or_insert_with(|| GpuProcessInfo {
+                    pid: process.pid,
+                    ..Default::default()
+                });
+            // WDDM 下驱动无法提供进程显存
+            if let UsedGpuMemory::Used(bytes) = process.used_gpu_memory {
+                entry.used_memory = Some(entry.used_memory.map_or(bytes, |used| used.max(bytes)));
+            }
+        }
+
+        // 只取上次查询之后的利用率样本，每个进程使用最新的一条
+        let last_seen = self
+            .last_process_sample
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&index)
+            .copied();
+        let samples = match device.process_utilization_stats(last_seen) {
+            Ok(samples) => Some(samples),
+            // 期间没有新的样本
+            Err(NvmlError::NotFound) => Some(Vec::new()),
+            Err(e) => {
+                log::debug!("GPU {} 无法获取进程利用率: {}", index, e);
+                None
+            }
+        };
+
+        if let Some(samples) = samples {
+            let mut latest: HashMap<u32, ProcessUtilizationSample> = HashMap::new();
+            for sample in samples {
+                match latest.get(&sample.pid) {
+                    Some(existing) if existing.timestamp >= sample.timestamp => {}
+                    _ => {
+                        latest.insert(sample.pid, sample);
+                    }
+                }
+            }
+            if let Some(timestamp) = latest.values().map(|sample| sample.timestamp).max() {
+                self.last_process_sample
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(index, timestamp);
+            }
+
+            // NVML 只返回利用率不为 0 的进程，其余进程的利用率为 0
+            for process in processes.values_mut() {
+                process.sm_utilization = Some(0.0);
+                process.encoder_utilization = Some(0.0);
+                process.decoder_utilization = Some(0.0);
+            }
+            for sample in latest.into_values() {
+                let entry = processes
+                    .entry(sample.pid)
+                    .or_insert_with(|| GpuProcessInfo {
+                        pid: sample.pid,
+                        ..Default::default()
+                    });
+                entry.sm_utilization = Some(sample.sm_util as f32);
+                entry.encoder_utilization = Some(sample.enc_util as f32);
+                entry.decoder_utilization = Some(sample.dec_util as f32);
+            }
+        }
+
+        let proc_root = Path::new(PROC_ROOT);
+        Ok(processes
+            .into_values()
+            .map(|mut process| {
+                process.name = proc_process_name(proc_root, process.pid);
+                process
+            })
+            .collect())
+    }
+}
+
+/// 将 NVML 降频原因位掩码转换为列表
+fn throttle_reasons(reasons: ThrottleReasons) -> Vec<GpuThrottleReason> {
+    [
+        (ThrottleReasons::GPU_IDLE, GpuThrottleReason::GpuIdle),
+        (
+            ThrottleReasons::APPLICATIONS_CLOCKS_SETTING,
+            GpuThrottleReason::ApplicationsClocksSetting,
+        ),
+        (ThrottleReasons::SW_POWER_CAP, GpuThrottleReason::SwPowerCap),
+        (ThrottleReasons::HW_SLOWDOWN, GpuThrottleReason::HwSlowdown),
+        (ThrottleReasons::SYNC_BOOST, GpuThrottleReason::SyncBoost),
+        (
+            ThrottleReasons::SW_THERMAL_SLOWDOWN,
+            GpuThrottleReason::SwThermalSlowdown,
+        ),
+        (
+            ThrottleReasons::HW_THERMAL_SLOWDOWN,
+            GpuThrottleReason::HwThermalSlowdown,
+        ),
+        (
+            ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN,
+            GpuThrottleReason::HwPowerBrakeSlowdown,
+        ),
+        (
+            ThrottleReasons::DISPLAY_CLOCK_SETTING,
+            GpuThrottleReason::DisplayClockSetting,
+        ),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| reasons.contains(*flag))
+    .map(|(_, reason)| reason)
+    .collect()
+}
+
+/// 将 NVML 性能状态转换为编号（P0 为 0），驱动无法确定时视为不支持
+fn performance_state(state: PerformanceState) -> Result<u32, MetricUnavailableReason> {
+    match state {
+        PerformanceState::Unknown => Err(MetricUnavailableReason::NotSupported),
+        state => Ok(state.as_c()),
+    }
+}
+
+/// 将 NVML 错误转换为指标不可用的原因
+fn unavailable_reason(error: &NvmlError) -> MetricUnavailableReason {
+    match error {
+        NvmlError::NotSupported | NvmlError::FunctionNotFound => {
+            MetricUnavailableReason::NotSupported
+        }
+        NvmlError::NoPermission => MetricUnavailableReason::PermissionDenied,
+        NvmlError::GpuLost => MetricUnavailableReason::DeviceLost,
+        _ => MetricUnavailableReason::ReadFailed,
+    }
+}
+
+/// 将 NVML 错误转换为GPU监控错误
+fn map_nvml_error(error: NvmlError) -> GpuMonitorError {
+    match error {
+        NvmlError::LibloadingError(_) | NvmlError::LibraryNotFound => {
+            GpuMonitorError::NvmlNotAvailable
+        }
+        NvmlError::NoPermission => GpuMonitorError::PermissionDenied,
+        NvmlError::DriverNotLoaded | NvmlError::Uninitialized => {
+            GpuMonitorError::NvmlInitError(error.to_string())
+        }
+        other => GpuMonitorError::DataFetchError(other.to_string()),
+    }
+}
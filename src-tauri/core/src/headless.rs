@@ -0,0 +1,327 @@
+//! 无界面运行模块
+//!
+//! 不创建 Tauri 应用、托盘和窗口，直接驱动 `SystemMonitor` 的刷新循环，
+//! 将采样结果以 JSON Lines 或表格形式输出到标准输出，并可选启动指标导出服务，
+//! 供服务器和 CI 等没有图形环境的场景使用。
+
+use crate::exporter::{ExporterSource, MetricsExporter};
+use crate::models::{ExporterConfig, MonitorConfig, SystemInfo};
+use crate::monitor::SystemMonitor;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// 命令行帮助信息
+const USAGE: &str = "\
+用法: system-monitor-headless [选项]
+
+选项:
+  --format <json|table>   输出格式，默认 json（每行一个 JSON 对象）
+  --interval-ms <毫秒>    采样间隔，默认 1000
+  --count <次数>          输出指定次数（大于 0）后退出，默认持续运行
+  --exporter <地址>       同时在该地址提供 /metrics，例如 127.0.0.1:9184
+  --no-gpu                不采集 GPU 信息
+  --no-processes          不采集进程信息
+  -h, --help              显示帮助信息";
+
+/// 表格模式下每隔多少行重复一次表头
+const TABLE_HEADER_EVERY: u64 = 20;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// 每行一个 JSON 对象
+    Json,
+    /// 适合终端阅读的表格
+    Table,
+}
+
+/// 无界面模式的运行参数
+#[derive(Debug, Clone)]
+struct HeadlessOptions {
+    format: OutputFormat,
+    interval: Duration,
+    count: Option<u64>,
+    exporter_address: Option<String>,
+    enable_gpu: bool,
+    enable_processes: bool,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Json,
+            interval: Duration::from_millis(1000),
+            count: None,
+            exporter_address: None,
+            enable_gpu: true,
+            enable_processes: true,
+        }
+    }
+}
+
+/// 解析命令行参数
+///
+/// # Returns
+/// * `Result<Option<HeadlessOptions>, String>` - 运行参数；请求帮助时返回 None
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<HeadlessOptions>, String> {
+    let mut options = HeadlessOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("参数 {} 缺少取值", name));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--format" => {
+                options.format = match value("--format")?.as_str() {
+                    "json" => OutputFormat::Json,
+                    "table" => OutputFormat::Table,
+                    other => return Err(format!("未知的输出格式: {}", other)),
+                };
+            }
+            "--interval-ms" => {
+                let millis = value("--interval-ms")?
+                    .parse::<u64>()
+                    .map_err(|e| format!("无效的采样间隔: {}", e))?;
+                // 间隔过短时 CPU 使用率无法计算
+                options.interval =
+                    Duration::from_millis(millis).max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            }
+            "--count" => {
+                let count = value("--count")?
+                    .parse::<u64>()
+                    .map_err(|e| format!("无效的输出次数: {}", e))?;
+                if count == 0 {
+                    return Err("输出次数必须大于 0".to_string());
+                }
+                options.count = Some(count);
+            }
+            "--exporter" => options.exporter_address = Some(value("--exporter")?),
+            "--no-gpu" => options.enable_gpu = false,
+            "--no-processes" => options.enable_processes = false,
+            other => return Err(format!("未知参数: {}", other)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+/// JSON Lines 模式下输出的单条记录
+#[derive(Serialize)]
+struct HeadlessSample<'a> {
+    /// 采样时间戳（毫秒）
+    timestamp: u64,
+    #[serde(flatten)]
+    info: &'a SystemInfo,
+}
+
+/// 以无界面模式运行
+///
+/// # Arguments
+/// * `args` - 命令行参数（不含程序名）
+///
+/// # Returns
+/// * `i32` - 进程退出码
+pub fn run(args: impl IntoIterator<Item = String>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("无法创建异步运行时: {}", e);
+            return 1;
+        }
+    };
+
+    match runtime.block_on(run_loop(options)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// 采样主循环，收到 Ctrl+C 或达到输出次数后退出
+async fn run_loop(options: HeadlessOptions) -> Result<(), String> {
+    let config = MonitorConfig {
+        refresh_interval: options.interval.as_millis() as u64,
+        enable_gpu: options.enable_gpu,
+        enable_processes: options.enable_processes,
+        ..MonitorConfig::default()
+    };
+    let monitor = SystemMonitor::new(config);
+    let current_data: Arc<RwLock<Option<Arc<SystemInfo>>>> = Arc::new(RwLock::new(None));
+
+    // 导出服务在独立线程中运行，需在本函数返回前保持存活
+    let _exporter = match &options.exporter_address {
+        Some(address) => {
+            let exporter = MetricsExporter::start(
+                ExporterConfig {
+                    enabled: true,
+                    listen_address: address.clone(),
+                },
+                ExporterSource {
+                    current_data: current_data.clone(),
+                },
+            )
+            .map_err(|e| e.to_string())?;
+            eprintln!("指标导出服务: http://{}/metrics", exporter.address());
+            Some(exporter)
+        }
+        None => None,
+    };
+
+    // CPU 使用率由相邻两次刷新的差值计算，等待一个最小间隔使第一行输出有效
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+
+    let mut stdout = std::io::stdout().lock();
+    let mut printed: u64 = 0;
+    let mut ticker = tokio::time::interval(options.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // 只创建一次，避免每轮循环重新注册信号监听而错过两次轮询之间到达的信号
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = &mut ctrl_c => break,
+        }
+
+        let system_info = match monitor.refresh().await {
+            Ok(system_info) => Arc::new(system_info),
+            Err(e) => {
+                eprintln!("采样失败: {}", e);
+                continue;
+            }
+        };
+        *current_data.write().await = Some(system_info.clone());
+
+        // 运行期间接入的外接显卡或延迟加载的驱动也能被发现
+        if let Some(change) = monitor.poll_gpu_devices().await {
+            eprintln!(
+                "GPU设备变化: 新增 {} 个，移除 {} 个",
+                change.added.len(),
+                change.removed.len()
+            );
+        }
+
+        let line = match options.format {
+            OutputFormat::Json => serde_json::to_string(&HeadlessSample {
+                timestamp: timestamp_ms(),
+                info: &system_info,
+            })
+            .map_err(|e| format!("序列化采样结果失败: {}", e))?,
+            OutputFormat::Table => {
+                let row = format_table_row(&system_info);
+                if printed.is_multiple_of(TABLE_HEADER_EVERY) {
+                    format!("{}\n{}", format_table_header(), row)
+                } else {
+                    row
+                }
+            }
+        };
+
+        // 标准输出被关闭（例如管道另一端退出）时直接结束
+        if writeln!(stdout, "{}", line)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+
+        printed += 1;
+        if options.count.is_some_and(|count| printed >= count) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// 表头
+fn format_table_header() -> String {
+    format!(
+        "{:<8} {:>6} {:>6} {:>10} {:>10} {:>10} {:>6} {:>6}",
+        "TIME", "CPU%", "MEM%", "MEM_USED", "RX/s", "TX/s", "DISK%", "TEMP"
+    )
+}
+
+/// 单次采样的表格行（磁盘取使用率最高的分区，温度取最高的传感器）
+fn format_table_row(info: &SystemInfo) -> String {
+    let receive_rate: f64 = info
+        .network
+        .interfaces
+        .iter()
+        .map(|iface| iface.receive_rate)
+        .sum();
+    let transmit_rate: f64 = info
+        .network
+        .interfaces
+        .iter()
+        .map(|iface| iface.transmit_rate)
+        .sum();
+    let disk_usage = info
+        .disk
+        .disks
+        .iter()
+        .map(|disk| disk.usage_percent)
+        .reduce(f32::max);
+    let temperature = info
+        .temperatures
+        .iter()
+        .map(|sensor| sensor.temperature)
+        .reduce(f32::max);
+
+    let optional = |value: Option<f32>| {
+        value
+            .map(|value| format!("{:.1}", value))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    format!(
+        "{:<8} {:>6.1} {:>6.1} {:>10} {:>10} {:>10} {:>6} {:>6}",
+        chrono::Local::now().format("%H:%M:%S"),
+        info.cpu_usage,
+        info.memory.usage_percent,
+        format_bytes(info.memory.used as f64),
+        format_bytes(receive_rate),
+        format_bytes(transmit_rate),
+        optional(disk_usage),
+        optional(temperature),
+    )
+}
+
+/// 以 1024 为进制格式化字节数
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
+}
+
+/// 当前时间戳（毫秒）
+fn timestamp_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,286 @@
+//! 指标历史记录模块
+//!
+//! 在 Rust 端为每个指标维护固定容量的环形缓冲区，供各窗口共享同一份历史数据，
+//! 查询时按时间分桶降采样，返回每个桶的最小值、最大值和平均值。
+
+use crate::models::{GpuThrottleReason, HistoryBucket, SystemInfo};
+use std::collections::{HashMap, VecDeque};
+
+/// 单个历史数据点
+#[derive(Debug, Clone, Copy)]
+struct HistoryPoint {
+    /// 时间戳（毫秒）
+    timestamp: u64,
+    /// 指标值
+    value: f64,
+}
+
+/// 指标历史存储
+pub struct HistoryStore {
+    /// 记录分辨率（毫秒），间隔小于该值的样本会被忽略
+    resolution_ms: u64,
+    /// 每个指标保留的最大点数
+    capacity: usize,
+    /// 上一次记录的时间戳
+    last_recorded: Option<u64>,
+    /// 各指标的环形缓冲区
+    series: HashMap<String, VecDeque<HistoryPoint>>,
+}
+
+impl HistoryStore {
+    /// 创建新的历史存储
+    ///
+    /// # Arguments
+    /// * `resolution_ms` - 记录分辨率（毫秒）
+    /// * `capacity` - 每个指标保留的最大点数
+    pub fn new(resolution_ms: u64, capacity: usize) -> Self {
+        Self {
+            resolution_ms,
+            capacity: capacity.max(1),
+            last_recorded: None,
+            series: HashMap::new(),
+        }
+    }
+
+    /// 更新分辨率和容量，容量缩小时丢弃最旧的数据
+    pub fn configure(&mut self, resolution_ms: u64, capacity: usize) {
+        self.resolution_ms = resolution_ms;
+        self.capacity = capacity.max(1);
+        for points in self.series.values_mut() {
+            while points.len() > self.capacity {
+                points.pop_front();
+            }
+        }
+    }
+
+    /// 记录一次采样
+    ///
+    /// # Arguments
+    /// * `timestamp` - 采样时间戳（毫秒）
+    /// * `system_info` - 采样数据
+    pub fn record(&mut self, timestamp: u64, system_info: &SystemInfo) {
+        if let Some(last) = self.last_recorded {
+            if timestamp.saturating_sub(last) < self.resolution_ms {
+                return;
+            }
+        }
+        self.last_recorded = Some(timestamp);
+
+        for (metric, value) in extract_metrics(system_info) {
+            let points = self
+                .series
+                .entry(metric)
+                .or_insert_with(|| VecDeque::with_capacity(self.capacity.min(1024)));
+            if points.len() >= self.capacity {
+                points.pop_front();
+            }
+            points.push_back(HistoryPoint { timestamp, value });
+        }
+    }
+
+    /// 获取所有已记录的指标名称
+    pub fn metrics(&self) -> Vec<String> {
+        let mut metrics: Vec<String> = self.series.keys().cloned().collect();
+        metrics.sort();
+        metrics
+    }
+
+    /// 查询指标历史
+    ///
+    /// # Arguments
+    /// * `metric` - 指标名称
+    /// * `from` - 起始时间戳（毫秒，含）
+    /// * `to` - 结束时间戳（毫秒，含）
+    /// * `max_points` - 返回的最大桶数
+    ///
+    /// # Returns
+    /// * `Option<Vec<HistoryBucket>>` - 指标不存在时返回 None
+    pub fn query(
+        &self,
+        metric: &str,
+        from: Option<u64>,
+        to: Option<u64>,
+        max_points: Option<usize>,
+    ) -> Option<Vec<HistoryBucket>> {
+        let points = self.series.get(metric)?;
+        let from = from.unwrap_or(0);
+        let to = to.unwrap_or(u64::MAX);
+
+        let selected: Vec<HistoryBucket> = points
+            .iter()
+            .filter(|point| point.timestamp >= from && point.timestamp <= to)
+            .map(|point| HistoryBucket {
+                timestamp: point.timestamp,
+                min: point.value,
+                max: point.value,
+                avg: point.value,
+                count: 1,
+            })
+            .collect();
+
+        Some(downsample(selected, max_points.unwrap_or(usize::MAX)))
+    }
+}
+
+/// 按时间将数据桶均匀合并，使数量不超过 `max_points`
+///
+/// 输入需按时间升序排列；合并时最小值、最大值取极值，平均值按样本数加权
+pub fn downsample(buckets: Vec<HistoryBucket>, max_points: usize) -> Vec<HistoryBucket> {
+    let max_points = max_points.max(1);
+    if buckets.len() <= max_points {
+        return buckets;
+    }
+
+    let first = buckets[0].timestamp;
+    let last = buckets[buckets.len() - 1].timestamp;
+    let span = last.saturating_sub(first) + 1;
+    let bucket_width = span.div_ceil(max_points as u64).max(1);
+
+    let mut merged: Vec<HistoryBucket> = Vec::with_capacity(max_points);
+    for bucket in buckets {
+        let bucket_start =
+            first + bucket.timestamp.saturating_sub(first) / bucket_width * bucket_width;
+
+        match merged.last_mut() {
+            Some(current) if current.timestamp == bucket_start => {
+                let total = current.count + bucket.count;
+                current.avg = (current.avg * current.count as f64
+                    + bucket.avg * bucket.count as f64)
+                    / total.max(1) as f64;
+                current.min = current.min.min(bucket.min);
+                current.max = current.max.max(bucket.max);
+                current.count = total;
+            }
+            _ => merged.push(HistoryBucket {
+                timestamp: bucket_start,
+                ..bucket
+            }),
+        }
+    }
+
+    merged
+}
+
+/// 将系统信息展开为扁平的指标列表
+///
+/// 指标名称使用点号分隔，例如 `cpu.usage`、`network.eth0.receive_rate`、
+/// `temperature.Package id 0`、`gpu.0.usage`
+pub fn extract_metrics(info: &SystemInfo) -> Vec<(String, f64)> {
+    let mut metrics = vec![
+        ("cpu.usage".to_string(), info.cpu_usage as f64),
+        (
+            "memory.usage_percent".to_string(),
+            info.memory.usage_percent as f64,
+        ),
+        ("memory.used".to_string(), info.memory.used as f64),
+        ("memory.swap_used".to_string(), info.memory.swap_used as f64),
+    ];
+
+    for core in &info.cpus {
+        metrics.push((
+            format!("cpu.{}.usage", core.name),
+            core.usage_percent as f64,
+        ));
+        metrics.push((
+            format!("cpu.{}.frequency", core.name),
+            core.frequency as f64,
+        ));
+    }
+
+    if let Some(times) = &info.cpu_times {
+        metrics.push(("cpu.iowait".to_string(), times.iowait as f64));
+        metrics.push(("cpu.steal".to_string(), times.steal as f64));
+    }
+
+    let mut total_receive_rate = 0.0;
+    let mut total_transmit_rate = 0.0;
+    for iface in &info.network.interfaces {
+        total_receive_rate += iface.receive_rate;
+        total_transmit_rate += iface.transmit_rate;
+        metrics.push((
+            format!("network.{}.receive_rate", iface.name),
+            iface.receive_rate,
+        ));
+        metrics.push((
+            format!("network.{}.transmit_rate", iface.name),
+            iface.transmit_rate,
+        ));
+    }
+    metrics.push(("network.receive_rate".to_string(), total_receive_rate));
+    metrics.push(("network.transmit_rate".to_string(), total_transmit_rate));
+
+    for disk in &info.disk.disks {
+        metrics.push((
+            format!("disk.{}.usage_percent", disk.mount_point),
+            disk.usage_percent as f64,
+        ));
+    }
+
+    for device in &info.block_devices {
+        metrics.push((
+            format!("block.{}.read_bytes_per_sec", device.name),
+            device.read_bytes_per_sec,
+        ));
+        metrics.push((
+            format!("block.{}.write_bytes_per_sec", device.name),
+            device.write_bytes_per_sec,
+        ));
+        metrics.push((
+            format!("block.{}.utilization_percent", device.name),
+            device.utilization_percent as f64,
+        ));
+    }
+
+    for temperature in &info.temperatures {
+        metrics.push((
+            format!("temperature.{}", temperature.label),
+            temperature.temperature as f64,
+        ));
+    }
+
+    // 只记录后端实际读到的GPU指标，不可用的指标不产生序列
+    for gpu in &info.gpus {
+        let mut push = |name: &str, value: Option<f64>| {
+            if let Some(value) = value {
+                metrics.push((format!("gpu.{}.{}", gpu.index, name), value));
+            }
+        };
+        push("usage", gpu.usage_percent.map(f64::from));
+        push("memory_used", gpu.memory.as_ref().map(|m| m.used as f64));
+        push(
+            "memory_usage_percent",
+            gpu.memory.as_ref().map(|m| m.usage_percent as f64),
+        );
+        push("temperature", gpu.temperature.map(f64::from));
+        push("frequency", gpu.frequency.map(|f| f as f64));
+        push("memory_frequency", gpu.memory_frequency.map(|f| f as f64));
+        push("power_draw_watts", gpu.power_draw_watts);
+        push("power_limit_watts", gpu.power_limit_watts);
+        push("fan_speed_percent", gpu.fan_speed_percent.map(f64::from));
+        push("performance_state", gpu.performance_state.map(f64::from));
+        // 空闲降频不算性能受限，其余任一原因生效时记为 1
+        push(
+            "throttled",
+            gpu.throttle_reasons.as_ref().map(|reasons| {
+                let throttled = reasons
+                    .iter()
+                    .any(|reason| *reason != GpuThrottleReason::GpuIdle);
+                if throttled {
+                    1.0
+                } else {
+                    0.0
+                }
+            }),
+        );
+        push(
+            "pcie_rx_bytes_per_sec",
+            gpu.pcie_rx_bytes_per_sec.map(|rate| rate as f64),
+        );
+        push(
+            "pcie_tx_bytes_per_sec",
+            gpu.pcie_tx_bytes_per_sec.map(|rate| rate as f64),
+        );
+    }
+
+    metrics
+}
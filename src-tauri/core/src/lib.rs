@@ -0,0 +1,58 @@
+//! 系统监控采集核心
+//!
+//! 提供与界面无关的系统信息采集能力，可在桌面应用、无界面服务和其他工具中复用：
+//!
+//! - [`SystemMonitor`]：采集 CPU、内存、网络、磁盘、温度、GPU 和进程信息
+//! - [`Collector`]、[`CollectorRegistry`]：可插拔的指标采集器，可注册自定义数据源
+//! - [`AdaptiveRefreshManager`]：根据系统负载自适应调整刷新频率
+//! - [`RetryManager`]：按错误类型自动重试
+//! - [`GpuMonitor`]：GPU 监控，NVIDIA 通过 NVML，Linux 上的 AMD/Intel 通过 sysfs/DRM
+//! - [`frame_monitor`]：帧率采集，Windows 通过 PresentMon，Linux 读取 [`mangohud`] 日志
+//! - [`models`]：所有可序列化的数据结构
+//! - [`history`]、[`storage`]、[`alerts`]、[`warnings`]、[`exporter`]：
+//!   指标历史、持久化、告警、系统警告和 OpenMetrics 导出
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use system_monitor_core::{models::MonitorConfig, SystemMonitor};
+//!
+//! # async fn sample() -> Result<(), String> {
+//! let mut monitor = SystemMonitor::new(MonitorConfig::default());
+//! let info = monitor.refresh().await?;
+//! println!("CPU: {:.1}%  内存: {:.1}%", info.cpu_usage, info.memory.usage_percent);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod adaptive_refresh;
+pub mod alerts;
+pub mod collectors;
+pub mod cpu_times;
+pub mod disk_io;
+pub mod errors;
+pub mod exporter;
+pub mod frame_monitor;
+pub mod gpu_drm;
+// 仅用于测试的模拟 GPU 后端，需启用 `mock` 特性
+#[cfg(any(test, feature = "mock"))]
+pub mod gpu_mock;
+pub mod gpu_monitor;
+pub mod gpu_nvml;
+pub mod headless;
+pub mod history;
+pub mod mangohud;
+pub mod models;
+pub mod monitor;
+pub mod process_control;
+pub mod processes;
+pub mod retry;
+pub mod storage;
+pub mod warnings;
+
+pub use adaptive_refresh::AdaptiveRefreshManager;
+pub use collectors::{Collector, CollectorRegistry, MetricSet};
+pub use errors::{MonitorError, MonitorResult};
+pub use gpu_monitor::{GpuBackend, GpuMonitor};
+pub use monitor::SystemMonitor;
+pub use retry::RetryManager;
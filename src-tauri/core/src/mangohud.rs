@@ -0,0 +1,148 @@
+//! MangoHud 帧率日志解析
+//!
+//! MangoHud 开启日志记录后，把每帧（或每个 `log_interval`）的数据追加写入 CSV 文件：
+//! 开头是系统信息和 `FRAME METRICS` 分隔行，随后是包含 `fps` 列的列头和数据行。
+//! 旧版本没有系统信息，文件直接从列头开始。汇总文件以 `_summary.csv` 结尾，不包含逐帧数据。
+
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 在日志开头查找列头时最多读取的行数
+const HEADER_SEARCH_LINES: usize = 16;
+
+/// 帧数据的列位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MangoHudColumns {
+    fps: usize,
+}
+
+impl MangoHudColumns {
+    /// 从列头行中找到帧率所在的列，不是列头时返回 `None`
+    pub fn from_header_line(line: &str) -> Option<Self> {
+        // 系统信息行（os,cpu,gpu,...）同样以逗号分隔，但不包含 fps 列
+        line.trim()
+            .split(',')
+            .position(|column| column.trim() == "fps")
+            .map(|fps| Self { fps })
+    }
+
+    /// 解析数据行中的帧率
+    ///
+    /// 跳过无法解析的行，以及没有换行结尾（MangoHud 仍在写入）的最后一行
+    pub fn parse_fps(&self, rows: &str) -> Vec<f32> {
+        rows.split_inclusive('\n')
+            .filter(|line| line.ends_with('\n'))
+            .filter_map(|line| line.trim().split(',').nth(self.fps)?.trim().parse().ok())
+            .filter(|fps: &f32| fps.is_finite() && *fps >= 0.0)
+            .collect()
+    }
+}
+
+/// 目录中的一个 MangoHud 日志
+#[derive(Debug, Clone)]
+pub struct MangoHudLogFile {
+    /// 日志路径
+    pub path: PathBuf,
+    /// 最后修改时间
+    pub modified: SystemTime,
+    /// 帧数据的列位置
+    pub columns: MangoHudColumns,
+}
+
+/// 解析完整的 MangoHud 日志
+///
+/// # Arguments
+/// * `content` - 日志内容
+///
+/// # Returns
+/// * `Option<Vec<f32>>` - 每条记录的帧率，找不到列头（不是 MangoHud 日志）时返回 `None`
+pub fn parse_log(content: &str) -> Option<Vec<f32>> {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n').take(HEADER_SEARCH_LINES) {
+        offset += line.len();
+        if let Some(columns) = MangoHudColumns::from_header_line(line) {
+            return Some(columns.parse_fps(&content[offset..]));
+        }
+    }
+    None
+}
+
+/// 读取日志开头的列头
+///
+/// # Arguments
+/// * `path` - 日志路径
+///
+/// # Returns
+/// * `io::Result<Option<MangoHudColumns>>` - 不是 MangoHud 日志时返回 `Ok(None)`
+pub fn read_columns(path: &Path) -> io::Result<Option<MangoHudColumns>> {
+    let reader = BufReader::new(File::open(path)?);
+    for line in reader.lines().take(HEADER_SEARCH_LINES) {
+        if let Some(columns) = MangoHudColumns::from_header_line(&line?) {
+            return Ok(Some(columns));
+        }
+    }
+    Ok(None)
+}
+
+/// 查找目录中最近写入的 MangoHud 日志
+///
+/// # Arguments
+/// * `dir` - MangoHud 的 `output_folder`
+///
+/// # Returns
+/// * `io::Result<Option<MangoHudLogFile>>` - 目录中没有 MangoHud 日志时返回 `Ok(None)`
+pub fn latest_log(dir: &Path) -> io::Result<Option<MangoHudLogFile>> {
+    let mut candidates: Vec<(PathBuf, SystemTime)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?;
+            if !name.ends_with(".csv") || name.ends_with("_summary.csv") {
+                return None;
+            }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    candidates.sort_by_key(|(_, modified)| Reverse(*modified));
+
+    // 目录中可能还有其他 CSV 文件，以列头确认是 MangoHud 日志
+    for (path, modified) in candidates {
+        if let Ok(Some(columns)) = read_columns(&path) {
+            return Ok(Some(MangoHudLogFile {
+                path,
+                modified,
+                columns,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// 读取日志从 `offset` 开始追加的内容
+///
+/// `offset` 落在一行中间时跳过该行剩余的部分，保证返回的内容从完整的行开始
+///
+/// # Arguments
+/// * `path` - 日志路径
+/// * `offset` - 开始读取的位置（通常为之前记录的文件长度）
+pub fn read_appended(path: &Path, offset: u64) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset.saturating_sub(1)))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let skip = if offset == 0 {
+        0
+    } else {
+        // 多读的一个字节是 offset 之前的字符，为换行符时 offset 正好位于行首
+        bytes
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(bytes.len(), |position| position + 1)
+    };
+    Ok(String::from_utf8_lossy(&bytes[skip..]).into_owned())
+}
@@ -0,0 +1,1191 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// 系统监控数据结构
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemInfo {
+    /// CPU使用率（百分比）
+    pub cpu_usage: f32,
+    /// 每个逻辑核心的使用情况
+    pub cpus: Vec<CpuCoreInfo>,
+    /// CPU时间分解（仅 Linux 可用）
+    pub cpu_times: Option<CpuTimeBreakdown>,
+    /// 内存使用情况
+    pub memory: MemoryInfo,
+    /// 网络使用情况
+    pub network: NetworkInfo,
+    /// 磁盘使用情况
+    pub disk: DiskInfo,
+    /// 块设备IO统计（仅 Linux 可用）
+    pub block_devices: Vec<BlockDeviceStats>,
+    /// 系统信息
+    pub system: SystemDetails,
+    /// 组件温度（如果可用）
+    pub temperatures: Vec<TemperatureInfo>,
+    /// GPU信息（每个被监控的设备一项）
+    #[serde(default)]
+    pub gpus: Vec<GpuInfo>,
+    /// 自定义采集器提供的指标
+    #[serde(default)]
+    pub custom_metrics: Vec<CustomMetric>,
+}
+
+/// 逻辑核心信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuCoreInfo {
+    /// 核心名称
+    pub name: String,
+    /// 使用率（百分比）
+    pub usage_percent: f32,
+    /// 当前频率（MHz）
+    pub frequency: u64,
+    /// CPU时间分解（仅 Linux 可用）
+    pub times: Option<CpuTimeBreakdown>,
+}
+
+/// CPU时间分解（两次采样之间各类时间的占比，百分比）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuTimeBreakdown {
+    /// 用户态
+    pub user: f32,
+    /// 低优先级用户态
+    pub nice: f32,
+    /// 内核态
+    pub system: f32,
+    /// 空闲
+    pub idle: f32,
+    /// 等待IO
+    pub iowait: f32,
+    /// 硬中断
+    pub irq: f32,
+    /// 软中断
+    pub softirq: f32,
+    /// 被虚拟机管理程序占用
+    pub steal: f32,
+}
+
+/// 内存信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    /// 总内存（字节）
+    pub total: u64,
+    /// 已使用内存（字节）
+    pub used: u64,
+    /// 可用内存（字节）
+    pub available: u64,
+    /// 使用率（百分比）
+    pub usage_percent: f32,
+    /// 交换区总大小（字节）
+    pub swap_total: u64,
+    /// 交换区已使用（字节）
+    pub swap_used: u64,
+}
+
+/// 网络信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    /// 网络接口列表
+    pub interfaces: Vec<NetworkInterface>,
+    /// 总下载量（字节）
+    pub total_received: u64,
+    /// 总上传量（字节）
+    pub total_transmitted: u64,
+}
+
+/// 网络接口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInterface {
+    /// 接口名称
+    pub name: String,
+    /// 接收量（字节）
+    pub received: u64,
+    /// 传输量（字节）
+    pub transmitted: u64,
+    /// 接收速率（字节/秒）
+    pub receive_rate: f64,
+    /// 传输速率（字节/秒）
+    pub transmit_rate: f64,
+}
+
+/// 磁盘信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskInfo {
+    /// 磁盘列表
+    pub disks: Vec<Disk>,
+}
+
+/// 磁盘
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Disk {
+    /// 磁盘名称
+    pub name: String,
+    /// 挂载点
+    pub mount_point: String,
+    /// 文件系统类型
+    pub file_system: String,
+    /// 总空间（字节）
+    pub total_space: u64,
+    /// 可用空间（字节）
+    pub available_space: u64,
+    /// 使用空间（字节）
+    pub used_space: u64,
+    /// 使用率（百分比）
+    pub usage_percent: f32,
+}
+
+/// 块设备IO统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDeviceStats {
+    /// 设备名称
+    pub name: String,
+    /// 读取速率（字节/秒）
+    pub read_bytes_per_sec: f64,
+    /// 写入速率（字节/秒）
+    pub write_bytes_per_sec: f64,
+    /// 每秒读请求数
+    pub read_iops: f64,
+    /// 每秒写请求数
+    pub write_iops: f64,
+    /// 平均队列深度
+    pub avg_queue_depth: f64,
+    /// 设备利用率（百分比）
+    pub utilization_percent: f32,
+}
+
+/// 系统详情
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemDetails {
+    /// 系统名称
+    pub name: Option<String>,
+    /// 内核版本
+    pub kernel_version: Option<String>,
+    /// 操作系统版本
+    pub os_version: Option<String>,
+    /// 主机名
+    pub host_name: Option<String>,
+    /// CPU核心数
+    pub cpu_count: usize,
+    /// CPU品牌
+    pub cpu_brand: Option<String>,
+    /// CPU频率（MHz）
+    pub cpu_frequency: Option<u64>,
+}
+
+/// 温度信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemperatureInfo {
+    /// 组件名称
+    pub label: String,
+    /// 当前温度（摄氏度）
+    pub temperature: f32,
+    /// 最高温度（摄氏度）
+    pub max: Option<f32>,
+    /// 临界温度（摄氏度）
+    pub critical: Option<f32>,
+    /// 归类信息（前端用于分组）
+    pub category: Option<String>,
+}
+
+/// 自定义指标（由注册的自定义采集器提供）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomMetric {
+    /// 指标名称
+    pub name: String,
+    /// 指标值
+    pub value: f64,
+    /// 单位（如 "celsius"、"percent"）
+    pub unit: Option<String>,
+}
+
+/// 采集器运行状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectorStatus {
+    /// 采集器名称
+    pub name: String,
+    /// 当前配置下是否启用
+    pub enabled: bool,
+    /// 采集间隔（毫秒，0 表示每次刷新都采集，`None` 表示只采集一次）
+    pub interval_ms: Option<u64>,
+    /// 最近一次成功采集的时间戳（毫秒）
+    pub last_success: Option<u64>,
+    /// 最近一次采集失败的错误信息
+    pub last_error: Option<String>,
+    /// 连续失败次数
+    pub consecutive_failures: u32,
+}
+
+/// GPU信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuInfo {
+    /// 设备索引
+    pub index: u32,
+    /// GPU名称
+    pub name: String,
+    /// 设备UUID
+    pub uuid: Option<String>,
+    /// PCI 总线ID（如 `00000000:01:00.0`）
+    pub pci_bus_id: Option<String>,
+    /// GPU使用率（百分比），不可用时为 `None`，原因见 `unavailable`
+    pub usage_percent: Option<f32>,
+    /// GPU内存使用情况，不可用时为 `None`
+    pub memory: Option<GpuMemoryInfo>,
+    /// GPU温度（摄氏度）
+    pub temperature: Option<f32>,
+    /// GPU频率（MHz）
+    pub frequency: Option<u64>,
+    /// 显存频率（MHz）
+    pub memory_frequency: Option<u64>,
+    /// 当前功耗（W）
+    pub power_draw_watts: Option<f64>,
+    /// 生效的功耗上限（W）
+    pub power_limit_watts: Option<f64>,
+    /// 风扇转速（百分比）
+    pub fan_speed_percent: Option<u32>,
+    /// 性能状态，0 表示 P0（性能最高），15 表示 P15
+    pub performance_state: Option<u32>,
+    /// 当前生效的降频原因，空列表表示没有降频
+    pub throttle_reasons: Option<Vec<GpuThrottleReason>>,
+    /// PCIe 接收吞吐量（字节/秒）
+    pub pcie_rx_bytes_per_sec: Option<u64>,
+    /// PCIe 发送吞吐量（字节/秒）
+    pub pcie_tx_bytes_per_sec: Option<u64>,
+    /// 本次采样中不可用的指标及原因
+    #[serde(default)]
+    pub unavailable: BTreeMap<GpuMetric, MetricUnavailableReason>,
+}
+
+/// GPU周期采样中的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuMetric {
+    /// 使用率
+    Usage,
+    /// 显存
+    Memory,
+    /// 温度
+    Temperature,
+    /// 图形频率
+    Frequency,
+    /// 显存频率
+    MemoryFrequency,
+    /// 功耗
+    PowerDraw,
+    /// 功耗上限
+    PowerLimit,
+    /// 风扇转速
+    FanSpeed,
+    /// 性能状态
+    PerformanceState,
+    /// 降频原因
+    ThrottleReasons,
+    /// PCIe 吞吐量
+    PcieThroughput,
+}
+
+/// 指标不可用的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricUnavailableReason {
+    /// 设备或驱动不支持（之后的采样不再查询）
+    NotSupported,
+    /// 权限不足
+    PermissionDenied,
+    /// 设备已断开
+    DeviceLost,
+    /// 读取失败
+    ReadFailed,
+}
+
+/// GPU内存信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuMemoryInfo {
+    /// 总内存（字节）
+    pub total: u64,
+    /// 已使用内存（字节）
+    pub used: u64,
+    /// 使用率（百分比）
+    pub usage_percent: f32,
+}
+
+impl GpuMemoryInfo {
+    /// 根据总量和已使用量创建显存信息
+    pub fn new(total: u64, used: u64) -> Self {
+        let usage_percent = if total > 0 {
+            (used as f32 / total as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            total,
+            used,
+            usage_percent,
+        }
+    }
+}
+
+/// GPU详细状态（按需查询，设备或驱动不支持的字段为 `None`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuDetails {
+    /// 设备索引
+    pub index: u32,
+    /// GPU名称
+    pub name: String,
+    /// 设备UUID
+    pub uuid: Option<String>,
+    /// PCI 总线ID
+    pub pci_bus_id: Option<String>,
+    /// 驱动版本
+    pub driver_version: Option<String>,
+    /// VBIOS 版本
+    pub vbios_version: Option<String>,
+    /// 当前 PCIe 代数
+    pub pcie_generation: Option<u32>,
+    /// 当前 PCIe 通道数
+    pub pcie_width: Option<u32>,
+    /// GPU使用率（百分比）
+    pub gpu_utilization: Option<f32>,
+    /// 显存控制器使用率（百分比）
+    pub memory_utilization: Option<f32>,
+    /// 总显存（字节）
+    pub memory_total: Option<u64>,
+    /// 已使用显存（字节）
+    pub memory_used: Option<u64>,
+    /// GPU温度（摄氏度）
+    pub temperature: Option<f32>,
+    /// 当前功耗（瓦）
+    pub power_draw_watts: Option<f64>,
+    /// 功耗上限（瓦）
+    pub power_limit_watts: Option<f64>,
+    /// 风扇转速（百分比）
+    pub fan_speed_percent: Option<u32>,
+    /// 各时钟域的频率
+    pub clocks: Vec<GpuClockInfo>,
+    /// 当前生效的降频原因
+    pub throttle_reasons: Vec<GpuThrottleReason>,
+    /// ECC 错误计数（未启用 ECC 或不支持时为 `None`）
+    pub ecc_errors: Option<GpuEccErrors>,
+    /// 视频编码器使用率（百分比）
+    pub encoder_utilization: Option<f32>,
+    /// 视频解码器使用率（百分比）
+    pub decoder_utilization: Option<f32>,
+}
+
+/// GPU时钟域
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuClockDomain {
+    /// 图形
+    Graphics,
+    /// 流式多处理器
+    Sm,
+    /// 显存
+    Memory,
+    /// 视频编解码
+    Video,
+}
+
+/// 时钟域频率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuClockInfo {
+    /// 时钟域
+    pub domain: GpuClockDomain,
+    /// 当前频率（MHz）
+    pub current_mhz: Option<u32>,
+    /// 最高频率（MHz）
+    pub max_mhz: Option<u32>,
+}
+
+/// GPU降频原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuThrottleReason {
+    /// GPU 空闲
+    GpuIdle,
+    /// 应用或用户设置的时钟限制
+    ApplicationsClocksSetting,
+    /// 软件功耗上限
+    SwPowerCap,
+    /// 硬件降频（过热或电源异常）
+    HwSlowdown,
+    /// 与同组 GPU 同步加速
+    SyncBoost,
+    /// 软件温度保护
+    SwThermalSlowdown,
+    /// 硬件温度保护
+    HwThermalSlowdown,
+    /// 外部电源制动
+    HwPowerBrakeSlowdown,
+    /// 显示时钟设置
+    DisplayClockSetting,
+}
+
+/// ECC 错误计数（自驱动加载以来）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuEccErrors {
+    /// 已纠正的错误
+    pub corrected: u64,
+    /// 未纠正的错误
+    pub uncorrected: u64,
+}
+
+/// 使用GPU的进程
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuProcessInfo {
+    /// 进程ID
+    pub pid: u32,
+    /// 进程名称（进程已退出或无法读取时为 `None`）
+    pub name: Option<String>,
+    /// 占用的显存（字节）
+    pub used_memory: Option<u64>,
+    /// SM（3D/计算）使用率（百分比）
+    pub sm_utilization: Option<f32>,
+    /// 视频编码器使用率（百分比）
+    pub encoder_utilization: Option<f32>,
+    /// 视频解码器使用率（百分比）
+    pub decoder_utilization: Option<f32>,
+}
+
+/// 用于识别GPU设备的信息，重新探测时据此判断设备的增减
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GpuDeviceId {
+    /// GPU名称
+    pub name: String,
+    /// 设备UUID
+    pub uuid: Option<String>,
+    /// PCI 总线ID
+    pub pci_bus_id: Option<String>,
+}
+
+/// GPU可用性或设备变化事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuStatusChange {
+    /// 变化前是否有可用的GPU
+    pub previously_available: bool,
+    /// 变化后是否有可用的GPU
+    pub available: bool,
+    /// 新出现的设备
+    pub added: Vec<GpuDeviceId>,
+    /// 消失的设备
+    pub removed: Vec<GpuDeviceId>,
+    /// 变化时间戳（毫秒）
+    pub timestamp: u64,
+}
+
+/// GPU监控状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuMonitorStatus {
+    /// 是否有可用的GPU
+    pub available: bool,
+    /// 最近一次探测失败的原因
+    pub error: Option<String>,
+    /// 当前监控的设备
+    pub devices: Vec<GpuDeviceId>,
+    /// 距离下一次重新探测的时间（毫秒），不会重新探测时为 `None`
+    pub next_probe_in_ms: Option<u64>,
+    /// 最近一次可用性或设备变化
+    pub last_change: Option<GpuStatusChange>,
+}
+
+/// 进程信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    /// 进程ID
+    pub pid: u32,
+    /// 父进程ID
+    pub parent_pid: Option<u32>,
+    /// 进程名称
+    pub name: String,
+    /// 完整命令行
+    pub command: String,
+    /// 所属用户
+    pub user: Option<String>,
+    /// 进程状态
+    pub state: String,
+    /// CPU使用率（百分比，多核时可能超过100）
+    pub cpu_usage: f32,
+    /// 常驻内存（字节）
+    pub memory: u64,
+    /// 虚拟内存（字节）
+    pub virtual_memory: u64,
+    /// 累计磁盘读取量（字节）
+    pub disk_read_bytes: u64,
+    /// 累计磁盘写入量（字节）
+    pub disk_written_bytes: u64,
+    /// 启动时间（Unix 时间戳，秒）
+    pub start_time: u64,
+}
+
+/// 进程树节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessTreeNode {
+    /// 进程自身信息
+    pub process: ProcessInfo,
+    /// 子树（含自身）CPU使用率总和（百分比）
+    pub subtree_cpu_usage: f32,
+    /// 子树（含自身）常驻内存总和（字节）
+    pub subtree_memory: u64,
+    /// 子树（含自身）进程数量
+    pub subtree_process_count: usize,
+    /// 子进程
+    pub children: Vec<ProcessTreeNode>,
+}
+
+/// 进程排序字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessSortKey {
+    Pid,
+    Name,
+    User,
+    Cpu,
+    Memory,
+    VirtualMemory,
+    DiskRead,
+    DiskWrite,
+    StartTime,
+}
+
+/// 进程查询条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessQuery {
+    /// 排序字段
+    pub sort_by: ProcessSortKey,
+    /// 是否降序
+    pub descending: bool,
+    /// 名称过滤（匹配进程名或命令行，不区分大小写）
+    pub name_filter: Option<String>,
+    /// 用户过滤（不区分大小写）
+    pub user_filter: Option<String>,
+    /// 返回数量上限（Top-N）
+    pub limit: Option<usize>,
+}
+
+impl Default for ProcessQuery {
+    fn default() -> Self {
+        Self {
+            sort_by: ProcessSortKey::Cpu,
+            descending: true,
+            name_filter: None,
+            user_filter: None,
+            limit: None,
+        }
+    }
+}
+
+/// 进程控制操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessAction {
+    /// 请求结束（SIGTERM）
+    Terminate,
+    /// 强制结束（SIGKILL）
+    Kill,
+    /// 暂停（SIGSTOP）
+    Suspend,
+    /// 恢复（SIGCONT）
+    Resume,
+    /// 调整 nice 值
+    Renice { nice: i32 },
+    /// 设置 CPU 亲和性
+    SetAffinity { cpus: Vec<usize> },
+}
+
+/// 进程操作确认请求（需再次调用确认命令才会真正执行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessActionRequest {
+    /// 确认令牌
+    pub token: String,
+    /// 目标进程ID
+    pub pid: u32,
+    /// 目标进程名称
+    pub process_name: String,
+    /// 待执行的操作
+    pub action: ProcessAction,
+    /// 令牌有效期（毫秒）
+    pub expires_in_ms: u64,
+}
+
+/// 帧率统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameStats {
+    /// 平均帧率
+    pub average_fps: f32,
+    /// 采样数量
+    pub sample_count: u32,
+    /// 采集时长（毫秒）
+    pub duration_ms: u64,
+    /// 时间戳（毫秒）
+    pub timestamp: u64,
+    /// 数据来源
+    pub source: FrameDataSource,
+}
+
+/// 帧率数据来源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameDataSource {
+    PresentMon,
+    /// Linux 上由 MangoHud 写入的帧率日志
+    MangoHud,
+    Unsupported,
+    MissingDependency,
+}
+
+/// 监控配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    /// 刷新间隔（毫秒）- 仅在固定频率模式下使用
+    pub refresh_interval: u64,
+    /// 是否启用CPU监控
+    pub enable_cpu: bool,
+    /// 是否启用内存监控
+    pub enable_memory: bool,
+    /// 是否启用网络监控
+    pub enable_network: bool,
+    /// 是否启用磁盘监控
+    pub enable_disk: bool,
+    /// 是否启用温度监控
+    pub enable_temperature: bool,
+    /// 是否启用GPU监控
+    pub enable_gpu: bool,
+    /// 要监控的GPU，按设备索引、UUID 或 PCI 总线ID 匹配；为空表示监控全部设备
+    pub gpu_devices: Vec<String>,
+    /// 是否启用进程监控
+    pub enable_processes: bool,
+    /// 历史记录分辨率（毫秒）
+    pub history_resolution_ms: u64,
+    /// 每个指标保留的历史点数
+    pub history_capacity: usize,
+    /// 各子系统的采集间隔
+    pub intervals: CollectorIntervals,
+    /// 持久化存储配置
+    pub storage: StorageConfig,
+    /// 系统警告通知配置
+    pub notifications: NotificationConfig,
+    /// Prometheus/OpenMetrics 导出配置
+    pub exporter: ExporterConfig,
+    /// 帧率采集配置
+    pub frame_capture: FrameCaptureConfig,
+    /// 刷新策略类型
+    pub refresh_strategy: RefreshStrategyType,
+}
+
+/// 各子系统的采集间隔（毫秒）
+///
+/// 刷新时只运行到期的采集器，其余子系统沿用上一次的结果；0 表示每次刷新都采集
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectorIntervals {
+    /// CPU 使用率和核心信息
+    pub cpu_ms: u64,
+    /// 内存信息
+    pub memory_ms: u64,
+    /// 网络接口和速率
+    pub network_ms: u64,
+    /// 磁盘空间
+    pub disk_ms: u64,
+    /// 块设备IO统计
+    pub disk_io_ms: u64,
+    /// 组件温度
+    pub temperature_ms: u64,
+    /// GPU信息
+    pub gpu_ms: u64,
+    /// 进程列表
+    pub processes_ms: u64,
+    /// 系统详情，`None` 表示仅在启动时采集一次
+    pub system_details_ms: Option<u64>,
+}
+
+impl Default for CollectorIntervals {
+    fn default() -> Self {
+        Self {
+            cpu_ms: 500,
+            memory_ms: 1000,
+            network_ms: 1000,
+            disk_ms: 10_000,
+            disk_io_ms: 1000,
+            temperature_ms: 2000,
+            gpu_ms: 1000,
+            processes_ms: 2000,
+            system_details_ms: None,
+        }
+    }
+}
+
+/// 持久化存储配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// 是否启用持久化存储
+    pub enabled: bool,
+    /// 原始样本保留时长（小时）
+    pub raw_retention_hours: u64,
+    /// 1分钟聚合数据保留时长（天）
+    pub minute_retention_days: u64,
+    /// 1小时聚合数据保留时长（天）
+    pub hour_retention_days: u64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            raw_retention_hours: 6,
+            minute_retention_days: 30,
+            hour_retention_days: 365,
+        }
+    }
+}
+
+/// 系统警告通知配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// 是否启用系统通知和托盘警告
+    pub enabled: bool,
+    /// 高负载判定的CPU使用率阈值（百分比）
+    pub cpu_threshold: f32,
+    /// 高负载判定的内存使用率阈值（百分比）
+    pub memory_threshold: f32,
+    /// 高负载持续多久后发出警告（秒）
+    pub high_load_duration_secs: u64,
+    /// 温度距离临界值小于该值时发出警告（摄氏度）
+    pub temperature_margin: f32,
+    /// 传感器没有临界值时使用的温度警告阈值（摄氏度）
+    pub temperature_threshold: f32,
+    /// 磁盘使用率警告阈值（百分比）
+    pub disk_usage_threshold: f32,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cpu_threshold: 80.0,
+            memory_threshold: 85.0,
+            high_load_duration_secs: 60,
+            temperature_margin: 5.0,
+            temperature_threshold: 90.0,
+            disk_usage_threshold: 90.0,
+        }
+    }
+}
+
+/// Prometheus/OpenMetrics 导出配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExporterConfig {
+    /// 是否启用 `/metrics` HTTP 服务
+    pub enabled: bool,
+    /// 监听地址（默认仅本机可访问，端口为 0 时由系统分配）
+    pub listen_address: String,
+}
+
+impl Default for ExporterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: "127.0.0.1:9184".to_string(),
+        }
+    }
+}
+
+/// 帧率采集配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameCaptureConfig {
+    /// MangoHud 日志目录（即 MangoHud 的 `output_folder`），未配置时使用用户主目录；仅 Linux 使用
+    pub mangohud_log_dir: Option<String>,
+}
+
+/// 导出服务状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExporterStatus {
+    /// 是否正在运行
+    pub running: bool,
+    /// 实际监听的地址（未运行时为 None）
+    pub address: Option<String>,
+}
+
+/// 系统警告类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// 持续高负载
+    HighLoad,
+    /// 温度接近临界值
+    Temperature,
+    /// 磁盘使用率过高
+    DiskUsage,
+}
+
+/// 系统警告记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarningEntry {
+    /// 警告ID（应用运行期间递增）
+    pub id: u64,
+    /// 警告类型
+    pub kind: WarningKind,
+    /// 相关指标名称（与历史记录的指标名称一致，如 `cpu.usage`）
+    pub metric: String,
+    /// 警告内容
+    pub message: String,
+    /// 触发时的指标值
+    pub value: f64,
+    /// 触发时间戳（毫秒）
+    pub timestamp: u64,
+}
+
+/// 持久化数据层级
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageResolution {
+    /// 原始样本
+    Raw,
+    /// 1分钟聚合
+    Minute,
+    /// 1小时聚合
+    Hour,
+}
+
+/// 刷新策略类型（用于序列化）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RefreshStrategyType {
+    /// 固定频率
+    Fixed { interval_ms: u64 },
+    /// 自适应频率
+    Adaptive {
+        min_interval_ms: u64,
+        max_interval_ms: u64,
+        cpu_threshold: f32,
+        memory_threshold: f32,
+        change_threshold: f32,
+    },
+    /// 节能模式
+    PowerSaving {
+        base_interval_ms: u64,
+        idle_interval_ms: u64,
+        active_interval_ms: u64,
+    },
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: 1000, // 1秒
+            enable_cpu: true,
+            enable_memory: true,
+            enable_network: true,
+            enable_disk: true,
+            enable_temperature: true,
+            enable_gpu: true,
+            gpu_devices: Vec::new(),
+            enable_processes: true,
+            history_resolution_ms: 1000,
+            history_capacity: 3600, // 1秒分辨率下保留1小时
+            intervals: CollectorIntervals::default(),
+            storage: StorageConfig::default(),
+            notifications: NotificationConfig::default(),
+            exporter: ExporterConfig::default(),
+            frame_capture: FrameCaptureConfig::default(),
+            refresh_strategy: RefreshStrategyType::Adaptive {
+                min_interval_ms: 500,
+                max_interval_ms: 5000,
+                cpu_threshold: 30.0,
+                memory_threshold: 70.0,
+                change_threshold: 5.0,
+            },
+        }
+    }
+}
+
+impl From<Duration> for MonitorConfig {
+    fn from(duration: Duration) -> Self {
+        Self {
+            refresh_interval: duration.as_millis() as u64,
+            ..Default::default()
+        }
+    }
+}
+
+/// 历史数据桶（降采样后的一段时间内的统计值）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryBucket {
+    /// 桶起始时间戳（毫秒）
+    pub timestamp: u64,
+    /// 最小值
+    pub min: f64,
+    /// 最大值
+    pub max: f64,
+    /// 平均值
+    pub avg: f64,
+    /// 桶内样本数
+    pub count: u32,
+}
+
+/// 告警规则
+///
+/// `metric` 为 `SystemInfo` 中的字段路径，字段之间用点号分隔，数组可用 `[key=value]` 按字段筛选、
+/// `[index]` 按下标选取或 `[*]` 选取全部元素，例如 `cpu_usage`、`memory.usage_percent`、
+/// `temperatures[category=cpu-package]`、`disk.disks[mount_point=/].usage_percent`。
+/// 路径指向对象时取其主数值字段（`temperature`、`usage_percent` 或 `value`）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// 规则ID
+    pub id: String,
+    /// 规则名称
+    pub name: String,
+    /// 是否启用
+    #[serde(default = "default_alert_enabled")]
+    pub enabled: bool,
+    /// 指标路径
+    pub metric: String,
+    /// 比较方式
+    pub comparison: AlertComparison,
+    /// 触发阈值
+    pub trigger_threshold: f64,
+    /// 解除阈值（与触发阈值之间的差值即为回差）
+    pub clear_threshold: f64,
+    /// 条件需持续满足的时长（毫秒），为 0 时立即触发
+    #[serde(default)]
+    pub for_ms: u64,
+}
+
+fn default_alert_enabled() -> bool {
+    true
+}
+
+/// 告警比较方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertComparison {
+    /// 指标高于触发阈值时告警，低于解除阈值时解除（路径匹配多个值时取最大值）
+    Above,
+    /// 指标低于触发阈值时告警，高于解除阈值时解除（路径匹配多个值时取最小值）
+    Below,
+}
+
+/// 告警状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    /// 未触发
+    Inactive,
+    /// 已满足触发条件，等待持续时长
+    Pending,
+    /// 告警中
+    Firing,
+    /// 已解除
+    Resolved,
+}
+
+/// 告警规则的当前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertStatus {
+    /// 规则ID
+    pub rule_id: String,
+    /// 当前状态
+    pub state: AlertState,
+    /// 最近一次评估得到的指标值（路径无匹配时为 None）
+    pub value: Option<f64>,
+    /// 进入当前状态的时间戳（毫秒）
+    pub since: u64,
+    /// 最近一次评估的时间戳（毫秒）
+    pub last_evaluated: u64,
+}
+
+/// 告警状态变化事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// 规则ID
+    pub rule_id: String,
+    /// 规则名称
+    pub rule_name: String,
+    /// 变化前的状态
+    pub previous_state: AlertState,
+    /// 变化后的状态
+    pub state: AlertState,
+    /// 触发变化时的指标值
+    pub value: Option<f64>,
+    /// 变化时间戳（毫秒）
+    pub timestamp: u64,
+}
+
+/// 增量更新数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemInfoDelta {
+    /// 更新的时间戳
+    pub timestamp: u64,
+    /// CPU使用率（如果有变化）
+    pub cpu_usage: Option<f32>,
+    /// 逻辑核心信息（如果有变化）
+    pub cpus: Option<Vec<CpuCoreInfo>>,
+    /// CPU时间分解（如果有变化）
+    pub cpu_times: Option<CpuTimeBreakdown>,
+    /// 内存信息（如果有变化）
+    pub memory: Option<MemoryInfo>,
+    /// 网络信息（总是更新，因为包含速率）
+    pub network: Option<NetworkInfo>,
+    /// 磁盘信息（如果有变化）
+    pub disk: Option<DiskInfo>,
+    /// 块设备IO统计（总是更新，因为包含速率）
+    pub block_devices: Option<Vec<BlockDeviceStats>>,
+    /// 系统信息（很少变化）
+    pub system: Option<SystemDetails>,
+    /// 温度信息（如果有变化）
+    pub temperatures: Option<Vec<TemperatureInfo>>,
+    /// GPU信息（总是更新，因为包含使用率）
+    pub gpus: Option<Vec<GpuInfo>>,
+    /// 自定义指标（如果有变化）
+    pub custom_metrics: Option<Vec<CustomMetric>>,
+    /// 完整数据（用于初始化或重大变化时）
+    pub full_data: Option<SystemInfo>,
+}
+
+impl SystemInfoDelta {
+    /// 创建完整的增量更新（用于初始化）
+    pub fn full(system_info: SystemInfo) -> Self {
+        let full_data = system_info.clone();
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            cpu_usage: Some(system_info.cpu_usage),
+            cpus: Some(system_info.cpus),
+            cpu_times: system_info.cpu_times,
+            memory: Some(system_info.memory),
+            network: Some(system_info.network),
+            disk: Some(system_info.disk),
+            block_devices: Some(system_info.block_devices),
+            system: Some(system_info.system),
+            temperatures: Some(system_info.temperatures),
+            gpus: Some(system_info.gpus),
+            custom_metrics: Some(system_info.custom_metrics),
+            full_data: Some(full_data),
+        }
+    }
+
+    /// 创建增量更新（比较新旧数据）
+    pub fn from_diff(old: &SystemInfo, new: &SystemInfo) -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let cpu_usage = if (old.cpu_usage - new.cpu_usage).abs() > 0.5 {
+            Some(new.cpu_usage)
+        } else {
+            None
+        };
+
+        let cpus = if old.cpus.len() != new.cpus.len()
+            || old
+                .cpus
+                .iter()
+                .zip(new.cpus.iter())
+                .any(|(old_core, new_core)| {
+                    (old_core.usage_percent - new_core.usage_percent).abs() > 0.5
+                        || old_core.frequency != new_core.frequency
+                }) {
+            Some(new.cpus.clone())
+        } else {
+            None
+        };
+
+        // CPU时间分解与 CPU 使用率同步更新
+        let cpu_times = if cpu_usage.is_some() || cpus.is_some() {
+            new.cpu_times.clone()
+        } else {
+            None
+        };
+
+        let memory = if (old.memory.usage_percent - new.memory.usage_percent).abs() > 0.5 {
+            Some(new.memory.clone())
+        } else {
+            None
+        };
+
+        let disk =
+            if !old.disk.disks.is_empty() && !new.disk.disks.is_empty() {
+                // 简单比较磁盘数量和使用率变化
+                if old.disk.disks.len() != new.disk.disks.len()
+                    || old.disk.disks.iter().zip(new.disk.disks.iter()).any(
+                        |(old_disk, new_disk)| {
+                            (old_disk.usage_percent - new_disk.usage_percent).abs() > 1.0
+                        },
+                    )
+                {
+                    Some(new.disk.clone())
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+        let system = if old.system.cpu_count != new.system.cpu_count
+            || old.system.cpu_brand != new.system.cpu_brand
+            || old.system.cpu_frequency != new.system.cpu_frequency
+        {
+            Some(new.system.clone())
+        } else {
+            None
+        };
+
+        let temperatures = if old.temperatures.len() != new.temperatures.len()
+            || old
+                .temperatures
+                .iter()
+                .zip(new.temperatures.iter())
+                .any(|(old_temp, new_temp)| {
+                    (old_temp.temperature - new_temp.temperature).abs() > 1.0
+                }) {
+            Some(new.temperatures.clone())
+        } else {
+            None
+        };
+
+        let custom_metrics = if old.custom_metrics != new.custom_metrics {
+            Some(new.custom_metrics.clone())
+        } else {
+            None
+        };
+
+        Self {
+            timestamp,
+            cpu_usage,
+            cpus,
+            cpu_times,
+            memory,
+            network: Some(new.network.clone()), // 网络信息总是更新（包含速率）
+            disk,
+            block_devices: Some(new.block_devices.clone()), // 块设备IO总是更新（包含速率）
+            system,
+            temperatures,
+            gpus: Some(new.gpus.clone()), // GPU信息总是更新（包含使用率）
+            custom_metrics,
+            full_data: None,
+        }
+    }
+
+    /// 检查是否为空更新（没有实际变化）
+    pub fn is_empty(&self) -> bool {
+        self.cpu_usage.is_none()
+            && self.cpus.is_none()
+            && self.cpu_times.is_none()
+            && self.memory.is_none()
+            && self.network.is_none()
+            && self.disk.is_none()
+            && self.block_devices.is_none()
+            && self.system.is_none()
+            && self.temperatures.is_none()
+            && self.gpus.is_none()
+            && self.custom_metrics.is_none()
+            && self.full_data.is_none()
+    }
+}
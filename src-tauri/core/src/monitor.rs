@@ -0,0 +1,411 @@
+use crate::adaptive_refresh::{AdaptiveRefreshManager, RefreshStatistics};
+use crate::collectors::{CollectContext, Collector, CollectorRegistry, MetricSet};
+use crate::errors::{MonitorError, MonitorResult};
+use crate::frame_monitor::FrameMonitor;
+use crate::gpu_monitor::GpuMonitor;
+use crate::models::*;
+use crate::processes::ProcessCollector;
+use crate::retry::RetryConfig;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+use sysinfo::{CpuRefreshKind, System};
+use tokio::time::sleep;
+
+/// 刷新过程使用的可变状态，由互斥锁串行化并发的刷新
+///
+/// 只有采样（在阻塞线程中）和采集器的注册、移除会获取该锁，查询命令读取各自的快照
+struct SamplingState {
+    system: System,
+    collectors: CollectorRegistry,
+    process_collector: ProcessCollector,
+    last_cpu_refresh: Option<Instant>,
+    last_process_refresh: Option<Instant>,
+}
+
+/// 一次采样在阻塞线程中得到的结果
+struct Sample {
+    sets: Vec<MetricSet>,
+    statuses: Vec<CollectorStatus>,
+    /// 本次刷新了进程表时的新快照
+    processes: Option<Vec<ProcessInfo>>,
+}
+
+/// GPU重新探测的初始间隔
+const GPU_PROBE_INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// GPU重新探测的最长间隔，设备没有变化时按此间隔检查热插拔
+const GPU_PROBE_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// 系统监控器（优化为异步安全，支持智能重试和自适应刷新）
+///
+/// 刷新和查询都只需要 `&self`：调用方可以在读锁下刷新。采集器在阻塞线程中运行，
+/// 采集器状态、GPU 信息和进程表在每次采样后发布为快照，查询命令不会等待正在进行的采样。
+/// CPU 使用率由相邻两次刷新之间的差值计算，刷新过程中不会休眠。
+pub struct SystemMonitor {
+    sampling: Arc<Mutex<SamplingState>>,
+    config: MonitorConfig,
+    gpu_monitor: Arc<GpuMonitor>,
+    latest_gpus: RwLock<Vec<GpuInfo>>,
+    collector_statuses: RwLock<Vec<CollectorStatus>>,
+    processes: RwLock<Arc<Vec<ProcessInfo>>>,
+    frame_monitor: FrameMonitor,
+    adaptive_refresh: Mutex<AdaptiveRefreshManager>,
+}
+
+impl SystemMonitor {
+    /// 创建新的系统监控器
+    pub fn new(config: MonitorConfig) -> Self {
+        // 首次刷新同时作为 CPU 使用率的计算基准
+        let mut system = System::new();
+        system.refresh_cpu_all();
+        system.refresh_memory();
+        let mut process_collector = ProcessCollector::new();
+        let processes = if config.enable_processes {
+            process_collector.refresh()
+        } else {
+            Vec::new()
+        };
+
+        // 初始化GPU监控器，之后按退避间隔重新探测，驱动延迟加载或接入外接显卡后自动恢复
+        let gpu_monitor = GpuMonitor::with_prober(
+            GpuMonitor::probe_system,
+            RetryConfig::new()
+                .initial_delay(GPU_PROBE_INITIAL_DELAY)
+                .max_delay(GPU_PROBE_MAX_DELAY),
+        );
+        if gpu_monitor.is_available() {
+            log::info!("GPU监控器初始化成功");
+        }
+
+        // 创建自适应刷新管理器
+        let adaptive_refresh = AdaptiveRefreshManager::new(config.refresh_strategy.clone().into());
+        let frame_monitor = FrameMonitor::with_config(&config.frame_capture);
+
+        let gpu_monitor = Arc::new(gpu_monitor);
+        let collectors = CollectorRegistry::with_builtin(gpu_monitor.clone());
+        let collector_statuses = collectors.statuses(&config);
+
+        Self {
+            sampling: Arc::new(Mutex::new(SamplingState {
+                system,
+                collectors,
+                process_collector,
+                last_cpu_refresh: Some(Instant::now()),
+                last_process_refresh: Some(Instant::now()),
+            })),
+            config,
+            gpu_monitor,
+            latest_gpus: RwLock::new(Vec::new()),
+            collector_statuses: RwLock::new(collector_statuses),
+            processes: RwLock::new(Arc::new(processes)),
+            frame_monitor,
+            adaptive_refresh: Mutex::new(adaptive_refresh),
+        }
+    }
+
+    /// 刷新系统信息（异步版本，提升性能，异步安全，支持智能重试）
+    pub async fn refresh(&self) -> Result<SystemInfo, String> {
+        self.refresh_internal().await.map_err(|e| e.to_string())
+    }
+
+    /// 带智能重试的刷新系统信息
+    pub async fn refresh_with_retry(&self) -> Result<SystemInfo, String> {
+        const MAX_RETRIES: u32 = 2;
+        const RETRY_DELAY_MS: u64 = 300;
+
+        let mut attempt = 0;
+        loop {
+            match self.refresh_internal().await {
+                Ok(info) => return Ok(info),
+                Err(error) => {
+                    attempt += 1;
+                    if attempt > MAX_RETRIES || !error.is_retryable() {
+                        log::error!(
+                            "系统信息刷新失败 (尝试 {}/{}): {}",
+                            attempt,
+                            MAX_RETRIES + 1,
+                            error
+                        );
+                        return Err(error.to_string());
+                    }
+
+                    log::warn!(
+                        "系统信息刷新遇到临时错误，准备重试 ({}): {}",
+                        attempt,
+                        error
+                    );
+                    sleep(Duration::from_millis(RETRY_DELAY_MS * attempt as u64)).await;
+                }
+            }
+        }
+    }
+
+    /// 内部刷新实现
+    async fn refresh_internal(&self) -> Result<SystemInfo, MonitorError> {
+        // 1. 采集器会读取 procfs/sysfs 并调用 NVML，放到阻塞线程中执行，不占用异步工作线程
+        let sampling = self.sampling.clone();
+        let config = self.config.clone();
+        let sample =
+            tokio::task::spawn_blocking(move || take_sample(&mut lock(&sampling), &config))
+                .await
+                .map_err(|e| MonitorError::GenericError(format!("采样任务失败: {}", e)))?;
+
+        // 2. 组装数据（被禁用的采集器保持默认值）
+        let mut info = SystemInfo::default();
+        for set in sample.sets {
+            set.apply_to(&mut info);
+        }
+        // 系统详情只采集一次，平均频率随 CPU 采集器的核心频率更新
+        if !info.cpus.is_empty() {
+            let total: u64 = info.cpus.iter().map(|core| core.frequency).sum();
+            info.system.cpu_frequency = Some(total / info.cpus.len() as u64);
+        }
+
+        // 3. 发布查询命令读取的快照
+        *write(&self.latest_gpus) = info.gpus.clone();
+        *write(&self.collector_statuses) = sample.statuses;
+        if let Some(processes) = sample.processes {
+            *write(&self.processes) = Arc::new(processes);
+        }
+
+        Ok(info)
+    }
+
+    /// 智能刷新系统信息（包含自适应频率管理）
+    ///
+    /// # Returns
+    /// * `Result<Option<SystemInfo>, String>` - 刷新后的系统信息；系统状态稳定、
+    ///   自适应策略决定跳过本次刷新时返回 `None`
+    pub async fn smart_refresh(&self) -> Result<Option<SystemInfo>, String> {
+        if lock(&self.adaptive_refresh).should_skip_refresh() {
+            return Ok(None);
+        }
+
+        // 执行刷新
+        let system_info = self.refresh_internal().await.map_err(|e| e.to_string())?;
+
+        // 计算下次刷新间隔
+        lock(&self.adaptive_refresh).calculate_next_interval(&system_info);
+
+        Ok(Some(system_info))
+    }
+
+    /// 获取建议的刷新间隔
+    pub fn suggested_refresh_interval(&self) -> std::time::Duration {
+        lock(&self.adaptive_refresh).current_interval()
+    }
+
+    /// 获取刷新统计信息
+    pub fn get_refresh_statistics(&self) -> RefreshStatistics {
+        lock(&self.adaptive_refresh).get_statistics()
+    }
+
+    /// 重置刷新统计信息和自适应刷新的历史数据
+    pub fn reset_refresh_statistics(&self) {
+        lock(&self.adaptive_refresh).reset_history();
+    }
+
+    /// 获取当前监控配置
+    pub fn config(&self) -> &MonitorConfig {
+        &self.config
+    }
+
+    /// 更新配置（包括刷新策略）
+    pub fn update_config(&mut self, config: MonitorConfig) {
+        *write(&self.collector_statuses) = lock(&self.sampling).collectors.statuses(&config);
+        self.config = config.clone();
+        self.frame_monitor = FrameMonitor::with_config(&config.frame_capture);
+        // 更新自适应刷新策略
+        self.adaptive_refresh
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .update_strategy(config.refresh_strategy.into());
+    }
+
+    /// 获取所有被监控GPU的信息（最近一次刷新时GPU采集器的结果）
+    pub fn get_gpu_info(&self) -> Vec<GpuInfo> {
+        read(&self.latest_gpus).clone()
+    }
+
+    /// 注册自定义采集器，从下一次刷新开始生效
+    ///
+    /// # Arguments
+    /// * `collector` - 采集器
+    ///
+    /// # Returns
+    /// * `MonitorResult<()>` - 名称已被占用时返回错误
+    pub fn register_collector(&self, collector: Box<dyn Collector>) -> MonitorResult<()> {
+        let mut sampling = lock(&self.sampling);
+        sampling.collectors.register(collector)?;
+        *write(&self.collector_statuses) = sampling.collectors.statuses(&self.config);
+        Ok(())
+    }
+
+    /// 移除采集器（包括内置采集器）
+    ///
+    /// # Arguments
+    /// * `name` - 采集器名称
+    ///
+    /// # Returns
+    /// * `bool` - 是否存在并已移除
+    pub fn unregister_collector(&self, name: &str) -> bool {
+        let mut sampling = lock(&self.sampling);
+        let removed = sampling.collectors.unregister(name);
+        *write(&self.collector_statuses) = sampling.collectors.statuses(&self.config);
+        removed
+    }
+
+    /// 获取所有采集器的运行状态（最近一次采样后的快照）
+    pub fn get_collector_statuses(&self) -> Vec<CollectorStatus> {
+        read(&self.collector_statuses).clone()
+    }
+
+    /// 获取进程列表
+    ///
+    /// 使用最近一次刷新得到的进程快照，CPU 使用率为两次刷新之间的平均值
+    pub async fn get_processes(&self, query: &ProcessQuery) -> Vec<ProcessInfo> {
+        if !self.config.enable_processes {
+            return Vec::new();
+        }
+
+        let processes = read(&self.processes).clone();
+        ProcessCollector::query(&processes, query)
+    }
+
+    /// 获取进程树（按父进程嵌套，并汇总子树资源占用）
+    pub async fn get_process_tree(&self) -> Vec<ProcessTreeNode> {
+        if !self.config.enable_processes {
+            return Vec::new();
+        }
+
+        let processes = read(&self.processes).clone();
+        ProcessCollector::build_tree(&processes)
+    }
+
+    /// 获取帧率信息
+    pub async fn capture_frame_stats(
+        &self,
+        duration: Duration,
+    ) -> Result<FrameStats, MonitorError> {
+        self.frame_monitor.capture_frame_stats(duration).await
+    }
+
+    /// 获取GPU监控器状态信息
+    pub fn get_gpu_monitor_status(&self) -> GpuMonitorStatus {
+        self.gpu_monitor.status()
+    }
+
+    /// 到达探测时间时重新探测GPU，设备变化从下一次刷新开始生效
+    ///
+    /// # Returns
+    /// * `Option<GpuStatusChange>` - GPU可用性或设备的变化
+    pub async fn poll_gpu_devices(&self) -> Option<GpuStatusChange> {
+        // 探测会初始化驱动库，放到阻塞线程中执行
+        let gpu_monitor = self.gpu_monitor.clone();
+        tokio::task::spawn_blocking(move || gpu_monitor.poll_devices())
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("GPU重新探测任务失败: {}", e);
+                None
+            })
+    }
+
+    /// 获取所有GPU名称
+    pub fn get_gpu_names(&self) -> Vec<String> {
+        self.gpu_monitor.get_all_gpu_names()
+    }
+
+    /// 获取详细GPU信息
+    pub fn get_detailed_gpu_info(&self, device_index: u32) -> Result<GpuDetails, String> {
+        self.gpu_monitor
+            .get_detailed_gpu_info(device_index)
+            .map_err(|e| e.to_string())
+    }
+
+    /// 获取使用指定GPU的进程
+    ///
+    /// 后端读不到进程名时使用系统进程表中的名称补全
+    ///
+    /// # Arguments
+    /// * `device_index` - GPU设备索引
+    pub async fn get_gpu_processes(
+        &self,
+        device_index: u32,
+    ) -> Result<Vec<GpuProcessInfo>, String> {
+        let mut processes = self
+            .gpu_monitor
+            .get_gpu_processes(device_index)
+            .map_err(|e| e.to_string())?;
+
+        let snapshot = read(&self.processes).clone();
+        for process in processes.iter_mut().filter(|p| p.name.is_none()) {
+            process.name = snapshot
+                .iter()
+                .find(|info| info.pid == process.pid)
+                .map(|info| info.name.clone());
+        }
+
+        Ok(processes)
+    }
+
+    /// 获取当前配置
+    pub fn get_config(&self) -> &MonitorConfig {
+        &self.config
+    }
+}
+
+/// 在阻塞线程中刷新 sysinfo 数据并运行所有到期的采集器
+///
+/// CPU 使用率由 sysinfo 根据与上一次刷新之间的差值计算，核心频率同时刷新。距离上次刷新不足
+/// `MINIMUM_CPU_UPDATE_INTERVAL` 时跳过本次 CPU 刷新，沿用上一次的结果，从而无需在刷新中等待。
+/// 进程列表开销较大，按单独的间隔刷新。
+fn take_sample(sampling: &mut SamplingState, config: &MonitorConfig) -> Sample {
+    let now = Instant::now();
+
+    let cpu_due = sampling
+        .last_cpu_refresh
+        .is_none_or(|last| now.duration_since(last) >= sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    if cpu_due {
+        sampling
+            .system
+            .refresh_cpu_specifics(CpuRefreshKind::nothing().with_cpu_usage().with_frequency());
+        sampling.last_cpu_refresh = Some(now);
+    }
+    sampling.system.refresh_memory();
+
+    let process_interval = Duration::from_millis(config.intervals.processes_ms);
+    let processes_due = sampling
+        .last_process_refresh
+        .is_none_or(|last| now.duration_since(last) >= process_interval);
+    let processes = if config.enable_processes && processes_due {
+        sampling.last_process_refresh = Some(now);
+        Some(sampling.process_collector.refresh())
+    } else {
+        None
+    };
+
+    // 单个采集器失败时沿用其上一次的结果
+    let SamplingState {
+        system, collectors, ..
+    } = sampling;
+    let sets = collectors.collect(&CollectContext { system, config });
+
+    Sample {
+        sets,
+        statuses: collectors.statuses(config),
+        processes,
+    }
+}
+
+/// 获取互斥锁，锁被毒化时继续使用其中的数据（采集器的 panic 已在注册表中捕获）
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,295 @@
+//! 进程控制模块
+//!
+//! 负责向进程发送信号、调整优先级和 CPU 亲和性。所有操作都需要两步完成：
+//! 先申请确认令牌，再凭令牌执行，避免界面误操作直接结束进程。
+
+use crate::errors::{MonitorError, MonitorResult};
+use crate::models::{ProcessAction, ProcessActionRequest};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// 确认令牌有效期
+const CONFIRMATION_TTL: Duration = Duration::from_secs(30);
+
+/// 等待确认的进程操作
+struct PendingAction {
+    pid: u32,
+    action: ProcessAction,
+    /// 申请时进程的启动时间，用于识别 PID 被复用的情况
+    start_time: u64,
+    expires_at: Instant,
+}
+
+/// 查询进程名称和启动时间的函数
+pub type ProcessLookup = Box<dyn Fn(u32) -> MonitorResult<(String, u64)> + Send + Sync>;
+
+/// 进程操作确认管理器
+pub struct ProcessActionConfirmations {
+    pending: Mutex<HashMap<String, PendingAction>>,
+    /// 确认令牌有效期
+    ttl: Duration,
+    /// 申请和确认时查询目标进程
+    lookup: ProcessLookup,
+}
+
+impl Default for ProcessActionConfirmations {
+    fn default() -> Self {
+        Self::with_lookup(CONFIRMATION_TTL, lookup_process)
+    }
+}
+
+impl ProcessActionConfirmations {
+    /// 使用指定的令牌有效期和进程查询函数创建确认管理器
+    ///
+    /// # Arguments
+    /// * `ttl` - 确认令牌有效期
+    /// * `lookup` - 进程查询函数，如 [`lookup_process`]
+    pub fn with_lookup(
+        ttl: Duration,
+        lookup: impl Fn(u32) -> MonitorResult<(String, u64)> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            ttl,
+            lookup: Box::new(lookup),
+        }
+    }
+
+    /// 申请执行进程操作，返回确认令牌
+    ///
+    /// # Arguments
+    /// * `pid` - 目标进程ID
+    /// * `action` - 要执行的操作
+    ///
+    /// # Returns
+    /// * `MonitorResult<ProcessActionRequest>` - 确认令牌及目标进程信息
+    pub fn request(&self, pid: u32, action: ProcessAction) -> MonitorResult<ProcessActionRequest> {
+        // PID 0 及超出 i32 范围的值在 kill(2) 中代表进程组，必须拒绝
+        if pid == 0 || pid > i32::MAX as u32 {
+            return Err(MonitorError::ProcessNotFound(pid));
+        }
+
+        let (process_name, start_time) = (self.lookup)(pid)?;
+        let token = uuid::Uuid::new_v4().to_string();
+
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| MonitorError::mutex_error(e.to_string()))?;
+
+        // 顺便清理已过期的令牌
+        let now = Instant::now();
+        pending.retain(|_, action| action.expires_at > now);
+
+        pending.insert(
+            token.clone(),
+            PendingAction {
+                pid,
+                action: action.clone(),
+                start_time,
+                expires_at: now + self.ttl,
+            },
+        );
+
+        Ok(ProcessActionRequest {
+            token,
+            pid,
+            process_name,
+            action,
+            expires_in_ms: self.ttl.as_millis() as u64,
+        })
+    }
+
+    /// 凭确认令牌执行进程操作（令牌只能使用一次）
+    ///
+    /// # Arguments
+    /// * `token` - `request` 返回的确认令牌
+    ///
+    /// # Returns
+    /// * `MonitorResult<()>` - 执行成功或错误信息
+    pub fn confirm(&self, token: &str) -> MonitorResult<()> {
+        let pending_action = self
+            .pending
+            .lock()
+            .map_err(|e| MonitorError::mutex_error(e.to_string()))?
+            .remove(token)
+            .ok_or_else(|| MonitorError::InvalidConfirmation("令牌不存在或已使用".to_string()))?;
+
+        if pending_action.expires_at <= Instant::now() {
+            return Err(MonitorError::InvalidConfirmation("令牌已过期".to_string()));
+        }
+
+        // 确认前后的进程必须是同一个，防止 PID 被新进程复用
+        let (_, start_time) = (self.lookup)(pending_action.pid)?;
+        if start_time != pending_action.start_time {
+            return Err(MonitorError::ProcessNotFound(pending_action.pid));
+        }
+
+        execute(pending_action.pid, &pending_action.action)
+    }
+}
+
+/// 查询进程名称和启动时间
+///
+/// # Arguments
+/// * `pid` - 进程ID
+///
+/// # Returns
+/// * `MonitorResult<(String, u64)>` - 进程名称和启动时间，进程不存在时返回错误
+pub fn lookup_process(pid: u32) -> MonitorResult<(String, u64)> {
+    let sys_pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sys_pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+
+    system
+        .process(sys_pid)
+        .map(|process| {
+            (
+                process.name().to_string_lossy().to_string(),
+                process.start_time(),
+            )
+        })
+        .ok_or(MonitorError::ProcessNotFound(pid))
+}
+
+/// 执行进程操作
+fn execute(pid: u32, action: &ProcessAction) -> MonitorResult<()> {
+    #[cfg(unix)]
+    {
+        match action {
+            ProcessAction::Terminate => send_signal(pid, libc::SIGTERM),
+            ProcessAction::Kill => send_signal(pid, libc::SIGKILL),
+            ProcessAction::Suspend => send_signal(pid, libc::SIGSTOP),
+            ProcessAction::Resume => send_signal(pid, libc::SIGCONT),
+            ProcessAction::Renice { nice } => renice(pid, *nice),
+            ProcessAction::SetAffinity { cpus } => set_affinity(pid, cpus),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        match action {
+            ProcessAction::Terminate | ProcessAction::Kill => kill_with_sysinfo(pid),
+            _ => Err(MonitorError::UnsupportedOperation(format!("{:?}", action))),
+        }
+    }
+}
+
+/// 发送信号
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: libc::c_int) -> MonitorResult<()> {
+    // SAFETY: kill 只读取参数，PID 已在 request 中校验为正数
+    let result = unsafe { libc::kill(pid as libc::pid_t, signal) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(map_os_error(pid, std::io::Error::last_os_error()))
+    }
+}
+
+/// 调整进程优先级（nice 值，范围 -20 ~ 19）
+#[cfg(unix)]
+fn renice(pid: u32, nice: i32) -> MonitorResult<()> {
+    if !(-20..=19).contains(&nice) {
+        return Err(MonitorError::ConfigError(format!(
+            "nice 值超出范围 (-20 ~ 19): {}",
+            nice
+        )));
+    }
+
+    // SAFETY: setpriority 只读取参数
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(map_os_error(pid, std::io::Error::last_os_error()))
+    }
+}
+
+/// 设置 CPU 亲和性
+#[cfg(target_os = "linux")]
+fn set_affinity(pid: u32, cpus: &[usize]) -> MonitorResult<()> {
+    if cpus.is_empty() {
+        return Err(MonitorError::ConfigError("CPU 列表不能为空".to_string()));
+    }
+
+    // SAFETY: cpu_set_t 是纯数据结构，全零即为空集合
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(MonitorError::ConfigError(format!(
+                "无效的 CPU 编号: {}",
+                cpu
+            )));
+        }
+        // SAFETY: cpu 已校验小于 CPU_SETSIZE
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+
+    // SAFETY: set 在调用期间有效，大小与传入的长度一致
+    let result = unsafe {
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(map_os_error(pid, std::io::Error::last_os_error()))
+    }
+}
+
+/// 设置 CPU 亲和性（非 Linux 平台不支持）
+#[cfg(all(unix, not(target_os = "linux")))]
+fn set_affinity(_pid: u32, _cpus: &[usize]) -> MonitorResult<()> {
+    Err(MonitorError::UnsupportedOperation(
+        "当前平台不支持设置 CPU 亲和性".to_string(),
+    ))
+}
+
+/// 将系统错误映射为监控错误
+///
+/// # Arguments
+/// * `pid` - 操作的进程ID
+/// * `error` - 系统调用返回的错误
+#[cfg(unix)]
+pub fn map_os_error(pid: u32, error: std::io::Error) -> MonitorError {
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => MonitorError::ProcessNotFound(pid),
+        Some(libc::EPERM) | Some(libc::EACCES) => {
+            MonitorError::PermissionDenied(format!("无权操作进程 {}: {}", pid, error))
+        }
+        _ => MonitorError::IoError(format!("操作进程 {} 失败: {}", pid, error)),
+    }
+}
+
+/// 通过 sysinfo 结束进程（Windows 仅支持强制结束）
+#[cfg(not(unix))]
+fn kill_with_sysinfo(pid: u32) -> MonitorResult<()> {
+    let sys_pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sys_pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+
+    let process = system
+        .process(sys_pid)
+        .ok_or(MonitorError::ProcessNotFound(pid))?;
+    if process.kill() {
+        Ok(())
+    } else {
+        Err(MonitorError::PermissionDenied(format!(
+            "无法结束进程 {}",
+            pid
+        )))
+    }
+}
@@ -0,0 +1,246 @@
+//! 进程采集模块
+//!
+//! 基于 sysinfo 的进程数据生成进程列表和进程树，支持服务端排序、按名称/用户过滤、Top-N 截取以及子树资源汇总。
+//! 进程表在独立的 sysinfo 实例中刷新，刷新结果作为快照发布，查询只读取快照，不会等待刷新。
+
+use crate::models::{ProcessInfo, ProcessQuery, ProcessSortKey, ProcessTreeNode};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Process, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users};
+
+/// 进程采集器
+pub struct ProcessCollector {
+    /// 只用于刷新进程表的 sysinfo 实例，刷新时不影响其他指标的读取
+    system: System,
+    /// 用户列表，用于将 UID 解析为用户名
+    users: Users,
+}
+
+impl Default for ProcessCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessCollector {
+    /// 创建新的进程采集器
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            users: Users::new_with_refreshed_list(),
+        }
+    }
+
+    /// 刷新进程数据（CPU、内存、磁盘 IO，命令行与用户仅在首次出现时读取）
+    ///
+    /// 进程的 CPU 使用率为与上一次刷新之间的平均值
+    ///
+    /// # Returns
+    /// * `Vec<ProcessInfo>` - 刷新后的进程快照（不含线程）
+    pub fn refresh(&mut self) -> Vec<ProcessInfo> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .with_disk_usage()
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_user(UpdateKind::OnlyIfNotSet),
+        );
+        // 用户可能在程序运行期间新增，刷新用户列表的开销很小
+        self.users.refresh();
+
+        // 过滤掉线程（Linux 下 sysinfo 会把线程也列为进程），避免重复统计
+        self.system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| self.to_process_info(process))
+            .collect()
+    }
+
+    /// 按查询条件生成进程列表
+    ///
+    /// # Arguments
+    /// * `processes` - 进程快照
+    /// * `query` - 排序、过滤和数量限制条件
+    ///
+    /// # Returns
+    /// * `Vec<ProcessInfo>` - 排序并截取后的进程列表
+    pub fn query(processes: &[ProcessInfo], query: &ProcessQuery) -> Vec<ProcessInfo> {
+        let name_filter = query.name_filter.as_deref().map(str::to_lowercase);
+        let user_filter = query.user_filter.as_deref().map(str::to_lowercase);
+
+        let mut processes: Vec<ProcessInfo> = processes
+            .iter()
+            .filter(|info| {
+                let name_matches = name_filter.as_ref().is_none_or(|filter| {
+                    info.name.to_lowercase().contains(filter)
+                        || info.command.to_lowercase().contains(filter)
+                });
+                let user_matches = user_filter.as_ref().is_none_or(|filter| {
+                    info.user
+                        .as_ref()
+                        .is_some_and(|user| user.to_lowercase().contains(filter))
+                });
+                name_matches && user_matches
+            })
+            .cloned()
+            .collect();
+
+        processes.sort_by(|a, b| {
+            let ordering = Self::compare(a, b, &query.sort_by);
+            if query.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        if let Some(limit) = query.limit {
+            processes.truncate(limit);
+        }
+
+        processes
+    }
+
+    /// 生成进程树，每个节点同时包含自身与整个子树的资源占用
+    ///
+    /// # Arguments
+    /// * `processes` - 进程快照
+    ///
+    /// # Returns
+    /// * `Vec<ProcessTreeNode>` - 根节点列表，各层按子树 CPU 使用率降序排列
+    pub fn build_tree(processes: &[ProcessInfo]) -> Vec<ProcessTreeNode> {
+        let mut processes: HashMap<u32, ProcessInfo> = processes
+            .iter()
+            .map(|info| (info.pid, info.clone()))
+            .collect();
+
+        // 建立父子索引，父进程不存在（已退出或无权限读取）的进程作为根节点
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut roots = Vec::new();
+        for info in processes.values() {
+            match info.parent_pid {
+                Some(parent) if parent != info.pid && processes.contains_key(&parent) => {
+                    children.entry(parent).or_default().push(info.pid);
+                }
+                _ => roots.push(info.pid),
+            }
+        }
+
+        let mut tree: Vec<ProcessTreeNode> = roots
+            .into_iter()
+            .filter_map(|pid| Self::build_node(pid, &mut processes, &children))
+            .collect();
+
+        // PID 复用可能使父子关系成环，这些进程无法从根节点到达。
+        // 沿父进程向上找到环上的进程作为根节点，挂在环上的子进程仍归入同一棵子树
+        while let Some(&start) = processes.keys().min() {
+            let mut pid = start;
+            let mut visited = HashSet::new();
+            while visited.insert(pid) {
+                match processes[&pid].parent_pid {
+                    Some(parent) if processes.contains_key(&parent) => pid = parent,
+                    _ => break,
+                }
+            }
+            if let Some(node) = Self::build_node(pid, &mut processes, &children) {
+                tree.push(node);
+            }
+        }
+
+        Self::sort_nodes(&mut tree);
+        tree
+    }
+
+    /// 递归构建子树（已构建的进程会从 `processes` 中移除，避免重复访问）
+    fn build_node(
+        pid: u32,
+        processes: &mut HashMap<u32, ProcessInfo>,
+        children: &HashMap<u32, Vec<u32>>,
+    ) -> Option<ProcessTreeNode> {
+        let process = processes.remove(&pid)?;
+
+        let mut child_nodes: Vec<ProcessTreeNode> = children
+            .get(&pid)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| Self::build_node(*child, processes, children))
+            .collect();
+        Self::sort_nodes(&mut child_nodes);
+
+        let subtree_cpu_usage = process.cpu_usage
+            + child_nodes
+                .iter()
+                .map(|node| node.subtree_cpu_usage)
+                .sum::<f32>();
+        let subtree_memory = process.memory
+            + child_nodes
+                .iter()
+                .map(|node| node.subtree_memory)
+                .sum::<u64>();
+        let subtree_process_count = 1 + child_nodes
+            .iter()
+            .map(|node| node.subtree_process_count)
+            .sum::<usize>();
+
+        Some(ProcessTreeNode {
+            process,
+            subtree_cpu_usage,
+            subtree_memory,
+            subtree_process_count,
+            children: child_nodes,
+        })
+    }
+
+    /// 按子树 CPU 使用率降序排列
+    fn sort_nodes(nodes: &mut [ProcessTreeNode]) {
+        nodes.sort_by(|a, b| b.subtree_cpu_usage.total_cmp(&a.subtree_cpu_usage));
+    }
+
+    /// 转换单个进程
+    fn to_process_info(&self, process: &Process) -> ProcessInfo {
+        let disk_usage = process.disk_usage();
+        let command = process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        ProcessInfo {
+            pid: process.pid().as_u32(),
+            parent_pid: process.parent().map(|pid| pid.as_u32()),
+            name: process.name().to_string_lossy().to_string(),
+            command,
+            user: process
+                .user_id()
+                .and_then(|uid| self.users.get_user_by_id(uid))
+                .map(|user| user.name().to_string()),
+            state: process.status().to_string(),
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+            virtual_memory: process.virtual_memory(),
+            disk_read_bytes: disk_usage.total_read_bytes,
+            disk_written_bytes: disk_usage.total_written_bytes,
+            start_time: process.start_time(),
+        }
+    }
+
+    /// 按排序字段比较两个进程
+    fn compare(a: &ProcessInfo, b: &ProcessInfo, sort_by: &ProcessSortKey) -> Ordering {
+        match sort_by {
+            ProcessSortKey::Pid => a.pid.cmp(&b.pid),
+            ProcessSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProcessSortKey::User => a.user.cmp(&b.user),
+            ProcessSortKey::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessSortKey::Memory => a.memory.cmp(&b.memory),
+            ProcessSortKey::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            ProcessSortKey::DiskRead => a.disk_read_bytes.cmp(&b.disk_read_bytes),
+            ProcessSortKey::DiskWrite => a.disk_written_bytes.cmp(&b.disk_written_bytes),
+            ProcessSortKey::StartTime => a.start_time.cmp(&b.start_time),
+        }
+    }
+}
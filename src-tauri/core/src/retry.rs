@@ -1,8 +1,6 @@
 //! 智能重试机制模块
 //! 提供基于错误类型的自动重试功能
 
-#![allow(dead_code)]
-
 use crate::errors::MonitorError;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -114,17 +112,19 @@ pub struct RetryManager {
     config: RetryConfig,
 }
 
+impl Default for RetryManager {
+    /// 使用默认配置创建重试管理器
+    fn default() -> Self {
+        Self::new(RetryConfig::default())
+    }
+}
+
 impl RetryManager {
     /// 创建新的重试管理器
     pub fn new(config: RetryConfig) -> Self {
         Self { config }
     }
 
-    /// 使用默认配置创建重试管理器
-    pub fn default() -> Self {
-        Self::new(RetryConfig::default())
-    }
-
     /// 执行带重试的操作
     pub async fn execute_with_retry<F, T, Fut>(&self, mut operation: F) -> RetryResult<T>
     where
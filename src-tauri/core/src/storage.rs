@@ -0,0 +1,504 @@
+//! 指标持久化存储模块
+//!
+//! 将每次采样以 JSON Lines 追加写入应用数据目录下的分段文件，并在写入时滚动生成
+//! 1 分钟和 1 小时聚合数据。三个层级分别按 `StorageConfig` 中的保留时长清理过期分段，
+//! 使历史数据在应用重启后依然可查。尚未结束的聚合桶在存储释放时写入，重启后同一时间桶
+//! 可能出现多行记录，查询时按时间戳合并。
+//!
+//! 目录结构：`<app_data_dir>/metrics/{raw,1m,1h}/<分段起始秒>.jsonl`
+
+use crate::history::{downsample, extract_metrics};
+use crate::models::{HistoryBucket, StorageConfig, StorageResolution, SystemInfo};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// 写入队列长度，写入线程跟不上时丢弃新样本而不是阻塞采样
+const QUEUE_CAPACITY: usize = 256;
+
+const MINUTE_MS: u64 = 60 * 1000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// 原始样本记录
+#[derive(Debug, Serialize, Deserialize)]
+struct RawRecord {
+    /// 时间戳（毫秒）
+    t: u64,
+    /// 指标值
+    v: HashMap<String, f64>,
+}
+
+/// 聚合记录
+#[derive(Debug, Serialize, Deserialize)]
+struct RollupRecord {
+    /// 桶起始时间戳（毫秒）
+    t: u64,
+    /// 各指标的聚合值
+    v: HashMap<String, RollupValue>,
+}
+
+/// 单个指标的聚合值
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct RollupValue {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u32,
+}
+
+impl RollupValue {
+    fn from_value(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn merge(&mut self, other: &RollupValue) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+    }
+
+    fn to_bucket(self, timestamp: u64) -> HistoryBucket {
+        HistoryBucket {
+            timestamp,
+            min: self.min,
+            max: self.max,
+            avg: self.sum / self.count.max(1) as f64,
+            count: self.count,
+        }
+    }
+}
+
+/// 正在累积的聚合桶
+#[derive(Default)]
+struct RollupAccumulator {
+    start: Option<u64>,
+    values: HashMap<String, RollupValue>,
+}
+
+impl RollupAccumulator {
+    /// 合并一批指标，若进入新的时间桶则返回上一个已完成的桶
+    fn add(
+        &mut self,
+        bucket_start: u64,
+        values: impl IntoIterator<Item = (String, RollupValue)>,
+    ) -> Option<RollupRecord> {
+        let finished = match self.start {
+            Some(start) if start != bucket_start => Some(RollupRecord {
+                t: start,
+                v: std::mem::take(&mut self.values),
+            }),
+            _ => None,
+        };
+        self.start = Some(bucket_start);
+
+        for (metric, value) in values {
+            self.values
+                .entry(metric)
+                .and_modify(|current| current.merge(&value))
+                .or_insert(value);
+        }
+
+        finished.filter(|record| !record.v.is_empty())
+    }
+
+    /// 取出尚未结束的桶
+    fn take(&mut self) -> Option<RollupRecord> {
+        let start = self.start.take()?;
+        Some(RollupRecord {
+            t: start,
+            v: std::mem::take(&mut self.values),
+        })
+        .filter(|record| !record.v.is_empty())
+    }
+}
+
+impl StorageResolution {
+    /// 分段文件目录名
+    fn dir_name(&self) -> &'static str {
+        match self {
+            StorageResolution::Raw => "raw",
+            StorageResolution::Minute => "1m",
+            StorageResolution::Hour => "1h",
+        }
+    }
+
+    /// 每个分段文件覆盖的时长（毫秒）
+    fn segment_ms(&self) -> u64 {
+        match self {
+            StorageResolution::Raw => HOUR_MS,
+            StorageResolution::Minute => DAY_MS,
+            StorageResolution::Hour => 30 * DAY_MS,
+        }
+    }
+
+    /// 该层级的保留时长（毫秒）
+    fn retention_ms(&self, config: &StorageConfig) -> u64 {
+        match self {
+            StorageResolution::Raw => config.raw_retention_hours * HOUR_MS,
+            StorageResolution::Minute => config.minute_retention_days * DAY_MS,
+            StorageResolution::Hour => config.hour_retention_days * DAY_MS,
+        }
+    }
+
+    const ALL: [StorageResolution; 3] = [
+        StorageResolution::Raw,
+        StorageResolution::Minute,
+        StorageResolution::Hour,
+    ];
+}
+
+/// 写入线程接收的消息
+enum StorageMessage {
+    /// 新的采样
+    Sample(u64, Arc<SystemInfo>),
+    /// 更新配置
+    Configure(StorageConfig),
+    /// 写入尚未结束的聚合桶后退出
+    Shutdown,
+}
+
+/// 指标持久化存储
+pub struct MetricsStorage {
+    root: PathBuf,
+    config: Mutex<StorageConfig>,
+    sender: SyncSender<StorageMessage>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl MetricsStorage {
+    /// 创建存储并启动后台写入线程
+    ///
+    /// # Arguments
+    /// * `root` - 存储根目录
+    /// * `config` - 存储配置
+    pub fn new(root: PathBuf, config: StorageConfig) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+
+        let writer = StorageWriter::new(root.clone(), config.clone());
+        let writer = std::thread::Builder::new()
+            .name("metrics-storage".to_string())
+            .spawn(move || writer.run(receiver))
+            .map_err(|e| error!("启动指标存储线程失败: {}", e))
+            .ok();
+
+        info!("指标持久化存储目录: {}", root.display());
+        Self {
+            root,
+            config: Mutex::new(config),
+            sender,
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// 提交一次采样（非阻塞）
+    pub fn record(&self, timestamp: u64, system_info: Arc<SystemInfo>) {
+        match self
+            .sender
+            .try_send(StorageMessage::Sample(timestamp, system_info))
+        {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => warn!("指标存储写入队列已满，丢弃本次样本"),
+            Err(TrySendError::Disconnected(_)) => debug!("指标存储线程已退出"),
+        }
+    }
+
+    /// 更新存储配置
+    pub fn configure(&self, config: StorageConfig) {
+        if let Ok(mut current) = self.config.lock() {
+            *current = config.clone();
+        }
+        if self.sender.send(StorageMessage::Configure(config)).is_err() {
+            debug!("指标存储线程已退出");
+        }
+    }
+
+    /// 写入尚未结束的聚合桶并停止写入线程，之后提交的采样会被忽略
+    ///
+    /// 释放存储时会自动调用；应用退出时不会释放共享状态，需要显式调用
+    pub fn shutdown(&self) {
+        let Some(writer) = self.writer.lock().ok().and_then(|mut writer| writer.take()) else {
+            return;
+        };
+        if self.sender.send(StorageMessage::Shutdown).is_err() {
+            debug!("指标存储线程已退出");
+        }
+        if writer.join().is_err() {
+            error!("指标存储线程异常退出");
+        }
+    }
+
+    /// 查询持久化的指标历史
+    ///
+    /// # Arguments
+    /// * `metric` - 指标名称
+    /// * `from` - 起始时间戳（毫秒，含）
+    /// * `to` - 结束时间戳（毫秒，含）
+    /// * `resolution` - 数据层级，为空时选择保留范围能覆盖 `from` 的最细层级
+    /// * `max_points` - 返回的最大数据点数
+    ///
+    /// # Returns
+    /// * `Vec<HistoryBucket>` - 按时间升序排列的数据
+    pub fn query(
+        &self,
+        metric: &str,
+        from: u64,
+        to: u64,
+        resolution: Option<StorageResolution>,
+        max_points: Option<usize>,
+    ) -> Vec<HistoryBucket> {
+        let config = self
+            .config
+            .lock()
+            .map(|config| config.clone())
+            .unwrap_or_default();
+        let resolution = resolution.unwrap_or_else(|| {
+            let now = now_ms();
+            StorageResolution::ALL
+                .into_iter()
+                .find(|tier| from >= now.saturating_sub(tier.retention_ms(&config)))
+                .unwrap_or(StorageResolution::Hour)
+        });
+
+        // 重启前后同一时间桶可能各有一行记录，按时间戳合并
+        let mut merged: BTreeMap<u64, RollupValue> = BTreeMap::new();
+        for path in segment_files(&self.root, &resolution) {
+            let Some(segment_start) = segment_start(&path) else {
+                continue;
+            };
+            if segment_start > to || segment_start + resolution.segment_ms() <= from {
+                continue;
+            }
+
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            // 最后一行可能正在写入，解析失败的行直接跳过
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                let value = match resolution {
+                    StorageResolution::Raw => serde_json::from_str::<RawRecord>(&line)
+                        .ok()
+                        .and_then(|record| {
+                            Some((record.t, RollupValue::from_value(*record.v.get(metric)?)))
+                        }),
+                    _ => serde_json::from_str::<RollupRecord>(&line)
+                        .ok()
+                        .and_then(|record| Some((record.t, *record.v.get(metric)?))),
+                };
+                if let Some((timestamp, value)) =
+                    value.filter(|(timestamp, _)| *timestamp >= from && *timestamp <= to)
+                {
+                    merged
+                        .entry(timestamp)
+                        .and_modify(|current| current.merge(&value))
+                        .or_insert(value);
+                }
+            }
+        }
+
+        let buckets = merged
+            .into_iter()
+            .map(|(timestamp, value)| value.to_bucket(timestamp))
+            .collect();
+        downsample(buckets, max_points.unwrap_or(usize::MAX))
+    }
+}
+
+impl Drop for MetricsStorage {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// 后台写入线程状态
+struct StorageWriter {
+    root: PathBuf,
+    config: StorageConfig,
+    /// 各层级当前打开的分段文件（分段起始时间，文件）
+    open_segments: HashMap<&'static str, (u64, File)>,
+    minute: RollupAccumulator,
+    hour: RollupAccumulator,
+}
+
+impl StorageWriter {
+    fn new(root: PathBuf, config: StorageConfig) -> Self {
+        Self {
+            root,
+            config,
+            open_segments: HashMap::new(),
+            minute: RollupAccumulator::default(),
+            hour: RollupAccumulator::default(),
+        }
+    }
+
+    /// 写入线程主循环，收到退出消息或发送端全部释放后写入未结束的聚合桶并退出
+    fn run(mut self, receiver: Receiver<StorageMessage>) {
+        self.enforce_retention();
+
+        for message in receiver {
+            match message {
+                StorageMessage::Sample(timestamp, system_info) => {
+                    if self.config.enabled {
+                        self.write_sample(timestamp, &system_info);
+                    }
+                }
+                StorageMessage::Configure(config) => {
+                    self.config = config;
+                    self.enforce_retention();
+                }
+                StorageMessage::Shutdown => break,
+            }
+        }
+
+        self.flush();
+    }
+
+    /// 写入原始样本并滚动聚合
+    fn write_sample(&mut self, timestamp: u64, system_info: &SystemInfo) {
+        let values: HashMap<String, f64> = extract_metrics(system_info).into_iter().collect();
+
+        let minute_values: Vec<(String, RollupValue)> = values
+            .iter()
+            .map(|(metric, value)| (metric.clone(), RollupValue::from_value(*value)))
+            .collect();
+
+        self.append(
+            StorageResolution::Raw,
+            timestamp,
+            &RawRecord {
+                t: timestamp,
+                v: values,
+            },
+        );
+
+        let minute_start = timestamp - timestamp % MINUTE_MS;
+        if let Some(finished) = self.minute.add(minute_start, minute_values) {
+            self.append(StorageResolution::Minute, finished.t, &finished);
+
+            let hour_start = finished.t - finished.t % HOUR_MS;
+            if let Some(finished_hour) = self.hour.add(hour_start, finished.v) {
+                self.append(StorageResolution::Hour, finished_hour.t, &finished_hour);
+            }
+        }
+    }
+
+    /// 写入尚未结束的 1 分钟和 1 小时聚合桶
+    fn flush(&mut self) {
+        if let Some(minute) = self.minute.take() {
+            self.append(StorageResolution::Minute, minute.t, &minute);
+
+            let hour_start = minute.t - minute.t % HOUR_MS;
+            if let Some(finished_hour) = self.hour.add(hour_start, minute.v) {
+                self.append(StorageResolution::Hour, finished_hour.t, &finished_hour);
+            }
+        }
+        if let Some(hour) = self.hour.take() {
+            self.append(StorageResolution::Hour, hour.t, &hour);
+        }
+    }
+
+    /// 追加一行记录到对应层级的分段文件
+    fn append<T: Serialize>(&mut self, resolution: StorageResolution, timestamp: u64, record: &T) {
+        let segment_ms = resolution.segment_ms();
+        let segment = timestamp - timestamp % segment_ms;
+        let key = resolution.dir_name();
+
+        let needs_new_segment = self
+            .open_segments
+            .get(key)
+            .is_none_or(|(start, _)| *start != segment);
+        if needs_new_segment {
+            let dir = self.root.join(key);
+            let opened = fs::create_dir_all(&dir).and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(dir.join(format!("{}.jsonl", segment / 1000)))
+            });
+            match opened {
+                Ok(file) => {
+                    self.open_segments.insert(key, (segment, file));
+                }
+                Err(e) => {
+                    error!("打开指标分段文件失败: {}", e);
+                    return;
+                }
+            }
+            // 每开始一个新分段顺便清理过期数据
+            self.enforce_retention();
+        }
+
+        let Some((_, file)) = self.open_segments.get_mut(key) else {
+            return;
+        };
+        match serde_json::to_string(record) {
+            Ok(mut line) => {
+                line.push('\n');
+                if let Err(e) = file.write_all(line.as_bytes()) {
+                    error!("写入指标记录失败: {}", e);
+                }
+            }
+            Err(e) => error!("序列化指标记录失败: {}", e),
+        }
+    }
+
+    /// 删除超出保留时长的分段文件
+    fn enforce_retention(&mut self) {
+        let now = now_ms();
+        for resolution in StorageResolution::ALL {
+            let cutoff = now.saturating_sub(resolution.retention_ms(&self.config));
+            for path in segment_files(&self.root, &resolution) {
+                let Some(start) = segment_start(&path) else {
+                    continue;
+                };
+                if start + resolution.segment_ms() <= cutoff {
+                    match fs::remove_file(&path) {
+                        Ok(()) => debug!("已删除过期指标分段: {}", path.display()),
+                        Err(e) => warn!("删除过期指标分段失败: {}", e),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 列出某层级的全部分段文件
+fn segment_files(root: &Path, resolution: &StorageResolution) -> Vec<PathBuf> {
+    fs::read_dir(root.join(resolution.dir_name()))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 从文件名解析分段起始时间（毫秒）
+fn segment_start(path: &Path) -> Option<u64> {
+    path.file_stem()?
+        .to_str()?
+        .parse::<u64>()
+        .ok()
+        .map(|secs| secs * 1000)
+}
+
+/// 当前时间戳（毫秒）
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
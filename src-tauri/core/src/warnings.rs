@@ -0,0 +1,253 @@
+//! 系统警告模块
+//!
+//! 检测持续高负载、传感器接近临界温度（或超过配置的温度阈值）以及磁盘使用率过高等情况，
+//! 并维护最近的警告列表；通知的发送方式由调用方决定。
+
+use crate::models::{NotificationConfig, SystemInfo, WarningEntry, WarningKind};
+use std::collections::{HashSet, VecDeque};
+
+/// 保留的最近警告数量
+const MAX_RECENT_WARNINGS: usize = 10;
+
+/// 警告解除需要回落的幅度（百分点或摄氏度），避免在阈值附近反复通知
+const CLEAR_MARGIN: f32 = 3.0;
+
+/// 高负载警告的条件键
+const HIGH_LOAD_KEY: &str = "high_load";
+
+/// 单次评估的结果
+#[derive(Debug, Default)]
+pub struct WarningUpdate {
+    /// 本次新产生的警告
+    pub new_warnings: Vec<WarningEntry>,
+    /// 仍处于警告状态的条件数量是否发生变化
+    pub active_changed: bool,
+}
+
+/// 系统警告检测器
+pub struct WarningMonitor {
+    config: NotificationConfig,
+    /// 高负载开始的时间戳（毫秒）
+    high_load_since: Option<u64>,
+    /// 当前处于警告状态的条件
+    active: HashSet<String>,
+    /// 最近的警告（最新的在前）
+    recent: VecDeque<WarningEntry>,
+    next_id: u64,
+}
+
+impl WarningMonitor {
+    /// 创建新的警告检测器
+    ///
+    /// # Arguments
+    /// * `config` - 通知配置
+    pub fn new(config: NotificationConfig) -> Self {
+        Self {
+            config,
+            high_load_since: None,
+            active: HashSet::new(),
+            recent: VecDeque::with_capacity(MAX_RECENT_WARNINGS),
+            next_id: 1,
+        }
+    }
+
+    /// 更新通知配置（禁用时清除当前的警告状态）
+    pub fn configure(&mut self, config: NotificationConfig) {
+        if !config.enabled {
+            self.active.clear();
+            self.high_load_since = None;
+        }
+        self.config = config;
+    }
+
+    /// 最近的警告（最新的在前）
+    pub fn recent(&self) -> Vec<WarningEntry> {
+        self.recent.iter().cloned().collect()
+    }
+
+    /// 按ID查找最近的警告
+    pub fn find(&self, id: u64) -> Option<WarningEntry> {
+        self.recent.iter().find(|entry| entry.id == id).cloned()
+    }
+
+    /// 是否有仍处于警告状态的条件
+    pub fn has_active(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// 按最新采样检测警告条件
+    ///
+    /// # Arguments
+    /// * `timestamp` - 采样时间戳（毫秒）
+    /// * `info` - 采样数据
+    ///
+    /// # Returns
+    /// * `WarningUpdate` - 新产生的警告及警告状态是否变化
+    pub fn evaluate(&mut self, timestamp: u64, info: &SystemInfo) -> WarningUpdate {
+        let mut update = WarningUpdate::default();
+        if !self.config.enabled {
+            return update;
+        }
+        let active_before = self.active.len();
+
+        self.check_high_load(timestamp, info, &mut update);
+
+        for sensor in &info.temperatures {
+            // `max` 是传感器的历史最高读数而非上限，只有临界值可用作参考
+            let critical = sensor.critical.filter(|critical| *critical > 0.0);
+            let trigger = match critical {
+                Some(critical) => critical - self.config.temperature_margin,
+                None => self.config.temperature_threshold,
+            };
+            let metric = format!("temperature.{}", sensor.label);
+            self.check_threshold(
+                timestamp,
+                metric.clone(),
+                sensor.temperature,
+                trigger,
+                || {
+                    let message = match critical {
+                        Some(critical) => format!(
+                            "{} 温度 {:.1}°C，接近临界值 {:.1}°C",
+                            sensor.label, sensor.temperature, critical
+                        ),
+                        None => format!(
+                            "{} 温度 {:.1}°C，超过警告阈值 {:.0}°C",
+                            sensor.label, sensor.temperature, trigger
+                        ),
+                    };
+                    (WarningKind::Temperature, metric.clone(), message)
+                },
+                &mut update,
+            );
+        }
+
+        for disk in &info.disk.disks {
+            let metric = format!("disk.{}.usage_percent", disk.mount_point);
+            self.check_threshold(
+                timestamp,
+                metric.clone(),
+                disk.usage_percent,
+                self.config.disk_usage_threshold,
+                || {
+                    (
+                        WarningKind::DiskUsage,
+                        metric.clone(),
+                        format!(
+                            "磁盘 {} 使用率已达 {:.1}%",
+                            disk.mount_point, disk.usage_percent
+                        ),
+                    )
+                },
+                &mut update,
+            );
+        }
+
+        update.active_changed =
+            self.active.len() != active_before || !update.new_warnings.is_empty();
+        update
+    }
+
+    /// 检测持续高负载（CPU 或内存超过阈值并持续配置的时长）
+    fn check_high_load(&mut self, timestamp: u64, info: &SystemInfo, update: &mut WarningUpdate) {
+        let cpu_high = info.cpu_usage > self.config.cpu_threshold;
+        let memory_high = info.memory.usage_percent > self.config.memory_threshold;
+
+        if !cpu_high && !memory_high {
+            self.high_load_since = None;
+            let cpu_cleared = info.cpu_usage < self.config.cpu_threshold - CLEAR_MARGIN;
+            let memory_cleared =
+                info.memory.usage_percent < self.config.memory_threshold - CLEAR_MARGIN;
+            if cpu_cleared && memory_cleared {
+                self.active.remove(HIGH_LOAD_KEY);
+            }
+            return;
+        }
+
+        let since = *self.high_load_since.get_or_insert(timestamp);
+        let duration_ms = self.config.high_load_duration_secs.saturating_mul(1000);
+        if timestamp.saturating_sub(since) < duration_ms || self.active.contains(HIGH_LOAD_KEY) {
+            return;
+        }
+
+        self.active.insert(HIGH_LOAD_KEY.to_string());
+        let (metric, value, message) = if cpu_high {
+            (
+                "cpu.usage",
+                info.cpu_usage,
+                format!(
+                    "CPU 使用率持续 {} 秒高于 {:.0}%（当前 {:.1}%）",
+                    self.config.high_load_duration_secs, self.config.cpu_threshold, info.cpu_usage
+                ),
+            )
+        } else {
+            (
+                "memory.usage_percent",
+                info.memory.usage_percent,
+                format!(
+                    "内存使用率持续 {} 秒高于 {:.0}%（当前 {:.1}%）",
+                    self.config.high_load_duration_secs,
+                    self.config.memory_threshold,
+                    info.memory.usage_percent
+                ),
+            )
+        };
+        let entry = self.push_warning(
+            timestamp,
+            WarningKind::HighLoad,
+            metric.to_string(),
+            message,
+            value,
+        );
+        update.new_warnings.push(entry);
+    }
+
+    /// 检测单个阈值条件，超过阈值时产生一次警告，回落到阈值以下一定幅度后解除
+    fn check_threshold(
+        &mut self,
+        timestamp: u64,
+        key: String,
+        value: f32,
+        trigger: f32,
+        describe: impl FnOnce() -> (WarningKind, String, String),
+        update: &mut WarningUpdate,
+    ) {
+        if self.active.contains(&key) {
+            if value < trigger - CLEAR_MARGIN {
+                self.active.remove(&key);
+            }
+            return;
+        }
+
+        if value >= trigger {
+            let (kind, metric, message) = describe();
+            self.active.insert(key);
+            let entry = self.push_warning(timestamp, kind, metric, message, value);
+            update.new_warnings.push(entry);
+        }
+    }
+
+    /// 记录一条警告
+    fn push_warning(
+        &mut self,
+        timestamp: u64,
+        kind: WarningKind,
+        metric: String,
+        message: String,
+        value: f32,
+    ) -> WarningEntry {
+        let entry = WarningEntry {
+            id: self.next_id,
+            kind,
+            metric,
+            message,
+            value: value as f64,
+            timestamp,
+        };
+        self.next_id += 1;
+
+        self.recent.push_front(entry.clone());
+        self.recent.truncate(MAX_RECENT_WARNINGS);
+        entry
+    }
+}
@@ -0,0 +1,231 @@
+//! 告警引擎的状态流转、回差和指标路径解析
+
+use system_monitor_core::alerts::{validate_rule, AlertEngine};
+use system_monitor_core::models::{
+    AlertComparison, AlertRule, AlertState, CpuCoreInfo, SystemInfo, TemperatureInfo,
+};
+
+fn rule(id: &str, metric: &str, trigger: f64, clear: f64, for_ms: u64) -> AlertRule {
+    AlertRule {
+        id: id.to_string(),
+        name: id.to_string(),
+        enabled: true,
+        metric: metric.to_string(),
+        comparison: AlertComparison::Above,
+        trigger_threshold: trigger,
+        clear_threshold: clear,
+        for_ms,
+    }
+}
+
+fn with_cpu(cpu_usage: f32) -> SystemInfo {
+    SystemInfo {
+        cpu_usage,
+        ..Default::default()
+    }
+}
+
+fn temperature(label: &str, category: &str, temperature: f32) -> TemperatureInfo {
+    TemperatureInfo {
+        label: label.to_string(),
+        temperature,
+        max: None,
+        critical: None,
+        category: Some(category.to_string()),
+    }
+}
+
+fn core(name: &str, usage_percent: f32, frequency: u64) -> CpuCoreInfo {
+    CpuCoreInfo {
+        name: name.to_string(),
+        usage_percent,
+        frequency,
+        times: None,
+    }
+}
+
+fn sample() -> SystemInfo {
+    SystemInfo {
+        cpus: vec![core("cpu0", 10.0, 3600), core("cpu1", 55.0, 4200)],
+        temperatures: vec![
+            temperature("Package id 0", "cpu-package", 70.0),
+            temperature("Core 0", "cpu-core", 75.0),
+            temperature("edge", "gpu", 60.0),
+        ],
+        ..Default::default()
+    }
+}
+
+/// 评估单条规则，返回状态和指标值
+fn evaluate_once(metric: &str, threshold: f64) -> (AlertState, Option<f64>) {
+    let mut engine = AlertEngine::new(vec![rule("r", metric, threshold, threshold, 0)]);
+    engine.evaluate(1, &sample());
+    let status = engine.statuses().remove(0);
+    (status.state, status.value)
+}
+
+fn state(engine: &AlertEngine) -> AlertState {
+    engine.statuses()[0].state
+}
+
+#[test]
+fn fires_only_after_condition_held_for_duration() {
+    let mut engine = AlertEngine::new(vec![rule("cpu", "cpu_usage", 90.0, 80.0, 1000)]);
+    assert_eq!(state(&engine), AlertState::Inactive);
+
+    let events = engine.evaluate(0, &with_cpu(95.0));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].previous_state, AlertState::Inactive);
+    assert_eq!(events[0].state, AlertState::Pending);
+
+    // 持续时长不足，保持等待且不产生事件
+    assert!(engine.evaluate(999, &with_cpu(95.0)).is_empty());
+    assert_eq!(state(&engine), AlertState::Pending);
+
+    let events = engine.evaluate(1000, &with_cpu(95.0));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].previous_state, AlertState::Pending);
+    assert_eq!(events[0].state, AlertState::Firing);
+    assert_eq!(events[0].value, Some(95.0));
+    assert_eq!(engine.statuses()[0].since, 1000);
+}
+
+#[test]
+fn pending_falls_back_when_condition_breaks() {
+    let mut engine = AlertEngine::new(vec![rule("cpu", "cpu_usage", 90.0, 80.0, 1000)]);
+    engine.evaluate(0, &with_cpu(95.0));
+
+    // 回落到回差区间内也会中断等待
+    let events = engine.evaluate(500, &with_cpu(85.0));
+    assert_eq!(events[0].state, AlertState::Inactive);
+
+    // 重新满足条件后从头计时
+    engine.evaluate(600, &with_cpu(95.0));
+    engine.evaluate(1500, &with_cpu(95.0));
+    assert_eq!(state(&engine), AlertState::Pending);
+    engine.evaluate(1600, &with_cpu(95.0));
+    assert_eq!(state(&engine), AlertState::Firing);
+}
+
+#[test]
+fn stays_firing_within_hysteresis_and_resolves_on_clear() {
+    let mut engine = AlertEngine::new(vec![rule("cpu", "cpu_usage", 90.0, 80.0, 0)]);
+
+    let events = engine.evaluate(0, &with_cpu(95.0));
+    assert_eq!(events[0].state, AlertState::Firing);
+
+    // 介于解除阈值与触发阈值之间时保持告警
+    for (timestamp, value) in [(1, 85.0), (2, 80.0), (3, 89.5)] {
+        assert!(engine.evaluate(timestamp, &with_cpu(value)).is_empty());
+        assert_eq!(state(&engine), AlertState::Firing);
+    }
+
+    let events = engine.evaluate(4, &with_cpu(79.5));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].previous_state, AlertState::Firing);
+    assert_eq!(events[0].state, AlertState::Resolved);
+    assert_eq!(events[0].value, Some(79.5));
+
+    // 解除后回到回差区间不会重新触发
+    assert!(engine.evaluate(5, &with_cpu(85.0)).is_empty());
+    assert_eq!(state(&engine), AlertState::Resolved);
+
+    let events = engine.evaluate(6, &with_cpu(95.0));
+    assert_eq!(events[0].previous_state, AlertState::Resolved);
+    assert_eq!(events[0].state, AlertState::Firing);
+}
+
+#[test]
+fn firing_rule_holds_while_metric_is_missing() {
+    let mut engine = AlertEngine::new(vec![rule(
+        "pkg",
+        "temperatures[category=cpu-package].temperature",
+        65.0,
+        50.0,
+        0,
+    )]);
+    engine.evaluate(0, &sample());
+    assert_eq!(state(&engine), AlertState::Firing);
+
+    assert!(engine.evaluate(1, &SystemInfo::default()).is_empty());
+    let status = engine.statuses().remove(0);
+    assert_eq!(status.state, AlertState::Firing);
+    assert_eq!(status.value, None);
+}
+
+#[test]
+fn resolves_array_filters() {
+    // 按字段筛选
+    assert_eq!(
+        evaluate_once("temperatures[category=cpu-package].temperature", 65.0),
+        (AlertState::Firing, Some(70.0))
+    );
+    // 通配符匹配多个值时取最大值；对象取其主数值字段
+    assert_eq!(
+        evaluate_once("temperatures[*]", 65.0),
+        (AlertState::Firing, Some(75.0))
+    );
+    // 按下标选取
+    assert_eq!(
+        evaluate_once("cpus[1].usage_percent", 50.0),
+        (AlertState::Firing, Some(55.0))
+    );
+    // 非字符串字段的筛选值按 JSON 比较
+    assert_eq!(
+        evaluate_once("cpus[frequency=3600].usage_percent", 50.0),
+        (AlertState::Inactive, Some(10.0))
+    );
+    // 筛选值中的点号不作为分隔符
+    assert_eq!(
+        evaluate_once("temperatures[label=Package id 0].temperature", 65.0),
+        (AlertState::Firing, Some(70.0))
+    );
+}
+
+#[test]
+fn missing_keys_resolve_to_no_value() {
+    for metric in [
+        "temperatures[category=storage].temperature",
+        "cpus[5].usage_percent",
+        "cpus[*].missing",
+        "missing.field",
+        "cpu_usage[0]",
+    ] {
+        assert_eq!(
+            evaluate_once(metric, 0.0),
+            (AlertState::Inactive, None),
+            "{}",
+            metric
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_paths_and_thresholds() {
+    for metric in [
+        "cpus[1",
+        "cpus[x].usage_percent",
+        "cpus[1]x",
+        "cpus..usage",
+        "",
+    ] {
+        assert!(
+            validate_rule(&rule("r", metric, 90.0, 80.0, 0)).is_err(),
+            "{}",
+            metric
+        );
+    }
+    assert!(validate_rule(&rule("r", "cpus[*].usage_percent", 90.0, 80.0, 0)).is_ok());
+
+    // 解除阈值方向与比较方式不一致
+    assert!(validate_rule(&rule("r", "cpu_usage", 80.0, 90.0, 0)).is_err());
+    assert!(validate_rule(&rule("r", "cpu_usage", f64::NAN, 80.0, 0)).is_err());
+
+    // 无效规则在创建引擎时被跳过
+    let engine = AlertEngine::new(vec![
+        rule("bad", "cpus[", 90.0, 80.0, 0),
+        rule("good", "cpu_usage", 90.0, 80.0, 0),
+    ]);
+    let ids: Vec<_> = engine.rules().into_iter().map(|rule| rule.id).collect();
+    assert_eq!(ids, ["good"]);
+}
@@ -0,0 +1,216 @@
+//! 采集器注册表：单个采集器失败时的隔离与结果沿用，以及按间隔调度
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use system_monitor_core::collectors::{CollectContext, Collector, CollectorRegistry, MetricSet};
+use system_monitor_core::models::{CollectorStatus, CustomMetric, MonitorConfig};
+use system_monitor_core::MonitorError;
+
+/// 第 n 次采集的结果
+enum Outcome {
+    Value(f64),
+    Fail,
+    Panic,
+}
+
+/// 按脚本返回结果的采集器
+struct ScriptedCollector {
+    name: &'static str,
+    interval: Duration,
+    script: fn(usize) -> Outcome,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ScriptedCollector {
+    fn boxed(
+        name: &'static str,
+        interval: Duration,
+        script: fn(usize) -> Outcome,
+    ) -> (Box<dyn Collector>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let collector = Self {
+            name,
+            interval,
+            script,
+            calls: calls.clone(),
+        };
+        (Box::new(collector), calls)
+    }
+}
+
+impl Collector for ScriptedCollector {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn interval(&self, _config: &MonitorConfig) -> Duration {
+        self.interval
+    }
+
+    fn collect(&mut self, _ctx: &CollectContext<'_>) -> Result<MetricSet, MonitorError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        match (self.script)(call) {
+            Outcome::Value(value) => Ok(MetricSet::Custom(vec![CustomMetric {
+                name: self.name.to_string(),
+                value,
+                unit: None,
+            }])),
+            Outcome::Fail => Err(MonitorError::IoError("传感器不可用".to_string())),
+            Outcome::Panic => panic!("采集器 {} 崩溃", self.name),
+        }
+    }
+}
+
+/// 运行一轮采集，返回 (指标名称, 值)
+fn collect(registry: &mut CollectorRegistry, system: &System) -> Vec<(String, f64)> {
+    let config = MonitorConfig::default();
+    let ctx = CollectContext {
+        system,
+        config: &config,
+    };
+    registry
+        .collect(&ctx)
+        .into_iter()
+        .flat_map(|set| match set {
+            MetricSet::Custom(metrics) => metrics,
+            other => panic!("意外的采集结果: {:?}", other),
+        })
+        .map(|metric| (metric.name, metric.value))
+        .collect()
+}
+
+fn status(registry: &CollectorRegistry, name: &str) -> CollectorStatus {
+    registry
+        .statuses(&MonitorConfig::default())
+        .into_iter()
+        .find(|status| status.name == name)
+        .unwrap()
+}
+
+fn values(pairs: &[(&str, f64)]) -> Vec<(String, f64)> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect()
+}
+
+#[test]
+fn failing_collector_reuses_previous_result_and_reports_status() {
+    let system = System::new();
+    let mut registry = CollectorRegistry::new();
+    let (steady, _) =
+        ScriptedCollector::boxed("steady", Duration::ZERO, |call| Outcome::Value(call as f64));
+    let (flaky, _) = ScriptedCollector::boxed("flaky", Duration::ZERO, |call| match call {
+        0 => Outcome::Value(10.0),
+        1 => Outcome::Fail,
+        2 => Outcome::Panic,
+        _ => Outcome::Value(40.0),
+    });
+    registry.register(flaky).unwrap();
+    registry.register(steady).unwrap();
+
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("flaky", 10.0), ("steady", 0.0)])
+    );
+    assert_eq!(status(&registry, "flaky").last_error, None);
+
+    // 返回错误：沿用上一次的结果，其他采集器照常更新
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("flaky", 10.0), ("steady", 1.0)])
+    );
+    let flaky_status = status(&registry, "flaky");
+    assert!(flaky_status.last_error.unwrap().contains("传感器不可用"));
+    assert_eq!(flaky_status.consecutive_failures, 1);
+    let steady_status = status(&registry, "steady");
+    assert_eq!(steady_status.last_error, None);
+    assert_eq!(steady_status.consecutive_failures, 0);
+    assert!(steady_status.last_success.is_some());
+
+    // panic 与返回错误同样处理
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("flaky", 10.0), ("steady", 2.0)])
+    );
+    let flaky_status = status(&registry, "flaky");
+    assert!(flaky_status.last_error.unwrap().contains("panic"));
+    assert_eq!(flaky_status.consecutive_failures, 2);
+
+    // 恢复后清除错误状态
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("flaky", 40.0), ("steady", 3.0)])
+    );
+    let flaky_status = status(&registry, "flaky");
+    assert_eq!(flaky_status.last_error, None);
+    assert_eq!(flaky_status.consecutive_failures, 0);
+}
+
+#[test]
+fn collector_without_previous_result_is_left_out() {
+    let system = System::new();
+    let mut registry = CollectorRegistry::new();
+    let (broken, _) = ScriptedCollector::boxed("broken", Duration::ZERO, |_| Outcome::Panic);
+    let (steady, _) = ScriptedCollector::boxed("steady", Duration::ZERO, |_| Outcome::Value(1.0));
+    registry.register(broken).unwrap();
+    registry.register(steady).unwrap();
+
+    assert_eq!(collect(&mut registry, &system), values(&[("steady", 1.0)]));
+    let broken_status = status(&registry, "broken");
+    assert_eq!(broken_status.consecutive_failures, 1);
+    assert_eq!(broken_status.last_success, None);
+
+    // 同名采集器不能重复注册
+    let (duplicate, _) = ScriptedCollector::boxed("steady", Duration::ZERO, |_| Outcome::Fail);
+    assert!(registry.register(duplicate).is_err());
+}
+
+#[test]
+fn longer_interval_collector_runs_only_when_due() {
+    /// 与注册表中的 DUE_TOLERANCE 一致
+    const TOLERANCE: Duration = Duration::from_millis(50);
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    let system = System::new();
+    let mut registry = CollectorRegistry::new();
+    let (fast, fast_calls) =
+        ScriptedCollector::boxed("fast", Duration::ZERO, |call| Outcome::Value(call as f64));
+    let (slow, slow_calls) =
+        ScriptedCollector::boxed("slow", INTERVAL, |call| Outcome::Value(call as f64));
+    registry.register(fast).unwrap();
+    registry.register(slow).unwrap();
+
+    // 首次采集时所有采集器都会运行
+    let first_run = Instant::now();
+    collect(&mut registry, &system);
+    assert_eq!(slow_calls.load(Ordering::SeqCst), 1);
+
+    // 未到期时跳过，但仍返回缓存的结果
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("fast", 1.0), ("slow", 0.0)])
+    );
+    assert_eq!(slow_calls.load(Ordering::SeqCst), 1);
+
+    // 距离到期不足容差时视为已到期
+    let almost_due = first_run + INTERVAL - TOLERANCE + Duration::from_millis(5);
+    std::thread::sleep(almost_due.saturating_duration_since(Instant::now()));
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("fast", 2.0), ("slow", 1.0)])
+    );
+    assert_eq!(slow_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(fast_calls.load(Ordering::SeqCst), 3);
+
+    // 重新计时
+    assert_eq!(
+        collect(&mut registry, &system),
+        values(&[("fast", 3.0), ("slow", 1.0)])
+    );
+    assert_eq!(slow_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(status(&registry, "slow").interval_ms, Some(500));
+}
@@ -0,0 +1,82 @@
+//! 使用录制的 `/proc/stat` 测试 CPU 时间分解
+
+use std::fs;
+use std::path::Path;
+use system_monitor_core::cpu_times::parse_proc_stat;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/proc")
+            .join(name),
+    )
+    .unwrap()
+}
+
+fn assert_percent(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-4,
+        "期望 {expected}，实际 {actual}"
+    );
+}
+
+#[test]
+fn parses_cpu_lines_only() {
+    let times = parse_proc_stat(&fixture("stat_before"));
+
+    let mut names: Vec<&str> = times.keys().map(String::as_str).collect();
+    names.sort();
+    // softirq 等其他行不是 CPU 行
+    assert_eq!(names, vec!["cpu", "cpu0", "cpu1"]);
+
+    let cpu0 = times["cpu0"];
+    assert_eq!(cpu0.user, 1000);
+    assert_eq!(cpu0.nice, 20);
+    assert_eq!(cpu0.system, 500);
+    assert_eq!(cpu0.idle, 8000);
+    assert_eq!(cpu0.iowait, 100);
+    assert_eq!(cpu0.softirq, 10);
+}
+
+#[test]
+fn missing_columns_on_old_kernels_read_as_zero() {
+    // 2.6.11 之前的内核只有前四列
+    let times = parse_proc_stat("cpu  10 20 30 40\n");
+    let cpu = times["cpu"];
+    assert_eq!((cpu.user, cpu.idle), (10, 40));
+    assert_eq!((cpu.iowait, cpu.irq, cpu.softirq, cpu.steal), (0, 0, 0, 0));
+}
+
+#[test]
+fn breakdown_between_samples() {
+    let before = parse_proc_stat(&fixture("stat_before"));
+    let after = parse_proc_stat(&fixture("stat_after"));
+
+    let cpu0 = after["cpu0"].breakdown_since(&before["cpu0"]).unwrap();
+    assert_percent(cpu0.user, 25.0);
+    assert_percent(cpu0.system, 10.0);
+    assert_percent(cpu0.idle, 60.0);
+    assert_percent(cpu0.iowait, 4.0);
+    assert_percent(cpu0.softirq, 1.0);
+    assert_percent(cpu0.steal, 0.0);
+
+    let cpu1 = after["cpu1"].breakdown_since(&before["cpu1"]).unwrap();
+    assert_percent(cpu1.idle, 100.0);
+    assert_percent(cpu1.user, 0.0);
+
+    let total = after["cpu"].breakdown_since(&before["cpu"]).unwrap();
+    assert_percent(total.user, 12.5);
+    assert_percent(total.system, 5.0);
+    assert_percent(total.idle, 80.0);
+    assert_percent(total.iowait, 2.0);
+    assert_percent(total.softirq, 0.5);
+}
+
+#[test]
+fn no_breakdown_without_progress_or_after_wraparound() {
+    let before = parse_proc_stat(&fixture("stat_before"));
+    let after = parse_proc_stat(&fixture("stat_after"));
+
+    assert!(before["cpu"].breakdown_since(&before["cpu"]).is_none());
+    assert!(before["cpu"].breakdown_since(&after["cpu"]).is_none());
+}
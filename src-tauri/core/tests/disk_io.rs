@@ -0,0 +1,78 @@
+//! 使用录制的 `/proc/diskstats` 测试块设备 IO 统计
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use system_monitor_core::disk_io::parse_diskstats;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/proc")
+            .join(name),
+    )
+    .unwrap()
+}
+
+#[test]
+fn parses_devices_and_skips_truncated_lines() {
+    let counters = parse_diskstats(&fixture("diskstats_before"));
+
+    let mut names: Vec<&str> = counters.keys().map(String::as_str).collect();
+    names.sort();
+    // 字段不足的 sdb 被跳过；分区和 loop 设备由 read_disk_counters 过滤
+    assert_eq!(names, vec!["loop0", "nvme0n1", "nvme0n1p1", "sda"]);
+
+    let nvme = counters["nvme0n1"];
+    assert_eq!(nvme.reads_completed, 10000);
+    assert_eq!(nvme.sectors_read, 800000);
+    assert_eq!(nvme.writes_completed, 20000);
+    assert_eq!(nvme.sectors_written, 1600000);
+    assert_eq!(nvme.io_ticks_ms, 12000);
+    assert_eq!(nvme.weighted_io_ticks_ms, 13000);
+
+    // 4.18 之前的内核没有 discard 和 flush 字段
+    let sda = counters["sda"];
+    assert_eq!(sda.io_ticks_ms, 300);
+    assert_eq!(sda.weighted_io_ticks_ms, 150);
+}
+
+#[test]
+fn rates_between_samples() {
+    let before = parse_diskstats(&fixture("diskstats_before"));
+    let after = parse_diskstats(&fixture("diskstats_after"));
+    let elapsed = Duration::from_secs(2);
+
+    let nvme = after["nvme0n1"]
+        .stats_since("nvme0n1", &before["nvme0n1"], elapsed)
+        .unwrap();
+    assert_eq!(nvme.name, "nvme0n1");
+    assert_eq!(nvme.read_bytes_per_sec, 2.0 * 1024.0 * 1024.0);
+    assert_eq!(nvme.write_bytes_per_sec, 512.0 * 1024.0);
+    assert_eq!(nvme.read_iops, 200.0);
+    assert_eq!(nvme.write_iops, 50.0);
+    assert_eq!(nvme.avg_queue_depth, 1.5);
+    assert_eq!(nvme.utilization_percent, 50.0);
+
+    let idle = after["nvme0n1p1"]
+        .stats_since("nvme0n1p1", &before["nvme0n1p1"], elapsed)
+        .unwrap();
+    assert_eq!(idle.read_bytes_per_sec, 0.0);
+    assert_eq!(idle.utilization_percent, 0.0);
+}
+
+#[test]
+fn utilization_is_capped_and_zero_interval_is_ignored() {
+    let before = parse_diskstats(&fixture("diskstats_before"));
+    let after = parse_diskstats(&fixture("diskstats_after"));
+
+    // 并发请求较多时忙碌时间可能超过采样间隔
+    let sda = after["sda"]
+        .stats_since("sda", &before["sda"], Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(sda.utilization_percent, 100.0);
+
+    assert!(after["sda"]
+        .stats_since("sda", &before["sda"], Duration::ZERO)
+        .is_none());
+}
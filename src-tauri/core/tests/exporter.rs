@@ -0,0 +1,106 @@
+//! 启动导出服务并通过 HTTP 抓取 `/metrics`，检查 OpenMetrics 输出
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use system_monitor_core::exporter::{self, ExporterSource, MetricsExporter};
+use system_monitor_core::models::{CpuCoreInfo, ExporterConfig, MemoryInfo, SystemInfo};
+use tokio::sync::RwLock;
+
+fn sample() -> SystemInfo {
+    SystemInfo {
+        cpu_usage: f32::NAN,
+        cpus: vec![CpuCoreInfo {
+            name: "cpu0".to_string(),
+            usage_percent: 42.5,
+            frequency: 3600,
+            times: None,
+        }],
+        memory: MemoryInfo {
+            total: 16 * 1024,
+            used: 4 * 1024,
+            available: 12 * 1024,
+            usage_percent: f32::INFINITY,
+            swap_total: 0,
+            swap_used: 0,
+        },
+        ..Default::default()
+    }
+}
+
+/// 发送一个 GET 请求，返回响应头和响应体
+fn get(address: &str, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, address
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.to_string(), body.to_string())
+}
+
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+#[test]
+fn formats_non_finite_values() {
+    assert_eq!(exporter::format_value(f64::INFINITY), "+Inf");
+    assert_eq!(exporter::format_value(f64::NEG_INFINITY), "-Inf");
+    assert_eq!(exporter::format_value(f64::NAN), "NaN");
+    assert_eq!(exporter::format_value(42.5), "42.5");
+    assert_eq!(exporter::format_value(3600.0), "3600");
+}
+
+#[test]
+fn serves_openmetrics_over_http() {
+    let current_data = Arc::new(RwLock::new(None));
+    let exporter = MetricsExporter::start(
+        ExporterConfig {
+            enabled: true,
+            listen_address: "127.0.0.1:0".to_string(),
+        },
+        ExporterSource {
+            current_data: current_data.clone(),
+        },
+    )
+    .unwrap();
+    let address = exporter.address().to_string();
+    assert!(!address.ends_with(":0"));
+
+    // 尚无采样数据
+    let (head, _) = get(&address, "/metrics");
+    assert!(head.starts_with("HTTP/1.1 503"), "{}", head);
+
+    *current_data.blocking_write() = Some(Arc::new(sample()));
+    let (head, body) = get(&address, "/metrics");
+    assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+    assert_eq!(
+        header(&head, "Content-Type"),
+        Some("application/openmetrics-text; version=1.0.0; charset=utf-8")
+    );
+
+    for family in [
+        "system_monitor_cpu_usage_percent gauge",
+        "system_monitor_cpu_core_usage_percent gauge",
+        "system_monitor_memory_bytes gauge",
+        "system_monitor_network_received_bytes counter",
+    ] {
+        assert!(body.contains(&format!("# TYPE {}\n", family)), "{}", family);
+    }
+    assert!(body.contains("\nsystem_monitor_cpu_usage_percent NaN\n"));
+    assert!(body.contains("\nsystem_monitor_cpu_core_usage_percent{core=\"cpu0\"} 42.5\n"));
+    assert!(body.contains("\nsystem_monitor_memory_usage_percent +Inf\n"));
+    assert!(body.ends_with("# EOF\n"));
+    assert_eq!(body.matches("# EOF").count(), 1);
+
+    let (head, _) = get(&address, "/other");
+    assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+}
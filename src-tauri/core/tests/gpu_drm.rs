@@ -0,0 +1,341 @@
+//! 使用临时目录中的模拟 sysfs 树测试 AMD/Intel GPU 后端
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use system_monitor_core::errors::GpuMonitorError;
+use system_monitor_core::gpu_drm::DrmBackend;
+use system_monitor_core::models::{
+    GpuClockDomain, GpuMetric, GpuThrottleReason, MetricUnavailableReason,
+};
+use system_monitor_core::{GpuBackend, GpuMonitor};
+
+/// 测试结束时删除的模拟 sysfs 目录
+struct FakeSysfs {
+    root: PathBuf,
+}
+
+impl FakeSysfs {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "system-monitor-drm-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Self { root }
+    }
+
+    fn write(&self, relative: &str, content: &str) {
+        let path = self.root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// 一块 amdgpu 独立显卡
+    fn add_amdgpu(&self, card: &str) {
+        self.write(
+            &format!("{}/device/uevent", card),
+            "DRIVER=amdgpu\nPCI_ID=1002:73BF\nPCI_SLOT_NAME=0000:03:00.0\n",
+        );
+        self.write(&format!("{}/device/gpu_busy_percent", card), "37\n");
+        self.write(
+            &format!("{}/device/mem_info_vram_total", card),
+            "17163091968\n",
+        );
+        self.write(
+            &format!("{}/device/mem_info_vram_used", card),
+            "4290772992\n",
+        );
+        self.write(&format!("{}/device/unique_id", card), "a1b2c3d4e5f60718\n");
+        self.write(
+            &format!("{}/device/hwmon/hwmon3/temp1_input", card),
+            "54000\n",
+        );
+        self.write(
+            &format!("{}/device/hwmon/hwmon3/freq1_input", card),
+            "2105000000\n",
+        );
+        self.write(
+            &format!("{}/device/hwmon/hwmon3/power1_average", card),
+            "187000000\n",
+        );
+        self.write(
+            &format!("{}/device/hwmon/hwmon3/power1_cap", card),
+            "203000000\n",
+        );
+        self.write(
+            &format!("{}/device/hwmon/hwmon3/freq2_input", card),
+            "1000000000\n",
+        );
+        self.write(&format!("{}/device/hwmon/hwmon3/pwm1", card), "102\n");
+        self.write(
+            &format!("{}/device/current_link_speed", card),
+            "16.0 GT/s PCIe\n",
+        );
+        self.write(&format!("{}/device/current_link_width", card), "16\n");
+        self.write(
+            &format!("{}/device/vbios_version", card),
+            "113-D4120100-100\n",
+        );
+        self.write(
+            &format!("{}/device/ras/umc_err_count", card),
+            "ue: 0\nce: 3\n",
+        );
+    }
+
+    /// 一块 i915 集成显卡
+    fn add_i915(&self, card: &str) {
+        self.write(
+            &format!("{}/device/uevent", card),
+            "DRIVER=i915\nPCI_ID=8086:A780\nPCI_SLOT_NAME=0000:00:02.0\n",
+        );
+        self.write(&format!("{}/gt_act_freq_mhz", card), "1450\n");
+        self.write(&format!("{}/power/rc6_residency_ms", card), "1000\n");
+    }
+}
+
+impl Drop for FakeSysfs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+#[test]
+fn reads_amdgpu_metrics() {
+    let sysfs = FakeSysfs::new("amdgpu");
+    sysfs.add_amdgpu("card0");
+    // 显示接口目录不是显卡
+    sysfs.write("card0-DP-1/status", "connected\n");
+
+    let backend = DrmBackend::probe_at(sysfs.path()).unwrap();
+    assert_eq!(backend.device_count(), 1);
+
+    let info = backend.device_info(0).unwrap();
+    assert_eq!(info.name, "AMD GPU [1002:73BF]");
+    assert_eq!(info.pci_bus_id.as_deref(), Some("0000:03:00.0"));
+    assert_eq!(info.uuid.as_deref(), Some("a1b2c3d4e5f60718"));
+    assert_eq!(info.usage_percent, Some(37.0));
+    let memory = info.memory.unwrap();
+    assert_eq!(memory.total, 17163091968);
+    assert_eq!(memory.used, 4290772992);
+    assert!((memory.usage_percent - 25.0).abs() < 0.1);
+    assert_eq!(info.temperature, Some(54.0));
+    assert_eq!(info.frequency, Some(2105));
+    assert_eq!(info.memory_frequency, Some(1000));
+    assert_eq!(info.power_draw_watts, Some(187.0));
+    assert_eq!(info.power_limit_watts, Some(203.0));
+    assert_eq!(info.fan_speed_percent, Some(40));
+    assert_eq!(info.throttle_reasons, None);
+    assert_eq!(
+        info.unavailable.keys().copied().collect::<Vec<_>>(),
+        vec![
+            GpuMetric::PerformanceState,
+            GpuMetric::ThrottleReasons,
+            GpuMetric::PcieThroughput,
+        ]
+    );
+
+    let details = backend.detailed_info(0).unwrap();
+    assert_eq!(details.power_draw_watts, Some(187.0));
+    assert_eq!(details.power_limit_watts, Some(203.0));
+    assert_eq!(details.pcie_generation, Some(4));
+    assert_eq!(details.pcie_width, Some(16));
+    assert_eq!(details.vbios_version.as_deref(), Some("113-D4120100-100"));
+    assert_eq!(details.fan_speed_percent, Some(40));
+    assert_eq!(details.clocks.len(), 2);
+    assert_eq!(details.clocks[1].domain, GpuClockDomain::Memory);
+    assert_eq!(details.clocks[1].current_mhz, Some(1000));
+    let ecc = details.ecc_errors.unwrap();
+    assert_eq!((ecc.corrected, ecc.uncorrected), (3, 0));
+}
+
+#[test]
+fn computes_i915_usage_from_rc6_residency() {
+    let sysfs = FakeSysfs::new("i915");
+    sysfs.add_i915("card1");
+
+    let backend = DrmBackend::probe_at(sysfs.path()).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    // 期间 GPU 一直处于 RC6，使用率为 0
+    sysfs.write("card1/power/rc6_residency_ms", "1200\n");
+
+    let info = backend.device_info(0).unwrap();
+    assert_eq!(info.name, "Intel GPU [8086:A780]");
+    assert_eq!(info.usage_percent, Some(0.0));
+    assert_eq!(info.frequency, Some(1450));
+    // 集成显卡没有独立显存，也没有温度传感器
+    assert!(info.memory.is_none());
+    assert_eq!(info.temperature, None);
+    assert_eq!(
+        info.unavailable.get(&GpuMetric::Memory),
+        Some(&MetricUnavailableReason::NotSupported)
+    );
+
+    // 内核未导出降频原因
+    assert_eq!(
+        info.unavailable.get(&GpuMetric::ThrottleReasons),
+        Some(&MetricUnavailableReason::NotSupported)
+    );
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    // RC6 没有增长，GPU 一直忙碌
+    let info = backend.device_info(0).unwrap();
+    assert!(
+        info.usage_percent.unwrap() > 99.0,
+        "{:?}",
+        info.usage_percent
+    );
+}
+
+#[test]
+fn detailed_info_keeps_its_own_rc6_baseline() {
+    let sysfs = FakeSysfs::new("i915-details");
+    sysfs.add_i915("card0");
+
+    let backend = DrmBackend::probe_at(sysfs.path()).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    // 前 100ms 一直处于 RC6
+    sysfs.write("card0/power/rc6_residency_ms", "1100\n");
+    let details = backend.detailed_info(0).unwrap();
+    assert!(details.gpu_utilization.unwrap() < 10.0, "{:?}", details);
+
+    // 后 100ms 一直忙碌；周期采样的区间从探测时开始，不受详情查询影响
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let usage = backend.device_info(0).unwrap().usage_percent.unwrap();
+    assert!(usage > 40.0 && usage < 90.0, "{}", usage);
+
+    // 详情查询的区间从上一次详情查询开始
+    let details = backend.detailed_info(0).unwrap();
+    assert!(details.gpu_utilization.unwrap() > 99.0, "{:?}", details);
+}
+
+#[test]
+fn reads_i915_throttle_reasons() {
+    let sysfs = FakeSysfs::new("i915-throttle");
+    sysfs.add_i915("card0");
+    for (file, active) in [
+        ("status", "0"),
+        ("pl1", "0"),
+        ("pl2", "0"),
+        ("thermal", "0"),
+        ("ratl", "0"),
+    ] {
+        sysfs.write(&format!("card0/gt/gt0/throttle_reason_{}", file), active);
+    }
+
+    let backend = DrmBackend::probe_at(sysfs.path()).unwrap();
+    assert_eq!(
+        backend.device_info(0).unwrap().throttle_reasons,
+        Some(Vec::new())
+    );
+
+    for file in ["status", "pl1", "pl2", "thermal"] {
+        sysfs.write(&format!("card0/gt/gt0/throttle_reason_{}", file), "1\n");
+    }
+    let info = backend.device_info(0).unwrap();
+    // PL1 和 PL2 都是功耗上限，只报告一次
+    assert_eq!(
+        info.throttle_reasons,
+        Some(vec![
+            GpuThrottleReason::SwPowerCap,
+            GpuThrottleReason::HwThermalSlowdown,
+        ])
+    );
+    assert_eq!(
+        backend.detailed_info(0).unwrap().throttle_reasons,
+        info.throttle_reasons.unwrap()
+    );
+}
+
+#[test]
+fn reports_unsupported_and_missing_gpus() {
+    let sysfs = FakeSysfs::new("unsupported");
+    sysfs.write("card0/device/uevent", "DRIVER=nouveau\n");
+    assert!(matches!(
+        DrmBackend::probe_at(sysfs.path()),
+        Err(GpuMonitorError::UnsupportedGpu)
+    ));
+
+    // NVIDIA 显卡交给 NVML 后端
+    let sysfs = FakeSysfs::new("nvidia");
+    sysfs.write("card0/device/uevent", "DRIVER=nvidia\n");
+    assert!(matches!(
+        DrmBackend::probe_at(sysfs.path()),
+        Err(GpuMonitorError::NoGpuFound)
+    ));
+}
+
+#[test]
+fn monitor_numbers_devices_across_cards() {
+    let sysfs = FakeSysfs::new("monitor");
+    sysfs.add_i915("card0");
+    sysfs.add_amdgpu("card1");
+
+    let backend = DrmBackend::probe_at(sysfs.path()).unwrap();
+    let monitor = GpuMonitor::with_backends(vec![Box::new(backend)]);
+    assert!(monitor.is_available());
+    assert_eq!(
+        monitor.get_all_gpu_names(),
+        vec!["Intel GPU [8086:A780]", "AMD GPU [1002:73BF]"]
+    );
+
+    let gpus = monitor.get_gpu_info(&["0000:03:00.0".to_string()]);
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].index, 1);
+
+    assert!(matches!(
+        monitor.get_detailed_gpu_info(2),
+        Err(GpuMonitorError::DeviceNotFound { device_index: 2 })
+    ));
+}
+
+#[test]
+fn attributes_processes_from_drm_fdinfo() {
+    let sysfs = FakeSysfs::new("fdinfo");
+    sysfs.add_amdgpu("card0");
+    let amdgpu_client = |gfx_ns: u64| {
+        format!(
+            "pos:\t0\nflags:\t02100002\ndrm-driver:\tamdgpu\ndrm-pdev:\t0000:03:00.0\n\
+             drm-client-id:\t7\ndrm-memory-vram:\t262144 KiB\ndrm-memory-gtt:\t4096 KiB\n\
+             drm-engine-gfx:\t{} ns\ndrm-engine-dec:\t0 ns\n",
+            gfx_ns
+        )
+    };
+    sysfs.write("proc/4242/comm", "game\n");
+    // 同一客户端被两个文件描述符引用时只统计一次
+    sysfs.write("proc/4242/fdinfo/9", &amdgpu_client(1_000_000));
+    sysfs.write("proc/4242/fdinfo/10", &amdgpu_client(1_000_000));
+    sysfs.write("proc/4242/fdinfo/0", "pos:\t0\nflags:\t02\n");
+    // 其他显卡上的客户端
+    sysfs.write(
+        "proc/5000/fdinfo/3",
+        "drm-pdev:\t0000:00:02.0\ndrm-client-id:\t1\ndrm-engine-render:\t5 ns\n",
+    );
+
+    let backend = DrmBackend::probe_at(sysfs.path())
+        .unwrap()
+        .with_proc_root(sysfs.path().join("proc"));
+
+    let processes = backend.processes(0).unwrap();
+    assert_eq!(processes.len(), 1);
+    assert_eq!(processes[0].pid, 4242);
+    assert_eq!(processes[0].name.as_deref(), Some("game"));
+    assert_eq!(processes[0].used_memory, Some(256 * 1024 * 1024));
+    // 第一次查询没有上一次的忙碌时间，无法计算利用率
+    assert_eq!(processes[0].sm_utilization, None);
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    sysfs.write("proc/4242/fdinfo/9", &amdgpu_client(1_000_000_000));
+    sysfs.write("proc/4242/fdinfo/10", &amdgpu_client(1_000_000_000));
+
+    let processes = backend.processes(0).unwrap();
+    // 忙碌时间的增量超过了墙钟时间，截断为 100%
+    assert_eq!(processes[0].sm_utilization, Some(100.0));
+    assert_eq!(processes[0].decoder_utilization, Some(0.0));
+    assert_eq!(processes[0].encoder_utilization, None);
+}
@@ -0,0 +1,524 @@
+//! 使用模拟后端测试 GpuMonitor
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use system_monitor_core::errors::GpuMonitorError;
+use system_monitor_core::gpu_mock::{MockGpuBackend, MockGpuDevice};
+use system_monitor_core::history;
+use system_monitor_core::models::{
+    GpuDeviceId, GpuMemoryInfo, GpuMetric, GpuProcessInfo, GpuThrottleReason,
+    MetricUnavailableReason, SystemInfo,
+};
+use system_monitor_core::retry::RetryConfig;
+use system_monitor_core::{GpuBackend, GpuMonitor};
+
+fn device(name: &str, uuid: &str, pci_bus_id: &str) -> MockGpuDevice {
+    MockGpuDevice {
+        name: name.to_string(),
+        uuid: Some(uuid.to_string()),
+        pci_bus_id: Some(pci_bus_id.to_string()),
+        usage_curve: vec![20.0, 60.0, 100.0],
+        memory: Some(GpuMemoryInfo::new(
+            8 * 1024 * 1024 * 1024,
+            2 * 1024 * 1024 * 1024,
+        )),
+        temperature: Some(65.0),
+        frequency: Some(1800),
+        ..Default::default()
+    }
+}
+
+fn two_gpus() -> (MockGpuBackend, GpuMonitor) {
+    let backend = MockGpuBackend::new(vec![
+        device("Mock GPU 0", "GPU-aaaa", "00000000:01:00.0"),
+        device("Mock GPU 1", "GPU-bbbb", "00000000:02:00.0"),
+    ]);
+    let monitor = GpuMonitor::with_backends(vec![Box::new(backend.clone())]);
+    (backend, monitor)
+}
+
+#[test]
+fn get_gpu_info_follows_usage_curve() {
+    let (_backend, monitor) = two_gpus();
+
+    let usages: Vec<Option<f32>> = (0..4)
+        .map(|_| monitor.get_gpu_info(&[])[0].usage_percent)
+        .collect();
+    assert_eq!(
+        usages,
+        vec![Some(20.0), Some(60.0), Some(100.0), Some(20.0)]
+    );
+
+    let gpus = monitor.get_gpu_info(&[]);
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[1].index, 1);
+    assert_eq!(gpus[1].name, "Mock GPU 1");
+    let memory = gpus[1].memory.as_ref().unwrap();
+    assert_eq!(memory.total, 8 * 1024 * 1024 * 1024);
+    assert_eq!(memory.usage_percent, 25.0);
+    assert!(!gpus[1].unavailable.contains_key(&GpuMetric::Memory));
+    assert_eq!(gpus[1].temperature, Some(65.0));
+}
+
+#[test]
+fn get_gpu_info_filters_by_selection() {
+    let (_backend, monitor) = two_gpus();
+
+    let by_index = monitor.get_gpu_info(&["1".to_string()]);
+    assert_eq!(by_index.len(), 1);
+    assert_eq!(by_index[0].name, "Mock GPU 1");
+
+    let by_uuid = monitor.get_gpu_info(&["GPU-aaaa".to_string()]);
+    assert_eq!(by_uuid.len(), 1);
+    assert_eq!(by_uuid[0].index, 0);
+
+    // PCI 总线ID 不区分大小写，首尾空白会被忽略
+    let by_bus_id = monitor.get_gpu_info(&[" 00000000:02:00.0 ".to_uppercase()]);
+    assert_eq!(by_bus_id.len(), 1);
+    assert_eq!(by_bus_id[0].index, 1);
+
+    assert!(monitor.get_gpu_info(&["GPU-cccc".to_string()]).is_empty());
+}
+
+#[test]
+fn devices_are_numbered_across_backends() {
+    let first = MockGpuBackend::new(vec![device("A", "GPU-a", "0000:01:00.0")]);
+    let second = MockGpuBackend::new(vec![
+        device("B", "GPU-b", "0000:02:00.0"),
+        device("C", "GPU-c", "0000:03:00.0"),
+    ]);
+    let monitor = GpuMonitor::with_backends(vec![Box::new(first), Box::new(second)]);
+
+    let indices: Vec<(u32, String)> = monitor
+        .get_gpu_info(&[])
+        .into_iter()
+        .map(|gpu| (gpu.index, gpu.name))
+        .collect();
+    assert_eq!(
+        indices,
+        vec![
+            (0, "A".to_string()),
+            (1, "B".to_string()),
+            (2, "C".to_string())
+        ]
+    );
+    let details = monitor.get_detailed_gpu_info(2).unwrap();
+    assert_eq!(details.index, 2);
+    assert_eq!(details.name, "C");
+}
+
+#[test]
+fn failed_reads_skip_the_device_for_one_sample() {
+    let (backend, monitor) = two_gpus();
+    backend.fail_next(0, GpuMonitorError::PermissionDenied);
+
+    let gpus = monitor.get_gpu_info(&[]);
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].index, 1);
+
+    // 失败的读取不推进曲线
+    let gpus = monitor.get_gpu_info(&[]);
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0].usage_percent, Some(20.0));
+}
+
+#[test]
+fn unplugged_devices_disappear_until_replugged() {
+    let (backend, monitor) = two_gpus();
+    backend.unplug(0);
+
+    let gpus = monitor.get_gpu_info(&[]);
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].index, 1);
+    assert_eq!(monitor.get_all_gpu_names(), vec!["GPU 0", "Mock GPU 1"]);
+    assert!(matches!(
+        monitor.get_detailed_gpu_info(0),
+        Err(GpuMonitorError::DataFetchError(_))
+    ));
+
+    backend.replug(0);
+    assert_eq!(monitor.get_gpu_info(&[]).len(), 2);
+
+    backend.add_device(device("Mock GPU 2", "GPU-cccc", "00000000:03:00.0"));
+    assert_eq!(monitor.get_gpu_info(&[]).len(), 3);
+}
+
+#[test]
+fn get_all_gpu_names_lists_every_device() {
+    let (_backend, monitor) = two_gpus();
+    assert_eq!(
+        monitor.get_all_gpu_names(),
+        vec!["Mock GPU 0", "Mock GPU 1"]
+    );
+    assert!(GpuMonitor::default().get_all_gpu_names().is_empty());
+}
+
+#[test]
+fn get_detailed_gpu_info_error_paths() {
+    let unavailable = GpuMonitor::default();
+    assert!(!unavailable.is_available());
+    assert_eq!(
+        unavailable.get_last_error().as_deref(),
+        Some("GPU监控不可用")
+    );
+    assert!(matches!(
+        unavailable.get_detailed_gpu_info(0),
+        Err(GpuMonitorError::NoGpuFound)
+    ));
+
+    let empty = GpuMonitor::with_backends(vec![Box::new(MockGpuBackend::new(Vec::new()))]);
+    assert!(!empty.is_available());
+    assert!(matches!(
+        empty.get_detailed_gpu_info(0),
+        Err(GpuMonitorError::NoGpuFound)
+    ));
+
+    let (backend, monitor) = two_gpus();
+    assert!(matches!(
+        monitor.get_detailed_gpu_info(2),
+        Err(GpuMonitorError::DeviceNotFound { device_index: 2 })
+    ));
+
+    backend.fail_next(1, GpuMonitorError::NvmlInitError("driver gone".to_string()));
+    assert!(matches!(
+        monitor.get_detailed_gpu_info(1),
+        Err(GpuMonitorError::NvmlInitError(_))
+    ));
+    let details = monitor.get_detailed_gpu_info(1).unwrap();
+    assert_eq!(details.uuid.as_deref(), Some("GPU-bbbb"));
+    assert_eq!(details.memory_total, Some(8 * 1024 * 1024 * 1024));
+    assert_eq!(details.clocks[0].current_mhz, Some(1800));
+    assert_eq!(details.power_draw_watts, None);
+}
+
+#[test]
+fn unsupported_metrics_are_reported_instead_of_invented() {
+    let backend = MockGpuBackend::new(vec![MockGpuDevice {
+        name: "Headless GPU".to_string(),
+        temperature: Some(40.0),
+        ..Default::default()
+    }]);
+    let monitor = GpuMonitor::with_backends(vec![Box::new(backend)]);
+
+    let gpu = &monitor.get_gpu_info(&[])[0];
+    assert_eq!(gpu.usage_percent, None);
+    assert!(gpu.memory.is_none());
+    assert_eq!(gpu.temperature, Some(40.0));
+    assert_eq!(
+        gpu.unavailable.keys().copied().collect::<Vec<_>>(),
+        vec![
+            GpuMetric::Usage,
+            GpuMetric::Memory,
+            GpuMetric::Frequency,
+            GpuMetric::MemoryFrequency,
+            GpuMetric::PowerDraw,
+            GpuMetric::PowerLimit,
+            GpuMetric::FanSpeed,
+            GpuMetric::PerformanceState,
+            GpuMetric::ThrottleReasons,
+            GpuMetric::PcieThroughput,
+        ]
+    );
+    assert!(gpu
+        .unavailable
+        .values()
+        .all(|reason| *reason == MetricUnavailableReason::NotSupported));
+
+    let json = serde_json::to_value(gpu).unwrap();
+    assert!(json["usage_percent"].is_null());
+    assert_eq!(json["unavailable"]["memory"], "not_supported");
+    assert_eq!(json["unavailable"]["pcie_throughput"], "not_supported");
+}
+
+#[test]
+fn power_fan_and_throttling_are_sampled_periodically() {
+    let backend = MockGpuBackend::new(vec![MockGpuDevice {
+        name: "Training GPU".to_string(),
+        usage_curve: vec![100.0],
+        memory_frequency: Some(9501),
+        power_draw_watts: Some(298.5),
+        power_limit_watts: Some(300.0),
+        fan_speed_percent: Some(85),
+        performance_state: Some(0),
+        throttle_reasons: Some(vec![
+            GpuThrottleReason::SwPowerCap,
+            GpuThrottleReason::SwThermalSlowdown,
+        ]),
+        pcie_throughput: Some((12 * 1024 * 1024, 256 * 1024)),
+        ..Default::default()
+    }]);
+    let monitor = GpuMonitor::with_backends(vec![Box::new(backend.clone())]);
+
+    let gpu = &monitor.get_gpu_info(&[])[0];
+    assert_eq!(gpu.memory_frequency, Some(9501));
+    assert_eq!(gpu.power_draw_watts, Some(298.5));
+    assert_eq!(gpu.power_limit_watts, Some(300.0));
+    assert_eq!(gpu.fan_speed_percent, Some(85));
+    assert_eq!(gpu.performance_state, Some(0));
+    assert_eq!(gpu.pcie_rx_bytes_per_sec, Some(12 * 1024 * 1024));
+    assert_eq!(gpu.pcie_tx_bytes_per_sec, Some(256 * 1024));
+    assert!(!gpu.unavailable.contains_key(&GpuMetric::ThrottleReasons));
+
+    let json = serde_json::to_value(gpu).unwrap();
+    assert_eq!(
+        json["throttle_reasons"],
+        serde_json::json!(["sw_power_cap", "sw_thermal_slowdown"])
+    );
+}
+
+#[test]
+fn history_records_only_available_gpu_metrics() {
+    let backend = MockGpuBackend::new(vec![MockGpuDevice {
+        name: "Training GPU".to_string(),
+        usage_curve: vec![90.0],
+        temperature: Some(83.0),
+        power_draw_watts: Some(298.5),
+        throttle_reasons: Some(vec![GpuThrottleReason::SwThermalSlowdown]),
+        pcie_throughput: Some((4096, 1024)),
+        ..Default::default()
+    }]);
+    let monitor = GpuMonitor::with_backends(vec![Box::new(backend.clone())]);
+    let info = SystemInfo {
+        gpus: monitor.get_gpu_info(&[]),
+        ..Default::default()
+    };
+
+    let gpu_metrics: Vec<(String, f64)> = history::extract_metrics(&info)
+        .into_iter()
+        .filter(|(name, _)| name.starts_with("gpu."))
+        .collect();
+    let expected = [
+        ("gpu.0.usage", 90.0),
+        ("gpu.0.temperature", 83.0),
+        ("gpu.0.power_draw_watts", 298.5),
+        ("gpu.0.throttled", 1.0),
+        ("gpu.0.pcie_rx_bytes_per_sec", 4096.0),
+        ("gpu.0.pcie_tx_bytes_per_sec", 1024.0),
+    ];
+    assert_eq!(
+        gpu_metrics,
+        expected
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect::<Vec<_>>()
+    );
+}
+
+fn process(pid: u32, used_memory: Option<u64>) -> GpuProcessInfo {
+    GpuProcessInfo {
+        pid,
+        name: Some(format!("proc-{}", pid)),
+        used_memory,
+        sm_utilization: Some(10.0),
+        encoder_utilization: None,
+        decoder_utilization: None,
+    }
+}
+
+#[test]
+fn get_gpu_processes_sorts_by_memory() {
+    let (backend, monitor) = two_gpus();
+    backend.set_processes(
+        1,
+        vec![
+            process(300, None),
+            process(200, Some(512)),
+            process(100, Some(4096)),
+            process(150, Some(512)),
+        ],
+    );
+
+    let pids: Vec<u32> = monitor
+        .get_gpu_processes(1)
+        .unwrap()
+        .into_iter()
+        .map(|process| process.pid)
+        .collect();
+    assert_eq!(pids, vec![100, 150, 200, 300]);
+    assert!(monitor.get_gpu_processes(0).unwrap().is_empty());
+    assert!(matches!(
+        monitor.get_gpu_processes(2),
+        Err(GpuMonitorError::DeviceNotFound { device_index: 2 })
+    ));
+}
+
+/// 每次轮询都重新探测
+fn probe_every_poll() -> RetryConfig {
+    RetryConfig::new()
+        .initial_delay(Duration::ZERO)
+        .max_delay(Duration::ZERO)
+}
+
+fn names(devices: &[GpuDeviceId]) -> Vec<&str> {
+    devices.iter().map(|device| device.name.as_str()).collect()
+}
+
+#[test]
+fn reprobing_detects_late_drivers_and_hotplug() {
+    let backend = MockGpuBackend::new(vec![device("Mock GPU 0", "GPU-aaaa", "00000000:01:00.0")]);
+    let driver_loaded = Arc::new(AtomicBool::new(false));
+    let monitor = GpuMonitor::with_prober(
+        {
+            let backend = backend.clone();
+            let driver_loaded = driver_loaded.clone();
+            move |_: &[Arc<dyn GpuBackend>]| {
+                if driver_loaded.load(Ordering::SeqCst) {
+                    Ok(vec![Arc::new(backend.clone()) as Arc<dyn GpuBackend>])
+                } else {
+                    Err(GpuMonitorError::NvmlNotAvailable)
+                }
+            }
+        },
+        probe_every_poll(),
+    );
+
+    let status = monitor.status();
+    assert!(!status.available);
+    assert_eq!(
+        status.error,
+        Some(GpuMonitorError::NvmlNotAvailable.to_string())
+    );
+    assert!(status.devices.is_empty());
+    assert!(monitor.poll_devices().is_none());
+
+    // 驱动加载完成
+    driver_loaded.store(true, Ordering::SeqCst);
+    let change = monitor.poll_devices().unwrap();
+    assert!(!change.previously_available);
+    assert!(change.available);
+    assert_eq!(names(&change.added), vec!["Mock GPU 0"]);
+    assert!(monitor.is_available());
+    assert_eq!(monitor.get_gpu_info(&[]).len(), 1);
+    assert_eq!(monitor.get_last_error(), None);
+    assert!(monitor.poll_devices().is_none());
+
+    // 接入外接显卡
+    backend.add_device(device("Mock GPU 1", "GPU-bbbb", "00000000:02:00.0"));
+    let change = monitor.poll_devices().unwrap();
+    assert!(change.previously_available);
+    assert_eq!(names(&change.added), vec!["Mock GPU 1"]);
+    assert!(change.removed.is_empty());
+
+    // 设备掉线
+    backend.unplug(0);
+    let change = monitor.poll_devices().unwrap();
+    assert!(change.added.is_empty());
+    assert_eq!(names(&change.removed), vec!["Mock GPU 0"]);
+    let status = monitor.status();
+    assert_eq!(names(&status.devices), vec!["Mock GPU 1"]);
+    assert_eq!(
+        status
+            .last_change
+            .map(|change| names(&change.removed).len()),
+        Some(1)
+    );
+
+    // 驱动卸载
+    driver_loaded.store(false, Ordering::SeqCst);
+    let change = monitor.poll_devices().unwrap();
+    assert!(change.previously_available);
+    assert!(!change.available);
+    assert!(!monitor.is_available());
+    assert!(monitor.get_gpu_info(&[]).is_empty());
+}
+
+#[test]
+fn reprobing_reuses_the_current_backends() {
+    let backend = MockGpuBackend::new(vec![device("Mock GPU 0", "GPU-aaaa", "00000000:01:00.0")]);
+    let created = Arc::new(AtomicUsize::new(0));
+    let monitor = GpuMonitor::with_prober(
+        {
+            let backend = backend.clone();
+            let created = created.clone();
+            move |current: &[Arc<dyn GpuBackend>]| {
+                if !current.is_empty() {
+                    return Ok(current.to_vec());
+                }
+                created.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![Arc::new(backend.clone()) as Arc<dyn GpuBackend>])
+            }
+        },
+        probe_every_poll(),
+    );
+
+    assert!(monitor.poll_devices().is_none());
+    assert!(monitor.poll_devices().is_none());
+
+    // 复用的后端仍能发现新设备
+    backend.add_device(device("Mock GPU 1", "GPU-bbbb", "00000000:02:00.0"));
+    let change = monitor.poll_devices().unwrap();
+    assert_eq!(names(&change.added), vec!["Mock GPU 1"]);
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn status_does_not_wait_for_a_running_probe() {
+    let backend = MockGpuBackend::new(vec![device("Mock GPU 0", "GPU-aaaa", "00000000:01:00.0")]);
+    let (entered_sender, entered) = mpsc::channel();
+    let (release, release_receiver) = mpsc::channel::<()>();
+    let channels = Mutex::new((entered_sender, release_receiver));
+    let probes = AtomicUsize::new(0);
+    let monitor = Arc::new(GpuMonitor::with_prober(
+        move |_: &[Arc<dyn GpuBackend>]| {
+            // 创建时的首次探测直接失败，之后的探测阻塞到测试放行
+            if probes.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(GpuMonitorError::NvmlNotAvailable);
+            }
+            let (entered, release) = &*channels.lock().unwrap();
+            entered.send(()).unwrap();
+            release.recv().unwrap();
+            Ok(vec![Arc::new(backend.clone()) as Arc<dyn GpuBackend>])
+        },
+        probe_every_poll(),
+    ));
+
+    let poller = std::thread::spawn({
+        let monitor = monitor.clone();
+        move || monitor.poll_devices()
+    });
+    entered.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    // 探测进行中时查询状态不会被阻塞
+    let (status_sender, status_receiver) = mpsc::channel();
+    std::thread::spawn({
+        let monitor = monitor.clone();
+        move || status_sender.send(monitor.status()).unwrap()
+    });
+    let status = status_receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("探测进行中时 status() 被阻塞");
+    assert!(!status.available);
+    assert!(status.devices.is_empty());
+
+    release.send(()).unwrap();
+    let change = poller.join().unwrap().unwrap();
+    assert_eq!(names(&change.added), vec!["Mock GPU 0"]);
+    assert_eq!(names(&monitor.status().devices), vec!["Mock GPU 0"]);
+}
+
+#[test]
+fn reprobing_waits_for_the_backoff_delay() {
+    let probes = Arc::new(AtomicUsize::new(0));
+    let monitor = GpuMonitor::with_prober(
+        {
+            let probes = probes.clone();
+            move |_: &[Arc<dyn GpuBackend>]| {
+                probes.fetch_add(1, Ordering::SeqCst);
+                Err(GpuMonitorError::NoGpuFound)
+            }
+        },
+        RetryConfig::new().initial_delay(Duration::from_secs(3600)),
+    );
+
+    assert_eq!(probes.load(Ordering::SeqCst), 1);
+    assert!(monitor.poll_devices().is_none());
+    assert_eq!(probes.load(Ordering::SeqCst), 1);
+    assert!(monitor.status().next_probe_in_ms.unwrap() > 0);
+
+    // 固定后端的监控器不会重新探测
+    let (_backend, fixed) = two_gpus();
+    assert!(fixed.poll_devices().is_none());
+    assert_eq!(fixed.status().next_probe_in_ms, None);
+    assert_eq!(fixed.status().devices.len(), 2);
+}
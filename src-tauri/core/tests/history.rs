@@ -0,0 +1,165 @@
+//! 指标历史的环形缓冲区、记录分辨率和降采样
+
+use system_monitor_core::history::{downsample, HistoryStore};
+use system_monitor_core::models::{HistoryBucket, SystemInfo};
+
+const CPU: &str = "cpu.usage";
+
+fn with_cpu(cpu_usage: f32) -> SystemInfo {
+    SystemInfo {
+        cpu_usage,
+        ..Default::default()
+    }
+}
+
+fn point(timestamp: u64, value: f64) -> HistoryBucket {
+    HistoryBucket {
+        timestamp,
+        min: value,
+        max: value,
+        avg: value,
+        count: 1,
+    }
+}
+
+/// 返回指标全部数据点的 (时间戳, 值)
+fn recorded(store: &HistoryStore) -> Vec<(u64, f64)> {
+    store
+        .query(CPU, None, None, None)
+        .unwrap()
+        .into_iter()
+        .map(|bucket| (bucket.timestamp, bucket.avg))
+        .collect()
+}
+
+#[test]
+fn evicts_oldest_points_at_capacity() {
+    let mut store = HistoryStore::new(1000, 3);
+    for (index, timestamp) in [0, 1000, 2000, 3000, 4000].into_iter().enumerate() {
+        store.record(timestamp, &with_cpu(index as f32));
+    }
+
+    assert_eq!(recorded(&store), [(2000, 2.0), (3000, 3.0), (4000, 4.0)]);
+    assert!(store.metrics().contains(&CPU.to_string()));
+    assert!(store.query("missing.metric", None, None, None).is_none());
+}
+
+#[test]
+fn skips_samples_closer_than_resolution() {
+    let mut store = HistoryStore::new(1000, 10);
+    store.record(0, &with_cpu(1.0));
+    store.record(500, &with_cpu(2.0));
+    // 间隔从上一次实际记录的样本起算
+    store.record(999, &with_cpu(3.0));
+    store.record(1000, &with_cpu(4.0));
+    store.record(1999, &with_cpu(5.0));
+    store.record(2500, &with_cpu(6.0));
+
+    assert_eq!(recorded(&store), [(0, 1.0), (1000, 4.0), (2500, 6.0)]);
+}
+
+#[test]
+fn configure_shrinks_buffers_and_applies_new_resolution() {
+    let mut store = HistoryStore::new(1000, 5);
+    for index in 0..5u64 {
+        store.record(index * 1000, &with_cpu(index as f32));
+    }
+
+    store.configure(100, 2);
+    assert_eq!(recorded(&store), [(3000, 3.0), (4000, 4.0)]);
+
+    // 新的分辨率和容量对之后的记录生效
+    store.record(4100, &with_cpu(5.0));
+    assert_eq!(recorded(&store), [(4000, 4.0), (4100, 5.0)]);
+
+    // 容量为 0 时按 1 处理
+    store.configure(100, 0);
+    assert_eq!(recorded(&store), [(4100, 5.0)]);
+}
+
+#[test]
+fn query_filters_by_time_range() {
+    let mut store = HistoryStore::new(0, 10);
+    for index in 0..5u64 {
+        store.record(index * 1000, &with_cpu(index as f32));
+    }
+
+    let timestamps: Vec<u64> = store
+        .query(CPU, Some(1000), Some(3000), None)
+        .unwrap()
+        .into_iter()
+        .map(|bucket| bucket.timestamp)
+        .collect();
+    assert_eq!(timestamps, [1000, 2000, 3000]);
+    assert!(store.query(CPU, Some(5000), None, None).unwrap().is_empty());
+}
+
+#[test]
+fn downsample_keeps_extremes_within_max_points() {
+    let values = [5.0, -3.0, 8.0, 1.0, 0.0, 12.0, 7.0, 4.0, -1.0, 6.0, 2.0];
+    let input: Vec<HistoryBucket> = values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| point(index as u64 * 100, value))
+        .collect();
+    let total: f64 = values.iter().sum();
+
+    for max_points in 1..=values.len() + 1 {
+        let output = downsample(input.clone(), max_points);
+        assert!(!output.is_empty());
+        assert!(output.len() <= max_points, "{}", max_points);
+
+        // 时间升序且样本数守恒
+        assert!(output.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        assert_eq!(
+            output.iter().map(|bucket| bucket.count).sum::<u32>(),
+            values.len() as u32
+        );
+
+        // 全局极值和加权总和不因降采样丢失
+        let min = output.iter().map(|b| b.min).fold(f64::INFINITY, f64::min);
+        let max = output
+            .iter()
+            .map(|b| b.max)
+            .fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!((min, max), (-3.0, 12.0), "{}", max_points);
+        let weighted: f64 = output.iter().map(|b| b.avg * b.count as f64).sum();
+        assert!((weighted - total).abs() < 1e-9, "{}", max_points);
+    }
+
+    // 不需要合并时原样返回；max_points 为 0 时按 1 处理
+    assert_eq!(downsample(input.clone(), values.len()).len(), values.len());
+    let single = downsample(input, 0);
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].timestamp, 0);
+}
+
+#[test]
+fn downsample_weights_average_by_count() {
+    let input = vec![
+        HistoryBucket {
+            timestamp: 0,
+            min: 5.0,
+            max: 15.0,
+            avg: 10.0,
+            count: 3,
+        },
+        HistoryBucket {
+            timestamp: 10,
+            min: 40.0,
+            max: 40.0,
+            avg: 40.0,
+            count: 1,
+        },
+    ];
+
+    let output = downsample(input, 1);
+    assert_eq!(output.len(), 1);
+    let bucket = &output[0];
+    assert_eq!(bucket.timestamp, 0);
+    assert_eq!(bucket.count, 4);
+    assert_eq!(bucket.min, 5.0);
+    assert_eq!(bucket.max, 40.0);
+    // (10 × 3 + 40 × 1) / 4，而不是两个平均值的简单平均 25
+    assert_eq!(bucket.avg, 17.5);
+}
@@ -0,0 +1,139 @@
+//! 使用录制的日志测试 MangoHud 日志解析和帧率采集
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use system_monitor_core::mangohud::{self, MangoHudColumns};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/mangohud")
+        .join(name)
+}
+
+fn read_fixture(name: &str) -> String {
+    fs::read_to_string(fixture(name)).unwrap()
+}
+
+/// 测试结束时删除的临时日志目录
+struct LogDir {
+    root: PathBuf,
+}
+
+impl LogDir {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "system-monitor-mangohud-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Self { root }
+    }
+
+    fn copy_fixture(&self, name: &str) -> PathBuf {
+        let path = self.root.join(name);
+        fs::copy(fixture(name), &path).unwrap();
+        path
+    }
+}
+
+impl Drop for LogDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+#[test]
+fn parses_current_log_format() {
+    let fps = mangohud::parse_log(&read_fixture("cyberpunk_2024-03-02_20-15-44.csv")).unwrap();
+    assert_eq!(fps, vec![62.5, 58.8, 66.7, 60.0]);
+}
+
+#[test]
+fn parses_legacy_log_and_skips_invalid_rows() {
+    // 旧版本直接从列头开始；NaN 和仍在写入的最后一行被跳过
+    let fps = mangohud::parse_log(&read_fixture("legacy_0.5.csv")).unwrap();
+    assert_eq!(fps, vec![144.0, 140.0, 136.0]);
+}
+
+#[test]
+fn rejects_files_without_frame_metrics() {
+    assert!(
+        mangohud::parse_log(&read_fixture("cyberpunk_2024-03-02_20-15-44_summary.csv")).is_none()
+    );
+    assert!(mangohud::parse_log("name,size\nreport.pdf,1024\n").is_none());
+    assert_eq!(
+        MangoHudColumns::from_header_line("os,cpu,gpu,ram,kernel,driver,cpuscheduler"),
+        None
+    );
+}
+
+#[test]
+fn latest_log_skips_summaries_and_unrelated_csv() {
+    let dir = LogDir::new("latest");
+    assert!(mangohud::latest_log(&dir.root).unwrap().is_none());
+
+    let log = dir.copy_fixture("cyberpunk_2024-03-02_20-15-44.csv");
+    std::thread::sleep(Duration::from_millis(20));
+    dir.copy_fixture("cyberpunk_2024-03-02_20-15-44_summary.csv");
+    fs::write(
+        dir.root.join("expenses.csv"),
+        "date,amount\n2024-03-01,12\n",
+    )
+    .unwrap();
+
+    let latest = mangohud::latest_log(&dir.root).unwrap().unwrap();
+    assert_eq!(latest.path, log);
+}
+
+#[test]
+fn read_appended_starts_at_a_complete_line() {
+    let dir = LogDir::new("appended");
+    let path = dir.root.join("game.csv");
+    fs::write(&path, "fps,frametime\n60,16.6\n59,16.9\n").unwrap();
+
+    // 从行首开始
+    assert_eq!(mangohud::read_appended(&path, 22).unwrap(), "59,16.9\n");
+    // 从行中间开始时跳过该行
+    assert_eq!(mangohud::read_appended(&path, 17).unwrap(), "59,16.9\n");
+    assert_eq!(mangohud::read_appended(&path, 30).unwrap(), "");
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn captures_frames_written_during_capture() {
+    use system_monitor_core::frame_monitor::FrameMonitor;
+    use system_monitor_core::models::{FrameCaptureConfig, FrameDataSource};
+
+    let dir = LogDir::new("capture");
+    let monitor = FrameMonitor::with_config(&FrameCaptureConfig {
+        mangohud_log_dir: Some(dir.root.to_string_lossy().into_owned()),
+    });
+    assert!(monitor.is_available());
+    assert!(monitor
+        .capture_frame_stats(Duration::from_millis(100))
+        .await
+        .is_err());
+
+    // 采集开始前已写入的帧不计入结果
+    let log = dir.copy_fixture("cyberpunk_2024-03-02_20-15-44.csv");
+    let writer = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut file = fs::OpenOptions::new().append(true).open(log).unwrap();
+        file.write_all(b"90.0,11.1,40\n110.0,9.1,40\n100").unwrap();
+    });
+
+    let stats = monitor
+        .capture_frame_stats(Duration::from_millis(400))
+        .await
+        .unwrap();
+    writer.await.unwrap();
+
+    assert_eq!(stats.sample_count, 2);
+    assert_eq!(stats.average_fps, 100.0);
+    assert_eq!(stats.duration_ms, 400);
+    assert!(matches!(stats.source, FrameDataSource::MangoHud));
+}
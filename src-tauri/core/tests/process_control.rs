@@ -0,0 +1,193 @@
+//! 进程操作确认令牌：有效期、一次性使用、与目标进程的绑定以及错误映射
+#![cfg(unix)]
+
+use std::io;
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use system_monitor_core::errors::MonitorError;
+use system_monitor_core::models::ProcessAction;
+use system_monitor_core::process_control::{
+    lookup_process, map_os_error, ProcessActionConfirmations,
+};
+
+/// 测试用子进程，析构时结束并回收
+struct TestProcess(Child);
+
+impl TestProcess {
+    fn spawn() -> Self {
+        Self(Command::new("sleep").arg("30").spawn().unwrap())
+    }
+
+    fn pid(&self) -> u32 {
+        self.0.id()
+    }
+
+    /// 等待进程退出，超时返回 false
+    fn exited_within(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if self.0.try_wait().unwrap().is_some() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+}
+
+impl Drop for TestProcess {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn is_invalid_confirmation(result: Result<(), MonitorError>) -> bool {
+    matches!(result, Err(MonitorError::InvalidConfirmation(_)))
+}
+
+#[test]
+fn expired_token_is_rejected() {
+    let process = TestProcess::spawn();
+    let confirmations = ProcessActionConfirmations::with_lookup(Duration::ZERO, lookup_process);
+
+    let request = confirmations
+        .request(process.pid(), ProcessAction::Resume)
+        .unwrap();
+    assert_eq!(request.expires_in_ms, 0);
+    assert!(is_invalid_confirmation(
+        confirmations.confirm(&request.token)
+    ));
+}
+
+#[test]
+fn default_token_lives_for_thirty_seconds() {
+    let process = TestProcess::spawn();
+    let confirmations = ProcessActionConfirmations::default();
+
+    let request = confirmations
+        .request(process.pid(), ProcessAction::Resume)
+        .unwrap();
+    assert_eq!(request.expires_in_ms, 30_000);
+    assert_eq!(request.pid, process.pid());
+    assert_eq!(request.process_name, "sleep");
+}
+
+#[test]
+fn token_is_single_use() {
+    let process = TestProcess::spawn();
+    let confirmations = ProcessActionConfirmations::default();
+
+    let request = confirmations
+        .request(process.pid(), ProcessAction::Resume)
+        .unwrap();
+    confirmations.confirm(&request.token).unwrap();
+    assert!(is_invalid_confirmation(
+        confirmations.confirm(&request.token)
+    ));
+    assert!(is_invalid_confirmation(confirmations.confirm("unknown")));
+}
+
+#[test]
+fn token_is_bound_to_its_process_and_action() {
+    let mut target = TestProcess::spawn();
+    let mut other = TestProcess::spawn();
+    let confirmations = ProcessActionConfirmations::default();
+
+    let kill = confirmations
+        .request(target.pid(), ProcessAction::Kill)
+        .unwrap();
+    let resume = confirmations
+        .request(other.pid(), ProcessAction::Resume)
+        .unwrap();
+
+    // 恢复令牌只作用于它申请时的进程，不会结束任何进程
+    confirmations.confirm(&resume.token).unwrap();
+    assert!(!other.exited_within(Duration::from_millis(100)));
+    assert!(!target.exited_within(Duration::from_millis(100)));
+
+    // 结束令牌只结束它申请时的进程
+    confirmations.confirm(&kill.token).unwrap();
+    assert!(target.exited_within(Duration::from_secs(5)));
+    assert!(!other.exited_within(Duration::from_millis(100)));
+}
+
+#[test]
+fn reused_pid_is_detected_by_start_time() {
+    let process = TestProcess::spawn();
+    let start_time_offset = Arc::new(AtomicU64::new(0));
+    let offset = start_time_offset.clone();
+    let confirmations =
+        ProcessActionConfirmations::with_lookup(Duration::from_secs(30), move |pid| {
+            let (name, start_time) = lookup_process(pid)?;
+            Ok((name, start_time + offset.load(Ordering::SeqCst)))
+        });
+
+    // 启动时间不变时正常执行
+    let request = confirmations
+        .request(process.pid(), ProcessAction::Resume)
+        .unwrap();
+    confirmations.confirm(&request.token).unwrap();
+
+    // 申请后同一 PID 换成了另一个进程
+    let request = confirmations
+        .request(process.pid(), ProcessAction::Resume)
+        .unwrap();
+    start_time_offset.store(1, Ordering::SeqCst);
+    assert!(matches!(
+        confirmations.confirm(&request.token),
+        Err(MonitorError::ProcessNotFound(pid)) if pid == process.pid()
+    ));
+}
+
+#[test]
+fn process_group_pids_are_rejected() {
+    let confirmations = ProcessActionConfirmations::with_lookup(Duration::from_secs(30), |_| {
+        Ok(("fake".to_string(), 0))
+    });
+
+    for pid in [0, i32::MAX as u32 + 1, u32::MAX] {
+        assert!(matches!(
+            confirmations.request(pid, ProcessAction::Kill),
+            Err(MonitorError::ProcessNotFound(_))
+        ));
+    }
+}
+
+#[test]
+fn renice_outside_range_is_rejected() {
+    let process = TestProcess::spawn();
+    let confirmations = ProcessActionConfirmations::default();
+
+    for nice in [-21, 20] {
+        let request = confirmations
+            .request(process.pid(), ProcessAction::Renice { nice })
+            .unwrap();
+        assert!(matches!(
+            confirmations.confirm(&request.token),
+            Err(MonitorError::ConfigError(_))
+        ));
+    }
+}
+
+#[test]
+fn maps_os_errors() {
+    assert!(matches!(
+        map_os_error(42, io::Error::from_raw_os_error(libc::ESRCH)),
+        MonitorError::ProcessNotFound(42)
+    ));
+    assert!(matches!(
+        map_os_error(42, io::Error::from_raw_os_error(libc::EPERM)),
+        MonitorError::PermissionDenied(_)
+    ));
+    assert!(matches!(
+        map_os_error(42, io::Error::from_raw_os_error(libc::EACCES)),
+        MonitorError::PermissionDenied(_)
+    ));
+    assert!(matches!(
+        map_os_error(42, io::Error::from_raw_os_error(libc::EINVAL)),
+        MonitorError::IoError(_)
+    ));
+}
@@ -0,0 +1,245 @@
+//! 使用合成的进程快照检查进程列表查询和进程树构建
+
+use std::sync::mpsc;
+use std::time::Duration;
+use system_monitor_core::models::{ProcessInfo, ProcessQuery, ProcessSortKey, ProcessTreeNode};
+use system_monitor_core::processes::ProcessCollector;
+
+fn process(pid: u32, parent_pid: Option<u32>, cpu_usage: f32, memory: u64) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        parent_pid,
+        name: format!("proc-{}", pid),
+        command: format!("/usr/bin/proc-{} --flag", pid),
+        user: None,
+        state: "Sleeping".to_string(),
+        cpu_usage,
+        memory,
+        virtual_memory: memory * 2,
+        disk_read_bytes: 0,
+        disk_written_bytes: 0,
+        start_time: 0,
+    }
+}
+
+/// 各字段取值互不相同，任一排序字段都能得到确定的顺序
+fn snapshot() -> Vec<ProcessInfo> {
+    let entry = |pid, name: &str, command: &str, user: Option<&str>, values: [u64; 6]| {
+        let [memory, virtual_memory, disk_read_bytes, disk_written_bytes, start_time, cpu] = values;
+        ProcessInfo {
+            name: name.to_string(),
+            command: command.to_string(),
+            user: user.map(str::to_string),
+            virtual_memory,
+            disk_read_bytes,
+            disk_written_bytes,
+            start_time,
+            ..process(pid, None, cpu as f32 / 10.0, memory)
+        }
+    };
+    vec![
+        entry(
+            3,
+            "Firefox",
+            "/usr/lib/firefox/firefox -P",
+            Some("alice"),
+            [500, 1000, 10, 70, 300, 300],
+        ),
+        entry(
+            1,
+            "systemd",
+            "/sbin/init",
+            Some("root"),
+            [50, 5000, 90, 5, 100, 5],
+        ),
+        entry(2, "bash", "-bash", Some("Alice"), [10, 20, 40, 30, 200, 50]),
+        entry(4, "kworker", "", None, [0, 0, 0, 0, 50, 120]),
+    ]
+}
+
+fn query_pids(query: ProcessQuery) -> Vec<u32> {
+    ProcessCollector::query(&snapshot(), &query)
+        .into_iter()
+        .map(|info| info.pid)
+        .collect()
+}
+
+fn find(nodes: &[ProcessTreeNode], pid: u32) -> Option<&ProcessTreeNode> {
+    nodes.iter().find_map(|node| {
+        if node.process.pid == pid {
+            Some(node)
+        } else {
+            find(&node.children, pid)
+        }
+    })
+}
+
+fn count(nodes: &[ProcessTreeNode]) -> usize {
+    nodes.iter().map(|node| 1 + count(&node.children)).sum()
+}
+
+fn pids(nodes: &[ProcessTreeNode]) -> Vec<u32> {
+    nodes.iter().map(|node| node.process.pid).collect()
+}
+
+/// 在独立线程中构建进程树，超时视为死循环
+fn build_tree_within(processes: Vec<ProcessInfo>, timeout: Duration) -> Vec<ProcessTreeNode> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(ProcessCollector::build_tree(&processes));
+    });
+    receiver.recv_timeout(timeout).expect("进程树构建未能结束")
+}
+
+#[test]
+fn sums_subtree_resources() {
+    let tree = ProcessCollector::build_tree(&[
+        process(1, None, 1.0, 100),
+        process(10, Some(1), 5.0, 200),
+        process(11, Some(10), 10.0, 50),
+        process(20, Some(1), 2.0, 300),
+        // 父进程已退出
+        process(30, Some(999), 3.0, 10),
+        // 父进程是自身
+        process(40, Some(40), 0.0, 20),
+    ]);
+
+    assert_eq!(count(&tree), 6);
+    // 根节点和子节点均按子树 CPU 使用率降序排列
+    assert_eq!(pids(&tree), [1, 30, 40]);
+
+    let init = find(&tree, 1).unwrap();
+    assert_eq!(init.subtree_cpu_usage, 18.0);
+    assert_eq!(init.subtree_memory, 650);
+    assert_eq!(init.subtree_process_count, 4);
+    assert_eq!(pids(&init.children), [10, 20]);
+
+    let child = find(&tree, 10).unwrap();
+    assert_eq!(child.subtree_cpu_usage, 15.0);
+    assert_eq!(child.subtree_memory, 250);
+    assert_eq!(child.subtree_process_count, 2);
+
+    let leaf = find(&tree, 11).unwrap();
+    assert_eq!(leaf.subtree_process_count, 1);
+    assert!(leaf.children.is_empty());
+
+    let orphan = find(&tree, 30).unwrap();
+    assert_eq!(orphan.subtree_memory, 10);
+    assert_eq!(orphan.subtree_process_count, 1);
+}
+
+#[test]
+fn parent_cycle_terminates_and_keeps_every_process() {
+    // 50 → 51 → 52 → 50 成环，53 挂在环上，60 是普通根节点
+    let tree = build_tree_within(
+        vec![
+            process(50, Some(52), 1.0, 100),
+            process(51, Some(50), 2.0, 200),
+            process(52, Some(51), 4.0, 400),
+            process(53, Some(51), 8.0, 800),
+            process(60, None, 0.5, 5),
+        ],
+        Duration::from_secs(5),
+    );
+
+    assert_eq!(count(&tree), 5);
+    assert_eq!(tree.len(), 2);
+
+    // 环上的进程从任意一个成员开始展开为一棵树，资源总和不受起点影响
+    let cycle = &tree[0];
+    assert!([50, 51, 52].contains(&cycle.process.pid));
+    assert_eq!(cycle.subtree_cpu_usage, 15.0);
+    assert_eq!(cycle.subtree_memory, 1500);
+    assert_eq!(cycle.subtree_process_count, 4);
+    assert_eq!(find(&tree, 53).unwrap().subtree_process_count, 1);
+
+    let root = &tree[1];
+    assert_eq!(root.process.pid, 60);
+    assert_eq!(root.subtree_process_count, 1);
+}
+
+#[test]
+fn empty_snapshot_builds_empty_tree() {
+    assert!(ProcessCollector::build_tree(&[]).is_empty());
+}
+
+#[test]
+fn sorts_by_every_key() {
+    for (sort_by, ascending) in [
+        (ProcessSortKey::Pid, [1, 2, 3, 4]),
+        // 名称比较不区分大小写
+        (ProcessSortKey::Name, [2, 3, 4, 1]),
+        // 没有用户的进程排在最前
+        (ProcessSortKey::User, [4, 2, 3, 1]),
+        (ProcessSortKey::Cpu, [1, 2, 4, 3]),
+        (ProcessSortKey::Memory, [4, 2, 1, 3]),
+        (ProcessSortKey::VirtualMemory, [4, 2, 3, 1]),
+        (ProcessSortKey::DiskRead, [4, 3, 2, 1]),
+        (ProcessSortKey::DiskWrite, [4, 1, 2, 3]),
+        (ProcessSortKey::StartTime, [4, 1, 2, 3]),
+    ] {
+        let query = ProcessQuery {
+            sort_by: sort_by.clone(),
+            descending: false,
+            ..Default::default()
+        };
+        assert_eq!(query_pids(query.clone()), ascending, "{:?}", sort_by);
+
+        let mut descending = ascending;
+        descending.reverse();
+        let query = ProcessQuery {
+            descending: true,
+            ..query
+        };
+        assert_eq!(query_pids(query), descending, "{:?}", sort_by);
+    }
+}
+
+#[test]
+fn filters_by_name_and_user() {
+    let by_name = |filter: &str| {
+        query_pids(ProcessQuery {
+            name_filter: Some(filter.to_string()),
+            ..Default::default()
+        })
+    };
+    // 不区分大小写，同时匹配进程名和命令行
+    assert_eq!(by_name("FIRE"), [3]);
+    assert_eq!(by_name("init"), [1]);
+    assert_eq!(by_name("bash"), [2]);
+    assert!(by_name("missing").is_empty());
+
+    // 没有用户的进程不会匹配用户过滤
+    assert_eq!(
+        query_pids(ProcessQuery {
+            user_filter: Some("ALICE".to_string()),
+            ..Default::default()
+        }),
+        [3, 2]
+    );
+    assert_eq!(
+        query_pids(ProcessQuery {
+            name_filter: Some("bash".to_string()),
+            user_filter: Some("alice".to_string()),
+            ..Default::default()
+        }),
+        [2]
+    );
+}
+
+#[test]
+fn limit_keeps_top_entries_after_filtering_and_sorting() {
+    let with_limit = |limit, user_filter: Option<&str>| {
+        query_pids(ProcessQuery {
+            limit: Some(limit),
+            user_filter: user_filter.map(str::to_string),
+            ..Default::default()
+        })
+    };
+    // 默认按 CPU 使用率降序
+    assert_eq!(with_limit(2, None), [3, 4]);
+    assert_eq!(with_limit(10, None), [3, 4, 2, 1]);
+    assert!(with_limit(0, None).is_empty());
+    assert_eq!(with_limit(1, Some("alice")), [3]);
+    assert_eq!(query_pids(ProcessQuery::default()).len(), 4);
+}
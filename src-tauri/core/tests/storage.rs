@@ -0,0 +1,232 @@
+//! 使用临时目录测试指标持久化存储的分段滚动、聚合和保留时长
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use system_monitor_core::models::{HistoryBucket, StorageConfig, StorageResolution, SystemInfo};
+use system_monitor_core::storage::MetricsStorage;
+
+const SECOND_MS: u64 = 1000;
+const MINUTE_MS: u64 = 60 * SECOND_MS;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// 测试结束时删除的存储目录
+struct StorageDir {
+    root: PathBuf,
+}
+
+impl StorageDir {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "system-monitor-storage-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        Self { root }
+    }
+
+    fn open(&self, config: StorageConfig) -> MetricsStorage {
+        MetricsStorage::new(self.root.clone(), config)
+    }
+
+    /// 某层级目录下的分段文件名（已排序）
+    fn segments(&self, tier: &str) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(self.root.join(tier))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    fn write_segment(&self, tier: &str, start_ms: u64) {
+        let dir = self.root.join(tier);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("{}.jsonl", start_ms / 1000)),
+            format!("{{\"t\":{},\"v\":{{}}}}\n", start_ms),
+        )
+        .unwrap();
+    }
+}
+
+impl Drop for StorageDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// 两小时前的整点，保证测试数据不会被默认保留时长清理
+fn hour_base() -> u64 {
+    let now = now_ms();
+    now - now % HOUR_MS - 2 * HOUR_MS
+}
+
+fn sample(cpu_usage: f32) -> Arc<SystemInfo> {
+    Arc::new(SystemInfo {
+        cpu_usage,
+        ..Default::default()
+    })
+}
+
+fn query(storage: &MetricsStorage, from: u64, resolution: StorageResolution) -> Vec<HistoryBucket> {
+    storage.query(
+        "cpu.usage",
+        from,
+        from + 2 * HOUR_MS,
+        Some(resolution),
+        None,
+    )
+}
+
+fn summary(buckets: &[HistoryBucket]) -> Vec<(u64, f64, f64, f64, u32)> {
+    buckets
+        .iter()
+        .map(|bucket| {
+            (
+                bucket.timestamp,
+                bucket.min,
+                bucket.max,
+                bucket.avg,
+                bucket.count,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn raw_segments_rotate_every_hour() {
+    let dir = StorageDir::new("rotate");
+    let base = hour_base();
+
+    let storage = dir.open(StorageConfig::default());
+    storage.record(base + 10 * SECOND_MS, sample(10.0));
+    storage.record(base + HOUR_MS + 10 * SECOND_MS, sample(20.0));
+    drop(storage);
+
+    assert_eq!(
+        dir.segments("raw"),
+        vec![
+            format!("{}.jsonl", base / 1000),
+            format!("{}.jsonl", (base + HOUR_MS) / 1000),
+        ]
+    );
+
+    let storage = dir.open(StorageConfig::default());
+    let raw = query(&storage, base, StorageResolution::Raw);
+    assert_eq!(
+        raw.iter().map(|b| (b.timestamp, b.avg)).collect::<Vec<_>>(),
+        vec![
+            (base + 10 * SECOND_MS, 10.0),
+            (base + HOUR_MS + 10 * SECOND_MS, 20.0)
+        ]
+    );
+}
+
+#[test]
+fn rollups_are_flushed_on_shutdown_and_merged_after_restart() {
+    let dir = StorageDir::new("rollup");
+    let base = hour_base();
+
+    let storage = dir.open(StorageConfig::default());
+    storage.record(base, sample(10.0));
+    storage.record(base + 30 * SECOND_MS, sample(30.0));
+    // 进入下一分钟，上一分钟的桶写入磁盘
+    storage.record(base + MINUTE_MS, sample(50.0));
+    storage.shutdown();
+    // 停止后的采样被忽略
+    storage.record(base + MINUTE_MS + 10 * SECOND_MS, sample(90.0));
+    drop(storage);
+
+    let storage = dir.open(StorageConfig::default());
+    assert_eq!(
+        summary(&query(&storage, base, StorageResolution::Minute)),
+        vec![
+            (base, 10.0, 30.0, 20.0, 2),
+            (base + MINUTE_MS, 50.0, 50.0, 50.0, 1)
+        ]
+    );
+    assert_eq!(
+        summary(&query(&storage, base, StorageResolution::Hour)),
+        vec![(base, 10.0, 50.0, 30.0, 3)]
+    );
+
+    // 重启后仍在同一分钟和同一小时内，查询时与退出前写入的桶合并
+    storage.record(base + MINUTE_MS + 30 * SECOND_MS, sample(70.0));
+    drop(storage);
+
+    let storage = dir.open(StorageConfig::default());
+    assert_eq!(
+        summary(&query(&storage, base, StorageResolution::Minute)),
+        vec![
+            (base, 10.0, 30.0, 20.0, 2),
+            (base + MINUTE_MS, 50.0, 70.0, 60.0, 2)
+        ]
+    );
+    assert_eq!(
+        summary(&query(&storage, base, StorageResolution::Hour)),
+        vec![(base, 10.0, 70.0, 40.0, 4)]
+    );
+    assert_eq!(query(&storage, base, StorageResolution::Raw).len(), 4);
+}
+
+#[test]
+fn expired_segments_are_removed_on_start_and_reconfigure() {
+    let dir = StorageDir::new("retention");
+    let base = hour_base();
+    let now = now_ms();
+
+    let expired_raw = base - 10 * HOUR_MS;
+    let expired_minute = now - now % DAY_MS - 3 * DAY_MS;
+    dir.write_segment("raw", expired_raw);
+    dir.write_segment("raw", base);
+    dir.write_segment("1m", expired_minute);
+    dir.write_segment("1m", now - now % DAY_MS);
+
+    let config = StorageConfig {
+        raw_retention_hours: 6,
+        minute_retention_days: 1,
+        ..Default::default()
+    };
+    let storage = dir.open(config.clone());
+    // 修改配置会在写入线程中立即清理，随后的释放等待写入线程退出
+    storage.configure(StorageConfig {
+        raw_retention_hours: 1,
+        ..config
+    });
+    drop(storage);
+
+    assert!(dir.segments("raw").is_empty());
+    assert_eq!(
+        dir.segments("1m"),
+        vec![format!("{}.jsonl", (now - now % DAY_MS) / 1000)]
+    );
+}
+
+#[test]
+fn disabled_storage_writes_nothing() {
+    let dir = StorageDir::new("disabled");
+    let storage = dir.open(StorageConfig {
+        enabled: false,
+        ..Default::default()
+    });
+    storage.record(hour_base(), sample(10.0));
+    drop(storage);
+
+    assert!(dir.segments("raw").is_empty());
+    assert!(dir.segments("1m").is_empty());
+}
@@ -2,14 +2,22 @@
 //!
 //! 负责应用的初始化、插件配置和应用状态管理
 
-use crate::models::*;
-use crate::monitor::SystemMonitor;
+use crate::sampler;
 use crate::store_commands;
 use crate::system_commands;
 use crate::tray;
 use crate::window;
-use log::info;
-use std::sync::Arc;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use system_monitor_core::alerts::AlertEngine;
+use system_monitor_core::exporter::{ExporterSource, MetricsExporter};
+use system_monitor_core::history::HistoryStore;
+use system_monitor_core::models::*;
+use system_monitor_core::process_control::ProcessActionConfirmations;
+use system_monitor_core::storage::MetricsStorage;
+use system_monitor_core::warnings::WarningMonitor;
+use system_monitor_core::SystemMonitor;
 use tauri::Manager;
 use tokio::sync::RwLock;
 
@@ -18,8 +26,20 @@ pub struct AppState {
     pub monitor: Arc<RwLock<SystemMonitor>>,
     /// 使用 Arc 共享数据，避免不必要的克隆
     pub current_data: Arc<RwLock<Option<Arc<SystemInfo>>>>,
-    /// 上一次的数据，用于计算增量更新
-    pub last_data: Arc<RwLock<Option<Arc<SystemInfo>>>>,
+    /// 后台采样任务最近一次推送的增量更新
+    pub latest_delta: Arc<RwLock<Option<SystemInfoDelta>>>,
+    /// 指标历史记录（由后台采样任务写入）
+    pub history: Arc<RwLock<HistoryStore>>,
+    /// 指标持久化存储（无法确定数据目录时为 None）
+    pub storage: Option<Arc<MetricsStorage>>,
+    /// 等待确认的进程控制操作
+    pub process_actions: ProcessActionConfirmations,
+    /// 阈值告警引擎（由后台采样任务评估）
+    pub alerts: Arc<RwLock<AlertEngine>>,
+    /// 系统警告检测器（高负载、温度、磁盘使用率）
+    pub warnings: Arc<RwLock<WarningMonitor>>,
+    /// Prometheus/OpenMetrics 导出服务（未启用时为 None）
+    pub exporter: Mutex<Option<MetricsExporter>>,
 }
 
 impl AppState {
@@ -27,15 +47,42 @@ impl AppState {
     ///
     /// # Arguments
     /// * `config` - 监控配置
+    /// * `data_dir` - 应用数据目录，用于持久化指标历史
+    /// * `alert_rules` - 已保存的告警规则
     ///
     /// # Returns
     /// * `AppState` - 新的应用状态实例
-    pub fn new(config: MonitorConfig) -> Self {
+    pub fn new(
+        config: MonitorConfig,
+        data_dir: Option<PathBuf>,
+        alert_rules: Vec<AlertRule>,
+    ) -> Self {
+        let history = HistoryStore::new(config.history_resolution_ms, config.history_capacity);
+        let storage = data_dir.map(|dir| {
+            Arc::new(MetricsStorage::new(
+                dir.join("metrics"),
+                config.storage.clone(),
+            ))
+        });
+        let warnings = WarningMonitor::new(config.notifications.clone());
         let monitor = SystemMonitor::new(config);
         Self {
             monitor: Arc::new(RwLock::new(monitor)),
             current_data: Arc::new(RwLock::new(None)),
-            last_data: Arc::new(RwLock::new(None)),
+            latest_delta: Arc::new(RwLock::new(None)),
+            history: Arc::new(RwLock::new(history)),
+            storage,
+            process_actions: ProcessActionConfirmations::default(),
+            alerts: Arc::new(RwLock::new(AlertEngine::new(alert_rules))),
+            warnings: Arc::new(RwLock::new(warnings)),
+            exporter: Mutex::new(None),
+        }
+    }
+
+    /// 导出服务读取数据的来源
+    pub fn exporter_source(&self) -> ExporterSource {
+        ExporterSource {
+            current_data: self.current_data.clone(),
         }
     }
 }
@@ -90,6 +137,10 @@ pub fn configure_plugins(mut builder: tauri::Builder<tauri::Wry>) -> tauri::Buil
     builder = builder.plugin(tauri_plugin_store::Builder::new().build());
     info!("Store插件已初始化");
 
+    // 集成通知插件，用于发送系统警告
+    builder = builder.plugin(tauri_plugin_notification::init());
+    info!("通知插件已初始化");
+
     // 集成自动启动插件
     #[cfg(desktop)]
     {
@@ -122,9 +173,32 @@ pub fn initialize_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Er
     info!("开始初始化应用...");
 
     // 创建应用状态
-    let app_state = AppState::new(MonitorConfig::default());
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .inspect_err(|e| warn!("无法获取应用数据目录，指标历史将不会持久化: {}", e))
+        .ok();
+    let alert_rules = store_commands::load_alert_rules(app.handle());
+    info!("已加载 {} 条告警规则", alert_rules.len());
+    let config = store_commands::load_monitor_config(app.handle());
+    let exporter_config = config.exporter.clone();
+    let app_state = AppState::new(config, data_dir, alert_rules);
     info!("应用状态创建成功");
 
+    // 按已保存的配置启动指标导出服务，启动失败不影响应用运行
+    match app_state.exporter.lock() {
+        Ok(mut exporter) => {
+            if let Err(e) = MetricsExporter::reconcile(
+                &mut exporter,
+                &exporter_config,
+                app_state.exporter_source(),
+            ) {
+                warn!("启动指标导出服务失败: {}", e);
+            }
+        }
+        Err(e) => warn!("启动指标导出服务失败: {}", e),
+    }
+
     // 创建系统托盘
     tray::create_tray(app.handle())?;
     info!("系统托盘初始化成功");
@@ -133,6 +207,9 @@ pub fn initialize_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Er
     app.manage(app_state);
     info!("应用状态管理成功");
 
+    // 启动后台采样任务，统一为所有窗口推送系统信息
+    sampler::spawn_sampler(app.handle().clone());
+
     info!("应用初始化完成");
     Ok(())
 }
@@ -154,14 +231,31 @@ pub fn run() {
             system_commands::get_gpu_monitor_status,
             system_commands::get_gpu_names,
             system_commands::get_detailed_gpu_info,
+            system_commands::get_gpu_processes,
             system_commands::get_frame_stats,
+            system_commands::get_processes,
+            system_commands::get_process_tree,
+            system_commands::terminate_process,
+            system_commands::kill_process,
+            system_commands::suspend_process,
+            system_commands::resume_process,
+            system_commands::renice_process,
+            system_commands::set_process_affinity,
+            system_commands::confirm_process_action,
             system_commands::get_current_data,
             system_commands::get_system_info_delta,
+            system_commands::get_monitor_config,
             system_commands::update_monitor_config,
-            system_commands::smart_refresh_system_info,
             system_commands::get_suggested_refresh_interval,
             system_commands::get_refresh_statistics,
             system_commands::reset_refresh_statistics,
+            system_commands::get_history,
+            system_commands::get_history_metrics,
+            system_commands::get_stored_history,
+            system_commands::get_alert_states,
+            system_commands::get_recent_warnings,
+            system_commands::get_exporter_status,
+            system_commands::get_collector_statuses,
             // 窗口管理相关命令
             window::toggle_window,
             window::show_settings_window,
@@ -175,8 +269,22 @@ pub fn run() {
             store_commands::delete_settings,
             store_commands::update_multiple_settings,
             store_commands::clear_all_settings,
+            store_commands::get_alert_rules,
+            store_commands::save_alert_rule,
+            store_commands::delete_alert_rule,
         ])
         .setup(|app| initialize_app(app))
-        .run(tauri::generate_context!())
-        .expect("运行 Tauri 应用时发生错误");
+        .build(tauri::generate_context!())
+        .expect("运行 Tauri 应用时发生错误")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                // 共享状态在退出时不会被释放，需要手动写入尚未结束的聚合数据
+                if let Some(storage) = app_handle
+                    .try_state::<AppState>()
+                    .and_then(|state| state.storage.clone())
+                {
+                    storage.shutdown();
+                }
+            }
+        });
 }
@@ -1,16 +1,12 @@
 //! 系统监控应用主入口模块
 //!
 //! 这是应用程序的主入口点，负责模块的声明和应用的启动。
+//! 系统信息采集逻辑位于 `system_monitor_core` crate，本 crate 只包含命令、托盘和窗口。
 
 // 声明所有模块
-mod adaptive_refresh;
 mod app;
-mod errors;
-mod frame_monitor;
-mod gpu_monitor;
-mod models;
-mod monitor;
-mod retry;
+mod notifications;
+mod sampler;
 mod store_commands;
 mod system_commands;
 mod tray;
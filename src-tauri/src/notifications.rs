@@ -0,0 +1,60 @@
+//! 系统警告通知模块
+//!
+//! 将核心库检测到的系统警告转换为系统通知、前端事件和托盘状态。
+
+use crate::tray;
+use crate::AppState;
+use log::{error, info, warn};
+use system_monitor_core::models::SystemInfo;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+/// 新警告推送事件名称
+pub const WARNING_EVENT: &str = "system-warning";
+
+/// 检测采样中的警告条件，并发送通知、更新托盘
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `state` - 应用状态
+/// * `timestamp` - 采样时间戳（毫秒）
+/// * `system_info` - 采样数据
+pub async fn process_sample(
+    app_handle: &AppHandle,
+    state: &AppState,
+    timestamp: u64,
+    system_info: &SystemInfo,
+) {
+    let (update, recent, has_active) = {
+        let mut warnings = state.warnings.write().await;
+        let update = warnings.evaluate(timestamp, system_info);
+        if !update.active_changed {
+            return;
+        }
+        (update, warnings.recent(), warnings.has_active())
+    };
+
+    for entry in &update.new_warnings {
+        warn!("系统警告: {}", entry.message);
+
+        if let Err(e) = app_handle
+            .notification()
+            .builder()
+            .title("系统监控警告")
+            .body(&entry.message)
+            .show()
+        {
+            error!("发送系统通知失败: {}", e);
+        }
+
+        if let Err(e) = app_handle.emit(WARNING_EVENT, entry) {
+            error!("推送系统警告事件失败: {}", e);
+        }
+    }
+
+    if let Err(e) = tray::update_warnings(app_handle, &recent, has_active) {
+        error!("更新托盘警告状态失败: {}", e);
+    } else if !has_active {
+        info!("所有系统警告已解除");
+    }
+}
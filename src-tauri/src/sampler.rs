@@ -0,0 +1,181 @@
+//! 后台采样模块
+//!
+//! 负责在后台按自适应刷新策略持续采集系统信息，并通过 Tauri 事件推送给所有窗口，
+//! 替代前端逐个窗口轮询 `get_system_info` 的方式。
+
+use crate::notifications;
+use crate::AppState;
+use log::{debug, error, info};
+use std::sync::Arc;
+use std::time::Duration;
+use system_monitor_core::alerts::ALERT_EVENT;
+use system_monitor_core::gpu_monitor::GPU_STATUS_EVENT;
+use system_monitor_core::models::*;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 系统信息推送事件名称
+pub const SYSTEM_INFO_EVENT: &str = "system-info";
+
+/// 每隔多少次增量推送发送一次完整数据，便于新打开的窗口重新同步
+const FULL_SYNC_EVERY: u64 = 30;
+
+/// 采样失败后的等待时间
+const ERROR_BACKOFF: Duration = Duration::from_secs(2);
+
+/// 启动后台采样任务
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄（需已注册 `AppState`）
+pub fn spawn_sampler(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("后台采样任务已启动");
+        run_sampler(app_handle).await;
+    });
+}
+
+/// 采样主循环
+async fn run_sampler(app_handle: AppHandle) {
+    let mut sample_count: u64 = 0;
+
+    loop {
+        let state = app_handle.state::<AppState>();
+
+        // 按自适应策略刷新，采样只需要读锁，不会阻塞同时到达的查询命令
+        let (result, next_interval) = {
+            let monitor = state.monitor.read().await;
+            let result = monitor.smart_refresh().await;
+            (result, monitor.suggested_refresh_interval())
+        };
+        poll_gpu_devices(&app_handle, &state).await;
+
+        match result {
+            Ok(Some(system_info)) => {
+                evaluate_alerts(&app_handle, &state, &system_info).await;
+                notifications::process_sample(&app_handle, &state, timestamp_ms(), &system_info)
+                    .await;
+                let delta = publish_sample(&state, system_info, sample_count).await;
+                sample_count += 1;
+
+                if let Err(e) = app_handle.emit(SYSTEM_INFO_EVENT, &delta) {
+                    error!("推送系统信息事件失败: {}", e);
+                }
+            }
+            Ok(None) => {
+                debug!("系统状态稳定，跳过本次采样");
+                // 用最近一次数据继续评估，保证等待中的告警和持续高负载能够达到持续时长
+                let current = state.current_data.read().await.clone();
+                if let Some(system_info) = current {
+                    evaluate_alerts(&app_handle, &state, &system_info).await;
+                    notifications::process_sample(
+                        &app_handle,
+                        &state,
+                        timestamp_ms(),
+                        &system_info,
+                    )
+                    .await;
+                }
+            }
+            Err(e) => {
+                error!("后台采样失败: {}", e);
+                tokio::time::sleep(ERROR_BACKOFF).await;
+                continue;
+            }
+        }
+
+        tokio::time::sleep(next_interval).await;
+    }
+}
+
+/// 到达探测时间时重新探测GPU，可用性或设备变化时推送事件
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `state` - 应用状态
+async fn poll_gpu_devices(app_handle: &AppHandle, state: &AppState) {
+    let change = state.monitor.read().await.poll_gpu_devices().await;
+    if let Some(change) = change {
+        info!(
+            "GPU状态变化: 可用 {} -> {}，新增 {} 个设备，移除 {} 个设备",
+            change.previously_available,
+            change.available,
+            change.added.len(),
+            change.removed.len()
+        );
+        if let Err(e) = app_handle.emit(GPU_STATUS_EVENT, &change) {
+            error!("推送GPU状态事件失败: {}", e);
+        }
+    }
+}
+
+/// 评估告警规则并推送状态变化事件
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `state` - 应用状态
+/// * `system_info` - 本次采样结果
+async fn evaluate_alerts(app_handle: &AppHandle, state: &AppState, system_info: &SystemInfo) {
+    let events = state
+        .alerts
+        .write()
+        .await
+        .evaluate(timestamp_ms(), system_info);
+
+    for event in events {
+        info!(
+            "告警 '{}' 状态变化: {:?} -> {:?} (值: {:?})",
+            event.rule_name, event.previous_state, event.state, event.value
+        );
+        if let Err(e) = app_handle.emit(ALERT_EVENT, &event) {
+            error!("推送告警事件失败: {}", e);
+        }
+    }
+}
+
+/// 写入最新采样结果并生成推送给前端的数据
+///
+/// # Arguments
+/// * `state` - 应用状态
+/// * `system_info` - 本次采样结果
+/// * `sample_count` - 已推送的样本数量
+///
+/// # Returns
+/// * `SystemInfoDelta` - 完整数据或相对上次采样的增量
+async fn publish_sample(
+    state: &AppState,
+    system_info: SystemInfo,
+    sample_count: u64,
+) -> SystemInfoDelta {
+    let system_info_arc = Arc::new(system_info);
+
+    let timestamp = timestamp_ms();
+    state
+        .history
+        .write()
+        .await
+        .record(timestamp, &system_info_arc);
+    if let Some(storage) = &state.storage {
+        storage.record(timestamp, system_info_arc.clone());
+    }
+
+    // 命令只读取缓存的样本，上一次推送的样本即为计算增量的基准
+    let mut current_data = state.current_data.write().await;
+    let delta = match current_data.as_ref() {
+        Some(last) if !sample_count.is_multiple_of(FULL_SYNC_EVERY) => {
+            SystemInfoDelta::from_diff(last, &system_info_arc)
+        }
+        _ => SystemInfoDelta::full((*system_info_arc).clone()),
+    };
+
+    *current_data = Some(system_info_arc);
+    *state.latest_delta.write().await = Some(delta.clone());
+
+    delta
+}
+
+/// 当前时间戳（毫秒）
+fn timestamp_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -2,12 +2,28 @@
 //!
 //! 负责处理应用设置和数据的持久化存储操作
 
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use serde_json::Value;
 use std::collections::HashMap;
-use tauri::AppHandle;
+use system_monitor_core::models::{AlertRule, MonitorConfig};
+use tauri::{AppHandle, State};
 use tauri_plugin_store::StoreExt;
 
+/// 界面设置的 Store 文件
+const SETTINGS_STORE: &str = "settings.json";
+
+/// 告警规则的 Store 文件，与界面设置分开保存，清空界面设置时不受影响
+const ALERT_RULES_STORE: &str = "alert_rules.json";
+
+/// 告警规则在 Store 中的键名（旧版本保存在界面设置中，读取时迁移）
+const ALERT_RULES_KEY: &str = "alert_rules";
+
+/// 监控配置的 Store 文件，与界面设置分开保存
+const MONITOR_CONFIG_STORE: &str = "monitor_config.json";
+
+/// 监控配置在 Store 中的键名
+const MONITOR_CONFIG_KEY: &str = "monitor_config";
+
 /// 保存设置到Store
 ///
 /// # Arguments
@@ -20,7 +36,7 @@ use tauri_plugin_store::StoreExt;
 #[tauri::command]
 pub async fn save_settings(app_handle: AppHandle, key: String, value: Value) -> Result<(), String> {
     // 获取或创建存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -49,7 +65,7 @@ pub async fn save_settings(app_handle: AppHandle, key: String, value: Value) ->
 #[tauri::command]
 pub async fn get_settings(app_handle: AppHandle, key: String) -> Result<Option<Value>, String> {
     // 获取存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -71,7 +87,7 @@ pub async fn get_settings(app_handle: AppHandle, key: String) -> Result<Option<V
 #[tauri::command]
 pub async fn get_all_settings(app_handle: AppHandle) -> Result<HashMap<String, Value>, String> {
     // 获取存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -100,7 +116,7 @@ pub async fn get_all_settings(app_handle: AppHandle) -> Result<HashMap<String, V
 #[tauri::command]
 pub async fn delete_settings(app_handle: AppHandle, key: String) -> Result<(), String> {
     // 获取存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -138,7 +154,7 @@ pub async fn update_multiple_settings(
     settings: HashMap<String, Value>,
 ) -> Result<(), String> {
     // 获取存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -168,7 +184,7 @@ pub async fn update_multiple_settings(
 #[tauri::command]
 pub async fn clear_all_settings(app_handle: AppHandle) -> Result<(), String> {
     // 获取存储实例
-    let store = app_handle.store("settings.json").map_err(|e| {
+    let store = app_handle.store(SETTINGS_STORE).map_err(|e| {
         error!("获取存储实例失败: {}", e);
         e.to_string()
     })?;
@@ -188,3 +204,190 @@ pub async fn clear_all_settings(app_handle: AppHandle) -> Result<(), String> {
     info!("所有设置已清空");
     Ok(())
 }
+
+/// 从Store读取已保存的告警规则（读取或解析失败时返回空列表）
+///
+/// 旧版本把告警规则保存在界面设置中，首次读取时迁移到单独的 Store 文件
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+///
+/// # Returns
+/// * `Vec<AlertRule>` - 告警规则列表
+pub fn load_alert_rules(app_handle: &AppHandle) -> Vec<AlertRule> {
+    let store = match app_handle.store(ALERT_RULES_STORE) {
+        Ok(store) => store,
+        Err(e) => {
+            error!("获取存储实例失败: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let value = match store.get(ALERT_RULES_KEY) {
+        Some(value) => value,
+        None => match migrate_alert_rules(app_handle) {
+            Some(value) => value,
+            None => return Vec::new(),
+        },
+    };
+    serde_json::from_value(value).unwrap_or_else(|e| {
+        warn!("解析告警规则失败，已忽略: {}", e);
+        Vec::new()
+    })
+}
+
+/// 将旧版本保存在界面设置中的告警规则移动到告警规则的 Store 文件
+///
+/// # Returns
+/// * `Option<Value>` - 迁移的告警规则，界面设置中没有时返回 None
+fn migrate_alert_rules(app_handle: &AppHandle) -> Option<Value> {
+    let settings = app_handle.store(SETTINGS_STORE).ok()?;
+    let value = settings.get(ALERT_RULES_KEY)?;
+
+    let store = app_handle.store(ALERT_RULES_STORE).ok()?;
+    store.set(ALERT_RULES_KEY, value.clone());
+    if let Err(e) = store.save() {
+        // 保留界面设置中的旧数据，下次启动时重试
+        warn!("迁移告警规则失败: {}", e);
+        return Some(value);
+    }
+
+    settings.delete(ALERT_RULES_KEY);
+    if let Err(e) = settings.save() {
+        warn!("从界面设置中移除告警规则失败: {}", e);
+    }
+    info!("告警规则已迁移到 {}", ALERT_RULES_STORE);
+    Some(value)
+}
+
+/// 将告警规则写入Store
+fn persist_alert_rules(app_handle: &AppHandle, rules: &[AlertRule]) -> Result<(), String> {
+    let store = app_handle.store(ALERT_RULES_STORE).map_err(|e| {
+        error!("获取存储实例失败: {}", e);
+        e.to_string()
+    })?;
+
+    let value = serde_json::to_value(rules).map_err(|e| e.to_string())?;
+    store.set(ALERT_RULES_KEY, value);
+
+    store.save().map_err(|e| {
+        error!("保存告警规则失败: {}", e);
+        e.to_string()
+    })
+}
+
+/// 从Store读取已保存的监控配置（不存在或解析失败时返回默认配置）
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+///
+/// # Returns
+/// * `MonitorConfig` - 监控配置
+pub fn load_monitor_config(app_handle: &AppHandle) -> MonitorConfig {
+    let store = match app_handle.store(MONITOR_CONFIG_STORE) {
+        Ok(store) => store,
+        Err(e) => {
+            error!("获取存储实例失败: {}", e);
+            return MonitorConfig::default();
+        }
+    };
+
+    match store.get(MONITOR_CONFIG_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("解析监控配置失败，使用默认配置: {}", e);
+            MonitorConfig::default()
+        }),
+        None => MonitorConfig::default(),
+    }
+}
+
+/// 将监控配置写入Store
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `config` - 监控配置
+///
+/// # Returns
+/// * `Result<(), String>` - 保存成功或错误信息
+pub fn persist_monitor_config(
+    app_handle: &AppHandle,
+    config: &MonitorConfig,
+) -> Result<(), String> {
+    let store = app_handle.store(MONITOR_CONFIG_STORE).map_err(|e| {
+        error!("获取存储实例失败: {}", e);
+        e.to_string()
+    })?;
+
+    let value = serde_json::to_value(config).map_err(|e| e.to_string())?;
+    store.set(MONITOR_CONFIG_KEY, value);
+
+    store.save().map_err(|e| {
+        error!("保存监控配置失败: {}", e);
+        e.to_string()
+    })
+}
+
+/// 获取所有告警规则
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<AlertRule>, String>` - 告警规则列表或错误信息
+#[tauri::command]
+pub async fn get_alert_rules(state: State<'_, crate::AppState>) -> Result<Vec<AlertRule>, String> {
+    Ok(state.alerts.read().await.rules())
+}
+
+/// 添加或更新告警规则（按ID匹配）并保存到Store
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `rule` - 告警规则
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<(), String>` - 保存成功或错误信息
+#[tauri::command]
+pub async fn save_alert_rule(
+    app_handle: AppHandle,
+    rule: AlertRule,
+    state: State<'_, crate::AppState>,
+) -> Result<(), String> {
+    let rule_id = rule.id.clone();
+    let mut alerts = state.alerts.write().await;
+    alerts.upsert_rule(rule).map_err(|e| {
+        error!("告警规则无效: {}", e);
+        e.to_string()
+    })?;
+
+    persist_alert_rules(&app_handle, &alerts.rules())?;
+    info!("告警规则 '{}' 已保存", rule_id);
+    Ok(())
+}
+
+/// 删除告警规则并保存到Store
+///
+/// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `id` - 规则ID
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<(), String>` - 删除成功或错误信息
+#[tauri::command]
+pub async fn delete_alert_rule(
+    app_handle: AppHandle,
+    id: String,
+    state: State<'_, crate::AppState>,
+) -> Result<(), String> {
+    let mut alerts = state.alerts.write().await;
+    if !alerts.remove_rule(&id) {
+        debug!("告警规则 '{}' 不存在，跳过删除", id);
+        return Ok(());
+    }
+
+    persist_alert_rules(&app_handle, &alerts.rules())?;
+    info!("告警规则 '{}' 已删除", id);
+    Ok(())
+}
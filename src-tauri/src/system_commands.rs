@@ -2,43 +2,34 @@
 //!
 //! 负责处理系统监控、GPU信息和智能刷新等系统相关命令
 
-use crate::adaptive_refresh;
-use crate::models::*;
+use crate::store_commands;
 use log::{debug, error, info, warn};
-use std::sync::Arc;
 use std::time::Duration;
-use tauri::State;
+use system_monitor_core::adaptive_refresh;
+use system_monitor_core::exporter::MetricsExporter;
+use system_monitor_core::models::*;
+use system_monitor_core::MonitorError;
+use tauri::{AppHandle, State};
 
-/// 获取系统信息（异步版本，提升性能，优化内存使用，增强错误处理）
+/// 获取系统信息
+///
+/// 返回后台采样任务最近一次的样本，不会触发采样，采样频率只由自适应刷新策略决定
 ///
 /// # Arguments
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<SystemInfo, String>` - 系统信息或错误信息
+/// * `Result<SystemInfo, String>` - 系统信息，尚未完成首次采样时返回错误
 #[tauri::command]
 pub async fn get_system_info(state: State<'_, crate::AppState>) -> Result<SystemInfo, String> {
-    // 异步刷新系统信息
-    let system_info = {
-        let mut monitor = state.monitor.write().await;
-        monitor
-            .refresh()
-            .await // 使用异步 refresh 方法
-            .map_err(|e| {
-                error!("刷新系统信息失败: {}", e);
-                e.to_string()
-            })? // 转换错误为String
-    };
-
-    // 优化内存使用：使用 Arc 共享数据，减少克隆
-    let system_info_arc = Arc::new(system_info.clone());
-    {
-        let mut current_data = state.current_data.write().await;
-        *current_data = Some(system_info_arc);
+    let current_data = state.current_data.read().await;
+    match current_data.as_ref() {
+        Some(system_info) => Ok((**system_info).clone()),
+        None => {
+            debug!("尚未完成首次采样");
+            Err("系统信息尚未采集完成".to_string())
+        }
     }
-
-    debug!("系统信息获取成功");
-    Ok(system_info)
 }
 
 /// 获取帧率信息（仅 Windows 支持 PresentMon）
@@ -60,12 +51,12 @@ pub async fn get_frame_stats(state: State<'_, crate::AppState>) -> Result<FrameS
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<Option<GpuInfo>, String>` - GPU信息或错误信息
+/// * `Result<Vec<GpuInfo>, String>` - 所有被监控GPU的信息或错误信息
 #[tauri::command]
-pub async fn get_gpu_info(state: State<'_, crate::AppState>) -> Result<Option<GpuInfo>, String> {
+pub async fn get_gpu_info(state: State<'_, crate::AppState>) -> Result<Vec<GpuInfo>, String> {
     let monitor = state.monitor.read().await;
     let gpu_info = monitor.get_gpu_info();
-    debug!("GPU信息获取: {:?}", gpu_info.is_some());
+    debug!("获取到 {} 个GPU的信息", gpu_info.len());
     Ok(gpu_info)
 }
 
@@ -75,15 +66,18 @@ pub async fn get_gpu_info(state: State<'_, crate::AppState>) -> Result<Option<Gp
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<(bool, Option<String>), String>` - GPU监控状态和相关信息
+/// * `Result<GpuMonitorStatus, String>` - GPU监控状态、当前设备和最近一次变化
 #[tauri::command]
 pub async fn get_gpu_monitor_status(
     state: State<'_, crate::AppState>,
-) -> Result<(bool, Option<String>), String> {
+) -> Result<GpuMonitorStatus, String> {
     let monitor = state.monitor.read().await;
-    let (enabled, error) = monitor.get_gpu_monitor_status();
-    debug!("GPU监控状态: 启用={}, 错误={:?}", enabled, error);
-    Ok((enabled, error))
+    let status = monitor.get_gpu_monitor_status();
+    debug!(
+        "GPU监控状态: 启用={}, 错误={:?}",
+        status.available, status.error
+    );
+    Ok(status)
 }
 
 /// 获取所有GPU名称
@@ -108,12 +102,12 @@ pub async fn get_gpu_names(state: State<'_, crate::AppState>) -> Result<Vec<Stri
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<String, String>` - 详细GPU信息或错误信息
+/// * `Result<GpuDetails, String>` - 详细GPU信息或错误信息
 #[tauri::command]
 pub async fn get_detailed_gpu_info(
     device_index: u32,
     state: State<'_, crate::AppState>,
-) -> Result<String, String> {
+) -> Result<GpuDetails, String> {
     let monitor = state.monitor.read().await;
     match monitor.get_detailed_gpu_info(device_index) {
         Ok(info) => {
@@ -127,6 +121,211 @@ pub async fn get_detailed_gpu_info(
     }
 }
 
+/// 获取使用指定GPU的进程（显存占用和引擎利用率）
+///
+/// # Arguments
+/// * `device_index` - GPU设备索引
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<GpuProcessInfo>, String>` - 按显存占用降序排列的进程或错误信息
+#[tauri::command]
+pub async fn get_gpu_processes(
+    device_index: u32,
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<GpuProcessInfo>, String> {
+    let monitor = state.monitor.read().await;
+    match monitor.get_gpu_processes(device_index).await {
+        Ok(processes) => {
+            debug!(
+                "获取GPU {} 进程成功，共 {} 个",
+                device_index,
+                processes.len()
+            );
+            Ok(processes)
+        }
+        Err(e) => {
+            error!("获取GPU {} 进程失败: {}", device_index, e);
+            Err(e)
+        }
+    }
+}
+
+/// 获取进程列表（支持排序、过滤和 Top-N）
+///
+/// # Arguments
+/// * `query` - 查询条件，未提供时按 CPU 使用率降序返回全部进程
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<ProcessInfo>, String>` - 进程列表或错误信息
+#[tauri::command]
+pub async fn get_processes(
+    query: Option<ProcessQuery>,
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<ProcessInfo>, String> {
+    let query = query.unwrap_or_default();
+    let monitor = state.monitor.read().await;
+    let processes = monitor.get_processes(&query).await;
+    debug!("获取到 {} 个进程", processes.len());
+    Ok(processes)
+}
+
+/// 获取进程树（每个节点包含自身及整个子树的 CPU 和内存占用）
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<ProcessTreeNode>, String>` - 进程树根节点列表或错误信息
+#[tauri::command]
+pub async fn get_process_tree(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<ProcessTreeNode>, String> {
+    let monitor = state.monitor.read().await;
+    let tree = monitor.get_process_tree().await;
+    debug!("获取进程树成功，共 {} 个根节点", tree.len());
+    Ok(tree)
+}
+
+/// 申请进程控制操作，返回确认令牌
+fn request_process_action(
+    state: &crate::AppState,
+    pid: u32,
+    action: ProcessAction,
+) -> Result<ProcessActionRequest, MonitorError> {
+    state
+        .process_actions
+        .request(pid, action)
+        .inspect(|request| info!("申请对进程 {} 执行 {:?}", request.pid, request.action))
+        .inspect_err(|e| error!("申请进程操作失败: {}", e))
+}
+
+/// 申请结束进程（SIGTERM），需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn terminate_process(
+    pid: u32,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::Terminate)
+}
+
+/// 申请强制结束进程（SIGKILL），需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn kill_process(
+    pid: u32,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::Kill)
+}
+
+/// 申请暂停进程（SIGSTOP），需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn suspend_process(
+    pid: u32,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::Suspend)
+}
+
+/// 申请恢复进程（SIGCONT），需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn resume_process(
+    pid: u32,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::Resume)
+}
+
+/// 申请调整进程 nice 值，需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `nice` - 新的 nice 值（-20 ~ 19）
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn renice_process(
+    pid: u32,
+    nice: i32,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::Renice { nice })
+}
+
+/// 申请设置进程 CPU 亲和性，需调用 `confirm_process_action` 确认后执行
+///
+/// # Arguments
+/// * `pid` - 目标进程ID
+/// * `cpus` - 允许运行的逻辑核心编号
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ProcessActionRequest, MonitorError>` - 确认令牌或错误信息
+#[tauri::command]
+pub async fn set_process_affinity(
+    pid: u32,
+    cpus: Vec<usize>,
+    state: State<'_, crate::AppState>,
+) -> Result<ProcessActionRequest, MonitorError> {
+    request_process_action(&state, pid, ProcessAction::SetAffinity { cpus })
+}
+
+/// 确认并执行进程控制操作
+///
+/// # Arguments
+/// * `token` - 申请操作时返回的确认令牌
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<(), MonitorError>` - 执行成功或错误信息
+#[tauri::command]
+pub async fn confirm_process_action(
+    token: String,
+    state: State<'_, crate::AppState>,
+) -> Result<(), MonitorError> {
+    match state.process_actions.confirm(&token) {
+        Ok(()) => {
+            info!("进程操作已执行");
+            Ok(())
+        }
+        Err(e) => {
+            error!("执行进程操作失败: {}", e);
+            Err(e)
+        }
+    }
+}
+
 /// 获取当前系统数据（优化内存使用）
 ///
 /// # Arguments
@@ -147,99 +346,92 @@ pub async fn get_current_data(
 
 /// 获取系统信息增量更新（减少网络传输）
 ///
+/// 返回后台采样任务最近一次推送的增量，与 `system-info` 事件的内容相同
+///
 /// # Arguments
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<SystemInfoDelta, String>` - 增量更新数据或错误信息
+/// * `Result<SystemInfoDelta, String>` - 增量更新数据，尚未完成首次采样时返回错误
 #[tauri::command]
 pub async fn get_system_info_delta(
     state: State<'_, crate::AppState>,
 ) -> Result<SystemInfoDelta, String> {
-    // 异步刷新系统信息
-    let system_info = {
-        let mut monitor = state.monitor.write().await;
-        monitor.refresh().await.map_err(|e| {
-            error!("刷新系统信息失败: {}", e);
-            e.to_string()
-        })?
-    };
-
-    // 优化内存使用：使用 Arc 共享数据
-    let system_info_arc = Arc::new(system_info.clone());
-
-    // 计算增量更新
-    let delta = {
-        let last_data = state.last_data.read().await;
-
-        if let Some(ref last) = *last_data {
-            // 有历史数据，计算增量
-            let delta = SystemInfoDelta::from_diff(last, &system_info);
-            debug!("计算增量更新成功");
-            delta
-        } else {
-            // 第一次获取，返回完整数据
-            info!("首次获取，返回完整数据");
-            SystemInfoDelta::full(system_info.clone())
-        }
-    };
-
-    // 更新状态
-    {
-        let mut current_data = state.current_data.write().await;
-        let mut last_data = state.last_data.write().await;
-
-        // 保存当前数据
-        *current_data = Some(system_info_arc.clone());
-
-        // 更新上一次的数据（用于下次计算增量）
-        *last_data = Some(system_info_arc);
-    }
-
-    Ok(delta)
+    state
+        .latest_delta
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| "系统信息尚未采集完成".to_string())
 }
 
-/// 更新监控配置
+/// 获取当前监控配置
+///
+/// 前端在此基础上修改后再调用 `update_monitor_config`，避免覆盖界面没有涉及的配置项
 ///
 /// # Arguments
-/// * `config` - 新的监控配置
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<(), String>` - 更新成功或错误信息
+/// * `Result<MonitorConfig, String>` - 当前监控配置
 #[tauri::command]
-pub async fn update_monitor_config(
-    config: MonitorConfig,
+pub async fn get_monitor_config(
     state: State<'_, crate::AppState>,
-) -> Result<(), String> {
-    let mut monitor = state.monitor.write().await;
-    monitor.update_config(config);
-    info!("监控配置更新成功");
-    Ok(())
+) -> Result<MonitorConfig, String> {
+    Ok(state.monitor.read().await.config().clone())
 }
 
-/// 智能刷新系统信息（包含自适应频率管理）
+/// 更新监控配置并保存到Store
 ///
 /// # Arguments
+/// * `app_handle` - Tauri 应用句柄
+/// * `config` - 新的监控配置（完整配置）
 /// * `state` - 应用状态
 ///
 /// # Returns
-/// * `Result<SystemInfo, String>` - 刷新后的系统信息或错误信息
+/// * `Result<(), String>` - 更新成功或错误信息
 #[tauri::command]
-pub async fn smart_refresh_system_info(
+pub async fn update_monitor_config(
+    app_handle: AppHandle,
+    config: MonitorConfig,
     state: State<'_, crate::AppState>,
-) -> Result<SystemInfo, String> {
-    let mut monitor = state.monitor.write().await;
-    match monitor.smart_refresh().await {
-        Ok(info) => {
-            debug!("智能刷新系统信息成功");
-            Ok(info)
-        }
-        Err(e) => {
-            error!("智能刷新系统信息失败: {}", e);
-            Err(e)
-        }
+) -> Result<(), String> {
+    state
+        .history
+        .write()
+        .await
+        .configure(config.history_resolution_ms, config.history_capacity);
+    if let Some(storage) = &state.storage {
+        storage.configure(config.storage.clone());
     }
+    state
+        .warnings
+        .write()
+        .await
+        .configure(config.notifications.clone());
+
+    let exporter_result = {
+        let mut exporter = state
+            .exporter
+            .lock()
+            .map_err(|e| MonitorError::mutex_error(e.to_string()).to_string())?;
+        MetricsExporter::reconcile(&mut exporter, &config.exporter, state.exporter_source())
+    };
+
+    // 保存失败不影响本次配置生效
+    let persist_result = store_commands::persist_monitor_config(&app_handle, &config);
+
+    let mut monitor = state.monitor.write().await;
+    monitor.update_config(config);
+
+    // 导出服务启动失败不影响其他配置生效
+    exporter_result.map_err(|e| {
+        error!("启动指标导出服务失败: {}", e);
+        e.to_string()
+    })?;
+    persist_result?;
+    info!("监控配置更新成功");
+    Ok(())
 }
 
 /// 获取建议的刷新间隔
@@ -285,7 +477,157 @@ pub async fn get_refresh_statistics(
 /// * `Result<(), String>` - 重置成功或错误信息
 #[tauri::command]
 pub async fn reset_refresh_statistics(state: State<'_, crate::AppState>) -> Result<(), String> {
-    let _monitor = state.monitor.write().await;
-    warn!("刷新统计信息重置功能暂未实现");
+    state.monitor.read().await.reset_refresh_statistics();
+    info!("刷新统计信息已重置");
     Ok(())
 }
+
+/// 查询指标历史（按时间分桶降采样）
+///
+/// # Arguments
+/// * `metric` - 指标名称（可通过 `get_history_metrics` 获取）
+/// * `from` - 起始时间戳（毫秒），为空表示不限
+/// * `to` - 结束时间戳（毫秒），为空表示不限
+/// * `max_points` - 返回的最大数据点数，为空表示不降采样
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<HistoryBucket>, String>` - 历史数据或错误信息
+#[tauri::command]
+pub async fn get_history(
+    metric: String,
+    from: Option<u64>,
+    to: Option<u64>,
+    max_points: Option<usize>,
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<HistoryBucket>, String> {
+    let history = state.history.read().await;
+    match history.query(&metric, from, to, max_points) {
+        Some(buckets) => {
+            debug!("查询指标 '{}' 历史成功，共 {} 个点", metric, buckets.len());
+            Ok(buckets)
+        }
+        None => {
+            warn!("指标 '{}' 没有历史记录", metric);
+            Err(format!("未知指标: {}", metric))
+        }
+    }
+}
+
+/// 获取所有已记录历史的指标名称
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<String>, String>` - 指标名称列表或错误信息
+#[tauri::command]
+pub async fn get_history_metrics(state: State<'_, crate::AppState>) -> Result<Vec<String>, String> {
+    let history = state.history.read().await;
+    Ok(history.metrics())
+}
+
+/// 查询持久化的指标历史（可跨越应用重启）
+///
+/// # Arguments
+/// * `metric` - 指标名称
+/// * `from` - 起始时间戳（毫秒）
+/// * `to` - 结束时间戳（毫秒）
+/// * `resolution` - 数据层级（raw/minute/hour），为空时自动选择
+/// * `max_points` - 返回的最大数据点数，为空表示不降采样
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<HistoryBucket>, String>` - 历史数据或错误信息
+#[tauri::command]
+pub async fn get_stored_history(
+    metric: String,
+    from: u64,
+    to: u64,
+    resolution: Option<StorageResolution>,
+    max_points: Option<usize>,
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<HistoryBucket>, String> {
+    let Some(storage) = state.storage.clone() else {
+        return Err("指标持久化存储不可用".to_string());
+    };
+
+    // 读取分段文件是阻塞操作，放到阻塞线程池执行
+    let buckets = tauri::async_runtime::spawn_blocking(move || {
+        storage.query(&metric, from, to, resolution, max_points)
+    })
+    .await
+    .map_err(|e| {
+        error!("查询持久化历史失败: {}", e);
+        e.to_string()
+    })?;
+
+    debug!("查询持久化历史成功，共 {} 个点", buckets.len());
+    Ok(buckets)
+}
+
+/// 获取所有告警规则的当前状态
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<AlertStatus>, String>` - 规则状态列表或错误信息
+#[tauri::command]
+pub async fn get_alert_states(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<AlertStatus>, String> {
+    Ok(state.alerts.read().await.statuses())
+}
+
+/// 获取最近的系统警告（最新的在前）
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<WarningEntry>, String>` - 警告列表或错误信息
+#[tauri::command]
+pub async fn get_recent_warnings(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<WarningEntry>, String> {
+    Ok(state.warnings.read().await.recent())
+}
+
+/// 获取 Prometheus/OpenMetrics 导出服务状态
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<ExporterStatus, String>` - 导出服务状态或错误信息
+#[tauri::command]
+pub async fn get_exporter_status(
+    state: State<'_, crate::AppState>,
+) -> Result<ExporterStatus, String> {
+    let exporter = state
+        .exporter
+        .lock()
+        .map_err(|e| MonitorError::mutex_error(e.to_string()).to_string())?;
+    let address = exporter
+        .as_ref()
+        .map(|exporter| exporter.address().to_string());
+    Ok(ExporterStatus {
+        running: address.is_some(),
+        address,
+    })
+}
+
+/// 获取所有指标采集器的运行状态
+///
+/// # Arguments
+/// * `state` - 应用状态
+///
+/// # Returns
+/// * `Result<Vec<CollectorStatus>, String>` - 采集器状态列表或错误信息
+#[tauri::command]
+pub async fn get_collector_statuses(
+    state: State<'_, crate::AppState>,
+) -> Result<Vec<CollectorStatus>, String> {
+    Ok(state.monitor.read().await.get_collector_statuses())
+}
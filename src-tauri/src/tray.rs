@@ -3,13 +3,25 @@
 //! 负责创建和管理系统托盘图标、菜单以及相关事件处理
 
 use crate::window::{show_settings_window, toggle_window_internal};
-use log::{debug, error, info};
+use crate::AppState;
+use log::{debug, error, info, warn};
+use system_monitor_core::models::WarningEntry;
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
+    image::Image,
+    menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle,
+    AppHandle, Emitter, Manager,
 };
 
+/// 托盘图标ID
+const TRAY_ID: &str = "main";
+
+/// 最近警告菜单项ID前缀，后接警告ID
+const WARNING_ITEM_PREFIX: &str = "warning:";
+
+/// 定位到指定指标的事件名称（发送给主窗口，负载为指标名称）
+pub const FOCUS_METRIC_EVENT: &str = "focus-metric";
+
 /// 创建并初始化系统托盘
 ///
 /// # Arguments
@@ -18,18 +30,11 @@ use tauri::{
 /// # Returns
 /// * `Result<(), Box<dyn std::error::Error>>` - 创建成功或错误信息
 pub fn create_tray(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    // 创建托盘菜单项
-    let show_item = MenuItemBuilder::with_id("show", "显示/隐藏").build(app)?;
-    let settings_item = MenuItemBuilder::with_id("settings", "设置").build(app)?;
-    let quit_item = MenuItemBuilder::with_id("quit", "退出").build(app)?;
-
     // 构建托盘菜单
-    let menu = MenuBuilder::new(app)
-        .items(&[&show_item, &settings_item, &quit_item])
-        .build()?;
+    let menu = build_tray_menu(app, &[])?;
 
     // 创建托盘图标并配置事件处理
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
         .tooltip("系统监控")
@@ -45,6 +50,145 @@ pub fn create_tray(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+/// 构建托盘菜单
+///
+/// # Arguments
+/// * `app` - Tauri 应用句柄
+/// * `warnings` - 最近的警告（最新的在前）
+///
+/// # Returns
+/// * `tauri::Result<Menu<tauri::Wry>>` - 托盘菜单
+fn build_tray_menu(app: &AppHandle, warnings: &[WarningEntry]) -> tauri::Result<Menu<tauri::Wry>> {
+    // 创建托盘菜单项
+    let show_item = MenuItemBuilder::with_id("show", "显示/隐藏").build(app)?;
+    let settings_item = MenuItemBuilder::with_id("settings", "设置").build(app)?;
+    let quit_item = MenuItemBuilder::with_id("quit", "退出").build(app)?;
+
+    // 最近警告子菜单，每项显示触发时间和警告内容
+    let mut warnings_menu = SubmenuBuilder::with_id(app, "recent_warnings", "最近警告");
+    if warnings.is_empty() {
+        let empty_item = MenuItemBuilder::with_id("no_warnings", "暂无警告")
+            .enabled(false)
+            .build(app)?;
+        warnings_menu = warnings_menu.item(&empty_item);
+    }
+    for entry in warnings {
+        let time = chrono::DateTime::from_timestamp_millis(entry.timestamp as i64)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let item = MenuItemBuilder::with_id(
+            format!("{}{}", WARNING_ITEM_PREFIX, entry.id),
+            format!("[{}] {}", time, entry.message),
+        )
+        .build(app)?;
+        warnings_menu = warnings_menu.item(&item);
+    }
+    let warnings_menu = warnings_menu.build()?;
+
+    MenuBuilder::new(app)
+        .items(&[&show_item, &warnings_menu, &settings_item, &quit_item])
+        .build()
+}
+
+/// 更新托盘的警告状态：刷新最近警告菜单，并在存在未解除的警告时显示带标记的图标
+///
+/// # Arguments
+/// * `app` - Tauri 应用句柄
+/// * `warnings` - 最近的警告（最新的在前）
+/// * `has_active` - 是否存在未解除的警告
+///
+/// # Returns
+/// * `tauri::Result<()>` - 更新成功或错误信息
+pub fn update_warnings(
+    app: &AppHandle,
+    warnings: &[WarningEntry],
+    has_active: bool,
+) -> tauri::Result<()> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+
+    tray.set_menu(Some(build_tray_menu(app, warnings)?))?;
+
+    let default_icon = app.default_window_icon().cloned();
+    if has_active {
+        tray.set_icon(default_icon.map(|icon| warning_icon(&icon)))?;
+        tray.set_tooltip(Some("系统监控 - 存在未解除的警告"))?;
+    } else {
+        tray.set_icon(default_icon)?;
+        tray.set_tooltip(Some("系统监控"))?;
+    }
+    Ok(())
+}
+
+/// 在图标右下角绘制红色圆点，作为警告标记
+fn warning_icon(icon: &Image<'_>) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+
+    let radius = (width.min(height) as f32) * 0.22;
+    let center_x = width as f32 - radius - 1.0;
+    let center_y = height as f32 - radius - 1.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            if dx * dx + dy * dy <= radius * radius {
+                let offset = ((y * width + x) * 4) as usize;
+                if let Some(pixel) = rgba.get_mut(offset..offset + 4) {
+                    pixel.copy_from_slice(&[0xE5, 0x39, 0x35, 0xFF]);
+                }
+            }
+        }
+    }
+
+    Image::new(&rgba, width, height).to_owned()
+}
+
+/// 打开主窗口并定位到警告相关的指标
+///
+/// # Arguments
+/// * `app` - Tauri 应用句柄
+/// * `warning_id` - 警告ID
+fn open_warning(app: &AppHandle, warning_id: u64) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let Some(entry) = state.warnings.read().await.find(warning_id) else {
+            warn!("警告 {} 已不在最近列表中", warning_id);
+            return;
+        };
+
+        // 主窗口已显示时只需聚焦，避免 toggle 将其隐藏
+        let visible = app_handle
+            .get_webview_window("main")
+            .and_then(|window| window.is_visible().ok())
+            .unwrap_or(false);
+        let result = if visible {
+            app_handle
+                .get_webview_window("main")
+                .map(|window| window.set_focus().map_err(|e| e.to_string()))
+                .unwrap_or(Ok(()))
+        } else {
+            toggle_window_internal(&app_handle)
+        };
+        if let Err(e) = result {
+            error!("打开主窗口失败: {}", e);
+            return;
+        }
+
+        match app_handle.emit_to("main", FOCUS_METRIC_EVENT, &entry.metric) {
+            Ok(_) => debug!("已定位到指标 '{}'", entry.metric),
+            Err(e) => error!("推送指标定位事件失败: {}", e),
+        }
+    });
+}
+
 /// 处理托盘菜单事件
 ///
 /// # Arguments
@@ -73,7 +217,14 @@ fn handle_tray_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
             info!("通过托盘菜单退出应用");
             app.exit(0);
         }
-        _ => {}
+        id => {
+            if let Some(warning_id) = id
+                .strip_prefix(WARNING_ITEM_PREFIX)
+                .and_then(|id| id.parse::<u64>().ok())
+            {
+                open_warning(app, warning_id);
+            }
+        }
     }
 }
 
@@ -35,16 +35,12 @@ export function useSystemMonitor(autoStart = true, interval = 1000) {
     await systemStore.fetchFrameStats()
   }
 
-  // 开始轮询
+  // 开始轮询（系统信息和GPU信息由后台采样任务推送，这里只定期采集帧率）
   const startPolling = () => {
     if (isPolling.value) return
 
     isPolling.value = true
     pollInterval = window.setInterval(async () => {
-      await systemStore.fetchSystemInfo()
-      if (Math.random() < 0.2) {
-        await systemStore.fetchGpuInfo()
-      }
       if (settings.value.enableFrameStats) {
         await maybeFetchFrameStats()
       }
@@ -6,4 +6,4 @@ export default pinia
 
 // 导出所有store
 export { useSystemStore } from './system'
-export type { SystemInfo, MemoryInfo, NetworkInfo, DiskInfo, SystemDetails, TemperatureInfo, GpuInfo, GpuMemoryInfo, MonitorConfig } from './system'
\ No newline at end of file
+export type { SystemInfo, MemoryInfo, NetworkInfo, DiskInfo, SystemDetails, TemperatureInfo, GpuInfo, GpuMemoryInfo, GpuMetric, MetricUnavailableReason, MonitorConfig } from './system'
\ No newline at end of file
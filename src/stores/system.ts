@@ -1,15 +1,40 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
 // 系统信息接口定义
 export interface SystemInfo {
   cpu_usage: number
+  cpus: CpuCoreInfo[]
+  cpu_times: CpuTimeBreakdown | null
   memory: MemoryInfo
   network: NetworkInfo
   disk: DiskInfo
+  block_devices: BlockDeviceStats[]
   system: SystemDetails
   temperatures: TemperatureInfo[]
+  gpus: GpuInfo[]
+  custom_metrics: CustomMetric[]
+}
+
+export interface CpuCoreInfo {
+  name: string
+  usage_percent: number
+  frequency: number
+  times: CpuTimeBreakdown | null
+}
+
+// CPU时间分解（百分比，仅 Linux 可用）
+export interface CpuTimeBreakdown {
+  user: number
+  nice: number
+  system: number
+  idle: number
+  iowait: number
+  irq: number
+  softirq: number
+  steal: number
 }
 
 export interface MemoryInfo {
@@ -49,6 +74,17 @@ export interface Disk {
   usage_percent: number
 }
 
+// 块设备IO统计（仅 Linux 可用）
+export interface BlockDeviceStats {
+  name: string
+  read_bytes_per_sec: number
+  write_bytes_per_sec: number
+  read_iops: number
+  write_iops: number
+  avg_queue_depth: number
+  utilization_percent: number
+}
+
 export interface SystemDetails {
   name?: string
   kernel_version?: string
@@ -78,20 +114,156 @@ export interface TemperatureInfo {
 }
 
 export interface GpuInfo {
+  index: number
   name: string
-  usage_percent: number
-  memory: GpuMemoryInfo
+  uuid?: string
+  pci_bus_id?: string
+  usage_percent?: number
+  memory?: GpuMemoryInfo
   temperature?: number
   frequency?: number
+  memory_frequency?: number
+  power_draw_watts?: number
+  power_limit_watts?: number
+  fan_speed_percent?: number
+  performance_state?: number
+  throttle_reasons?: GpuThrottleReason[]
+  pcie_rx_bytes_per_sec?: number
+  pcie_tx_bytes_per_sec?: number
+  unavailable: Partial<Record<GpuMetric, MetricUnavailableReason>>
 }
 
+export type GpuMetric =
+  | 'usage'
+  | 'memory'
+  | 'temperature'
+  | 'frequency'
+  | 'memory_frequency'
+  | 'power_draw'
+  | 'power_limit'
+  | 'fan_speed'
+  | 'performance_state'
+  | 'throttle_reasons'
+  | 'pcie_throughput'
+
+export type MetricUnavailableReason = 'not_supported' | 'permission_denied' | 'device_lost' | 'read_failed'
+
 export interface GpuMemoryInfo {
   total: number
   used: number
   usage_percent: number
 }
 
-export type FrameDataSource = 'present_mon' | 'unsupported' | 'missing_dependency'
+export type GpuClockDomain = 'graphics' | 'sm' | 'memory' | 'video'
+
+export interface GpuClockInfo {
+  domain: GpuClockDomain
+  current_mhz?: number
+  max_mhz?: number
+}
+
+export type GpuThrottleReason =
+  | 'gpu_idle'
+  | 'applications_clocks_setting'
+  | 'sw_power_cap'
+  | 'hw_slowdown'
+  | 'sync_boost'
+  | 'sw_thermal_slowdown'
+  | 'hw_thermal_slowdown'
+  | 'hw_power_brake_slowdown'
+  | 'display_clock_setting'
+
+export interface GpuEccErrors {
+  corrected: number
+  uncorrected: number
+}
+
+export interface GpuDetails {
+  index: number
+  name: string
+  uuid?: string
+  pci_bus_id?: string
+  driver_version?: string
+  vbios_version?: string
+  pcie_generation?: number
+  pcie_width?: number
+  gpu_utilization?: number
+  memory_utilization?: number
+  memory_total?: number
+  memory_used?: number
+  temperature?: number
+  power_draw_watts?: number
+  power_limit_watts?: number
+  fan_speed_percent?: number
+  clocks: GpuClockInfo[]
+  throttle_reasons: GpuThrottleReason[]
+  ecc_errors?: GpuEccErrors
+  encoder_utilization?: number
+  decoder_utilization?: number
+}
+
+export interface GpuProcessInfo {
+  pid: number
+  name?: string
+  used_memory?: number
+  sm_utilization?: number
+  encoder_utilization?: number
+  decoder_utilization?: number
+}
+
+export interface GpuDeviceId {
+  name: string
+  uuid?: string
+  pci_bus_id?: string
+}
+
+export interface GpuStatusChange {
+  previously_available: boolean
+  available: boolean
+  added: GpuDeviceId[]
+  removed: GpuDeviceId[]
+  timestamp: number
+}
+
+export interface GpuMonitorStatus {
+  available: boolean
+  error?: string
+  devices: GpuDeviceId[]
+  next_probe_in_ms?: number
+  last_change?: GpuStatusChange
+}
+
+// 后台采样任务推送的系统信息，未变化的部分为 null
+// 自定义采集器提供的指标
+export interface CustomMetric {
+  name: string
+  value: number
+  unit: string | null
+}
+
+export interface SystemInfoDelta {
+  timestamp: number
+  cpu_usage: number | null
+  cpus: CpuCoreInfo[] | null
+  cpu_times: CpuTimeBreakdown | null
+  memory: MemoryInfo | null
+  network: NetworkInfo | null
+  disk: DiskInfo | null
+  block_devices: BlockDeviceStats[] | null
+  system: SystemDetails | null
+  temperatures: TemperatureInfo[] | null
+  gpus: GpuInfo[] | null
+  custom_metrics: CustomMetric[] | null
+  full_data: SystemInfo | null
+}
+
+// 后台采样任务推送系统信息的事件
+export const SYSTEM_INFO_EVENT = 'system-info'
+
+// GPU可用性或设备变化时后台推送的事件
+export const GPU_STATUS_EVENT = 'gpu-status-changed'
+
+export type FrameDataSource = 'present_mon' | 'mango_hud' | 'unsupported' | 'missing_dependency'
 
 export interface FrameStats {
   average_fps: number
@@ -130,6 +302,47 @@ export type RefreshStrategyType =
   | AdaptiveRefreshStrategy
   | PowerSavingRefreshStrategy
 
+// 各子系统的采集间隔（毫秒）
+export interface CollectorIntervals {
+  cpu_ms: number
+  memory_ms: number
+  network_ms: number
+  disk_ms: number
+  disk_io_ms: number
+  temperature_ms: number
+  gpu_ms: number
+  processes_ms: number
+  // 为空时仅在启动时采集一次
+  system_details_ms: number | null
+}
+
+export interface StorageConfig {
+  enabled: boolean
+  raw_retention_hours: number
+  minute_retention_days: number
+  hour_retention_days: number
+}
+
+export interface NotificationConfig {
+  enabled: boolean
+  cpu_threshold: number
+  memory_threshold: number
+  high_load_duration_secs: number
+  temperature_margin: number
+  temperature_threshold: number
+  disk_usage_threshold: number
+}
+
+export interface ExporterConfig {
+  enabled: boolean
+  listen_address: string
+}
+
+export interface FrameCaptureConfig {
+  // MangoHud 日志目录，为空时使用主目录
+  mangohud_log_dir: string | null
+}
+
 export interface MonitorConfig {
   refresh_interval: number
   enable_cpu: boolean
@@ -138,7 +351,16 @@ export interface MonitorConfig {
   enable_disk: boolean
   enable_temperature: boolean
   enable_gpu: boolean
+  gpu_devices: string[]
+  enable_processes: boolean
+  history_resolution_ms: number
+  history_capacity: number
+  intervals: CollectorIntervals
+  storage: StorageConfig
+  notifications: NotificationConfig
+  exporter: ExporterConfig
   refresh_strategy: RefreshStrategyType
+  frame_capture: FrameCaptureConfig
 }
 
 const createDefaultRefreshStrategy = (): RefreshStrategyType => ({
@@ -159,19 +381,61 @@ const createDefaultMonitorConfig = (): MonitorConfig => ({
   enable_disk: true,
   enable_temperature: true,
   enable_gpu: true,
-  refresh_strategy: createDefaultRefreshStrategy()
+  gpu_devices: [],
+  enable_processes: true,
+  history_resolution_ms: 1000,
+  history_capacity: 3600,
+  intervals: {
+    cpu_ms: 500,
+    memory_ms: 1000,
+    network_ms: 1000,
+    disk_ms: 10000,
+    disk_io_ms: 1000,
+    temperature_ms: 2000,
+    gpu_ms: 1000,
+    processes_ms: 2000,
+    system_details_ms: null
+  },
+  storage: {
+    enabled: true,
+    raw_retention_hours: 6,
+    minute_retention_days: 30,
+    hour_retention_days: 365
+  },
+  notifications: {
+    enabled: true,
+    cpu_threshold: 80,
+    memory_threshold: 85,
+    high_load_duration_secs: 60,
+    temperature_margin: 5,
+    temperature_threshold: 90,
+    disk_usage_threshold: 90
+  },
+  exporter: {
+    enabled: false,
+    listen_address: '127.0.0.1:9184'
+  },
+  refresh_strategy: createDefaultRefreshStrategy(),
+  frame_capture: {
+    mangohud_log_dir: null
+  }
 })
 
 export const useSystemStore = defineStore('system', () => {
   // 状态
   const systemInfo = ref<SystemInfo | null>(null)
-  const gpuInfo = ref<GpuInfo | null>(null)
+  const gpus = ref<GpuInfo[]>([])
+  // 第一个被监控的GPU，供只展示单个GPU的视图使用
+  const gpuInfo = computed<GpuInfo | null>(() => gpus.value[0] ?? null)
   const isMonitoring = ref(false)
   const lastUpdate = ref<Date | null>(null)
   const config = ref<MonitorConfig>(createDefaultMonitorConfig())
   const error = ref<string | null>(null)
   const frameStats = ref<FrameStats | null>(null)
   const frameError = ref<string | null>(null)
+  const gpuStatus = ref<GpuMonitorStatus | null>(null)
+  let systemInfoUnlisten: UnlistenFn | null = null
+  let gpuStatusUnlisten: UnlistenFn | null = null
 
   // 计算属性
   const memoryUsageText = computed(() => {
@@ -241,13 +505,39 @@ export const useSystemStore = defineStore('system', () => {
 
   const fetchGpuInfo = async () => {
     try {
-      const info = await invoke<GpuInfo | null>('get_gpu_info')
-      gpuInfo.value = info
+      gpus.value = await invoke<GpuInfo[]>('get_gpu_info')
     } catch (err) {
       console.error('获取GPU信息失败:', err)
     }
   }
 
+  // 合并后台采样任务推送的数据
+  const applySystemInfoDelta = (delta: SystemInfoDelta) => {
+    if (delta.full_data) {
+      systemInfo.value = delta.full_data
+      gpus.value = delta.full_data.gpus
+    } else if (systemInfo.value) {
+      const info = { ...systemInfo.value }
+      if (delta.cpu_usage !== null) info.cpu_usage = delta.cpu_usage
+      if (delta.cpus) info.cpus = delta.cpus
+      if (delta.cpu_times) info.cpu_times = delta.cpu_times
+      if (delta.memory) info.memory = delta.memory
+      if (delta.network) info.network = delta.network
+      if (delta.disk) info.disk = delta.disk
+      if (delta.block_devices) info.block_devices = delta.block_devices
+      if (delta.system) info.system = delta.system
+      if (delta.temperatures) info.temperatures = delta.temperatures
+      if (delta.custom_metrics) info.custom_metrics = delta.custom_metrics
+      systemInfo.value = info
+      if (delta.gpus) gpus.value = delta.gpus
+    } else {
+      // 还没有基准数据，等待下一次完整同步
+      return
+    }
+    error.value = null
+    lastUpdate.value = new Date(delta.timestamp)
+  }
+
   const getCurrentData = async () => {
     try {
       const data = await invoke<SystemInfo | null>('get_current_data')
@@ -260,9 +550,20 @@ export const useSystemStore = defineStore('system', () => {
     }
   }
 
+  // 读取后台当前的监控配置
+  const fetchConfig = async () => {
+    try {
+      config.value = await invoke<MonitorConfig>('get_monitor_config')
+    } catch (err) {
+      console.error('获取监控配置失败:', err)
+    }
+  }
+
   const updateConfig = async (newConfig: Partial<MonitorConfig>) => {
     try {
-      config.value = { ...config.value, ...newConfig }
+      // 在后台当前配置的基础上合并，其他窗口的修改和界面没有涉及的配置项都会保留
+      const current = await invoke<MonitorConfig>('get_monitor_config')
+      config.value = { ...current, ...newConfig }
       await invoke('update_monitor_config', { config: config.value })
     } catch (err) {
       error.value = err as string
@@ -272,12 +573,28 @@ export const useSystemStore = defineStore('system', () => {
 
   const startMonitoring = async () => {
     isMonitoring.value = true
+    // 先订阅再读取缓存的样本，避免错过两者之间推送的数据
+    if (!systemInfoUnlisten) {
+      systemInfoUnlisten = await listen<SystemInfoDelta>(SYSTEM_INFO_EVENT, (event) => {
+        applySystemInfoDelta(event.payload)
+      })
+    }
+    if (!gpuStatusUnlisten) {
+      gpuStatusUnlisten = await listen<GpuStatusChange>(GPU_STATUS_EVENT, (event) => {
+        handleGpuStatusChange(event.payload)
+      })
+    }
     await fetchSystemInfo()
     await fetchGpuInfo()
+    await fetchConfig()
   }
 
   const stopMonitoring = () => {
     isMonitoring.value = false
+    systemInfoUnlisten?.()
+    systemInfoUnlisten = null
+    gpuStatusUnlisten?.()
+    gpuStatusUnlisten = null
   }
 
   const toggleMonitoring = () => {
@@ -300,13 +617,25 @@ export const useSystemStore = defineStore('system', () => {
   }
 
   // GPU监控相关方法
-  const getGpuMonitorStatus = async (): Promise<[boolean, string | null]> => {
+  const getGpuMonitorStatus = async (): Promise<GpuMonitorStatus> => {
     try {
-      const status = await invoke<[boolean, string | null]>('get_gpu_monitor_status')
+      const status = await invoke<GpuMonitorStatus>('get_gpu_monitor_status')
+      gpuStatus.value = status
       return status
     } catch (err) {
       console.error('获取GPU监控状态失败:', err)
-      return [false, `获取状态失败: ${err}`]
+      return { available: false, error: `获取状态失败: ${err}`, devices: [] }
+    }
+  }
+
+  // 驱动加载、外接显卡插拔后刷新GPU状态和设备列表
+  const handleGpuStatusChange = async (change: GpuStatusChange) => {
+    console.info(`GPU设备变化: 新增 ${change.added.length} 个，移除 ${change.removed.length} 个`)
+    await getGpuMonitorStatus()
+    if (change.available) {
+      await fetchGpuInfo()
+    } else {
+      gpus.value = []
     }
   }
 
@@ -320,13 +649,22 @@ export const useSystemStore = defineStore('system', () => {
     }
   }
 
-  const getDetailedGpuInfo = async (deviceIndex: number = 0): Promise<string> => {
+  const getDetailedGpuInfo = async (deviceIndex: number = 0): Promise<GpuDetails | null> => {
     try {
-      const info = await invoke<string>('get_detailed_gpu_info', { deviceIndex })
+      const info = await invoke<GpuDetails>('get_detailed_gpu_info', { deviceIndex })
       return info
     } catch (err) {
       console.error('获取详细GPU信息失败:', err)
-      return 'GPU监控不可用'
+      return null
+    }
+  }
+
+  const getGpuProcesses = async (deviceIndex: number = 0): Promise<GpuProcessInfo[]> => {
+    try {
+      return await invoke<GpuProcessInfo[]>('get_gpu_processes', { deviceIndex })
+    } catch (err) {
+      console.error('获取GPU进程失败:', err)
+      return []
     }
   }
 
@@ -346,6 +684,7 @@ export const useSystemStore = defineStore('system', () => {
   return {
     // 状态
     systemInfo,
+    gpus,
     gpuInfo,
     isMonitoring,
     lastUpdate,
@@ -353,6 +692,7 @@ export const useSystemStore = defineStore('system', () => {
     error,
     frameStats,
     frameError,
+    gpuStatus,
 
     // 计算属性
     memoryUsageText,
@@ -365,7 +705,9 @@ export const useSystemStore = defineStore('system', () => {
     fetchSystemInfo,
     fetchGpuInfo,
     fetchFrameStats,
+    applySystemInfoDelta,
     getCurrentData,
+    fetchConfig,
     updateConfig,
     startMonitoring,
     stopMonitoring,
@@ -377,6 +719,7 @@ export const useSystemStore = defineStore('system', () => {
     // GPU监控相关方法
     getGpuMonitorStatus,
     getGpuNames,
-    getDetailedGpuInfo
+    getDetailedGpuInfo,
+    getGpuProcesses
   }
 })